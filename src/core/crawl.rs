@@ -1,9 +1,50 @@
-use super::document::{extract_metadata, Document};
-use super::export::Exporter;
-use super::html_to_md::parser;
-use super::link_extractor::extract_links;
-use super::url_manager::UrlManager;
+use super::document::{
+    extract_metadata, hash_content, Document, ExtractionInfo, Metadata, RobotsDirectives,
+};
+use super::error::CrawlError;
+use super::export::{DocumentSink, Exporter, OutputFormat, SharedSink, DEFAULT_MAX_FILENAME_LEN};
+use super::feed::parse_feed;
+use super::html_to_md::{
+    parser, resolve_noscript, sanitize_html, strip_boilerplate, truncate_html, visible_text,
+};
+use super::link_extractor::{
+    apply_default_scheme, extract_anchor_texts, extract_assets, extract_links_with_options,
+    normalize_url, LinkOptions,
+};
+use super::readability::extract_main;
+use super::url_manager::{
+    extract_domain, normalize_url_for_storage, CrawlStrategy, NormalizeOptions, UrlManager,
+    UrlRewriter,
+};
+use super::webshooter::{
+    fetch_following_redirects, FetchOptions, FetchResponse, DEFAULT_MAX_REDIRECTS,
+    DEFAULT_MAX_RETRIES, DEFAULT_MAX_RETRY_AFTER,
+};
 use super::Spiderman;
+use async_std::channel::{self, Receiver, Sender};
+use async_std::task::JoinHandle;
+use futures::future::join_all;
+use regex::Regex;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Default capacity of the queue feeding a `DocumentSink`
+pub const DEFAULT_MAX_BUFFERED_DOCUMENTS: usize = 100;
+
+/// How long a paused crawl sleeps between checks of its `CrawlControl`
+const PAUSE_POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Scheme given to seeds and links that have none
+///
+/// Not configurable: the fetcher only speaks plain HTTP.
+const DEFAULT_SCHEME: &str = "http";
 
 /// Configuration for the web crawler
 ///
@@ -24,31 +65,449 @@ pub struct CrawlConfig {
     /// Maximum number of pages to crawl (None = unlimited)
     pub max_pages: Option<usize>,
 
+    /// Whether failed fetches use up `max_pages` (false = keep crawling
+    /// until `max_pages` pages succeeded)
+    pub budget_counts_failures: bool,
+
     /// List of allowed domains (None = all domains)
     pub allowed_domains: Option<Vec<String>>,
 
+    /// Maximum number of distinct hosts to crawl (None = unlimited)
+    pub max_hosts: Option<usize>,
+
+    /// Maximum number of pages per link depth, indexed by depth (None =
+    /// unlimited)
+    pub per_depth_limits: Option<Vec<usize>>,
+
+    /// Order in which queued URLs are crawled
+    pub strategy: CrawlStrategy,
+
+    /// Maximum number of times the query of one path may grow
+    /// (None = unlimited)
+    pub max_query_growth: Option<usize>,
+
     /// Output directory for exported documents
     pub output_dir: String,
 
-    /// Output filename for JSONL export
+    /// Output filename
     pub output_file: String,
 
+    /// Format of the output file
+    pub output_format: OutputFormat,
+
+    /// Whether each domain's documents go to their own `<domain>.<ext>`
+    /// file instead of `output_file`
+    pub split_output_by_domain: bool,
+
     /// Whether to store raw HTML in documents
     pub store_raw_html: bool,
 
+    /// Whether stored raw HTML is stripped of scripts, event handlers and
+    /// `javascript:` URLs
+    pub sanitize_raw_html: bool,
+
     /// Whether to print progress during crawl
     pub verbose: bool,
+
+    /// Whether to send the discovering page's URL as the `Referer` header
+    pub send_referer: bool,
+
+    /// `Accept` header sent with every request (None = `DEFAULT_ACCEPT`,
+    /// which prefers HTML)
+    pub accept: Option<String>,
+
+    /// Whether a `<meta charset>` early in a page overrides a conflicting
+    /// `Content-Type` charset
+    pub respect_meta_charset_override: bool,
+
+    /// Non-2xx status codes whose pages are stored instead of counted as
+    /// failures (None = only 2xx responses are stored)
+    pub store_status_codes: Option<Vec<u16>>,
+
+    /// Number of failures after which the crawl is aborted (None = never abort)
+    pub max_failures: Option<usize>,
+
+    /// Whether `max_failures` counts total or consecutive failures
+    pub failure_count: FailureCount,
+
+    /// Maximum title length in characters (None = no limit)
+    pub max_title_len: Option<usize>,
+
+    /// Maximum description length in characters (None = no limit)
+    pub max_description_len: Option<usize>,
+
+    /// Whether to strip navigation, headers, footers and asides before
+    /// converting to Markdown
+    pub strip_boilerplate: bool,
+
+    /// Whether `<noscript>` content is converted or dropped (None = left to
+    /// the converter, which keeps it as raw text)
+    pub include_noscript: Option<bool>,
+
+    /// Whether only the main article, found by `readability::extract_main`,
+    /// is converted
+    pub readability: bool,
+
+    /// Handle for pausing, resuming or stopping the crawl (None = always run)
+    pub control: Option<CrawlControl>,
+
+    /// Maximum number of redirects followed per page
+    pub max_redirects: usize,
+
+    /// Rewrite applied to normalized URLs before deduplication and queuing
+    /// (None = no rewriting)
+    pub url_rewriter: Option<UrlRewriter>,
+
+    /// Query parameter handling used when deduplicating URLs
+    pub normalize_options: NormalizeOptions,
+
+    /// Whether `rel="next"` pagination links are crawled before other links
+    pub prioritize_next: bool,
+
+    /// Minimum Markdown content length in bytes for a page to be kept
+    /// (None = keep every page)
+    pub min_content_length: Option<usize>,
+
+    /// Politeness delay between requests (None = no delay)
+    pub delay: Option<Duration>,
+
+    /// Fraction (0.0–1.0) by which each delay is randomly lengthened or
+    /// shortened
+    pub delay_jitter: f64,
+
+    /// Seed for the jitter random numbers (None = seeded from the clock)
+    pub jitter_seed: Option<u64>,
+
+    /// Port used for URLs without an explicit port (None = scheme default)
+    pub default_port: Option<u16>,
+
+    /// Directory where non-HTML resources (PDFs, images, ...) are saved
+    /// (None = treat every response as HTML)
+    pub save_assets_dir: Option<String>,
+
+    /// Maximum length of file names derived from URLs (e.g. saved assets)
+    pub max_filename_len: usize,
+
+    /// Media types that are processed, with `*` wildcards such as `text/*`
+    /// (None = all)
+    pub allowed_content_types: Option<Vec<String>>,
+
+    /// File that request and response headers are appended to, for
+    /// debugging (None = no log)
+    pub http_log: Option<String>,
+
+    /// Whether the `action` targets of GET forms are crawled
+    pub follow_forms: bool,
+
+    /// Whether the RSS/Atom feeds pages declare are read for article URLs
+    pub follow_feeds: bool,
+
+    /// Whether documents list the scripts, stylesheets and images their
+    /// page references
+    pub inventory_assets: bool,
+
+    /// Pages whose URL matches one of these are exported, but their links
+    /// are not queued
+    pub no_discover_patterns: Vec<Regex>,
+
+    /// Phrases marking a page as a login wall or paywall (matched
+    /// case-insensitively against the page text)
+    pub paywall_signals: Vec<String>,
+
+    /// Whether links on gated pages are left unqueued
+    pub skip_gated_links: bool,
+
+    /// Whether pages whose canonical URL was already exported are skipped
+    pub canonical_dedup: bool,
+
+    /// Maximum number of fetches in flight at once
+    pub concurrency: usize,
+
+    /// Whether the number of fetches in flight follows the recent success
+    /// rate, between 1 and `concurrency`
+    pub adaptive_concurrency: bool,
+
+    /// How often a 429/503 response with `Retry-After` is retried
+    pub max_retries: usize,
+
+    /// Longest `Retry-After` wait honored before a retry
+    pub max_retry_after: Duration,
+
+    /// Minimum Markdown-to-HTML size ratio for a page to be kept
+    /// (None = keep every page)
+    pub min_text_density: Option<f32>,
+
+    /// HTML size in bytes above which a page is truncated before Markdown
+    /// conversion (None = convert whole pages)
+    pub max_html_bytes_for_conversion: Option<usize>,
+
+    /// Custom destination for documents (None = export to `output_file`)
+    pub sink: Option<SharedSink>,
+
+    /// Maximum number of documents waiting for the sink
+    pub max_buffered_documents: usize,
+
+    /// Number of JSONL documents appended to the output file at once
+    /// (1 = write each page as soon as it is crawled)
+    pub export_batch_size: usize,
+
+    /// Channel receiving progress events (None = no events)
+    pub events: Option<Sender<CrawlEvent>>,
+
+    /// Hook run once with the final result when the crawl ends (None = no
+    /// hook)
+    pub on_finish: Option<FinishHook>,
+}
+
+/// Progress event emitted while a crawl runs
+///
+/// Sent to the channel set with `CrawlConfig::with_events`, or returned by
+/// `Spiderman::crawl_with_progress`.
+#[derive(Debug, Clone)]
+pub enum CrawlEvent {
+    /// A page (or asset) was fetched and processed
+    PageCrawled {
+        /// Final URL of the page, after redirects
+        url: String,
+
+        /// HTTP status code of the response
+        status: u16,
+    },
+
+    /// A page could not be fetched or saved
+    PageFailed {
+        /// URL that was requested
+        url: String,
+
+        /// Description of the failure
+        error: String,
+    },
+
+    /// Adaptive concurrency changed the number of parallel fetches (see
+    /// `CrawlConfig::with_adaptive_concurrency`)
+    ConcurrencyChanged {
+        /// The new number of parallel fetches
+        concurrency: usize,
+    },
+
+    /// The crawl ended (completed, stopped or aborted); always the last event
+    Finished(CrawlResult),
+}
+
+/// Lifecycle state of a crawl, as seen through a `CrawlControl`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrawlState {
+    /// Pages are being fetched
+    Running,
+
+    /// No new fetches are dispatched until resumed; the queue is kept
+    Paused,
+
+    /// The crawl finishes after the current page
+    Stopped,
+}
+
+/// Shared handle for pausing, resuming and stopping an in-flight crawl
+///
+/// Clones share the same state, so a clone can be kept by the caller while
+/// another is passed in via `CrawlConfig::with_control`. The crawl loop
+/// checks the state before each page.
+///
+/// # Examples
+///
+/// ```no_run
+/// use spiderman::core::{CrawlConfig, CrawlControl};
+///
+/// let control = CrawlControl::new();
+/// let config = CrawlConfig::default().with_control(control.clone());
+///
+/// // From another thread or task:
+/// control.pause();
+/// control.resume();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CrawlControl {
+    /// Current `CrawlState`, encoded as a `u8`
+    state: Arc<AtomicU8>,
+}
+
+impl CrawlControl {
+    const RUNNING: u8 = 0;
+    const PAUSED: u8 = 1;
+    const STOPPED: u8 = 2;
+
+    /// Creates a handle in the running state
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pauses the crawl after the page currently being fetched
+    ///
+    /// Has no effect once the crawl is stopped.
+    pub fn pause(&self) {
+        let _ = self.state.compare_exchange(
+            Self::RUNNING,
+            Self::PAUSED,
+            Ordering::SeqCst,
+            Ordering::SeqCst,
+        );
+    }
+
+    /// Resumes a paused crawl
+    ///
+    /// Has no effect once the crawl is stopped.
+    pub fn resume(&self) {
+        let _ = self.state.compare_exchange(
+            Self::PAUSED,
+            Self::RUNNING,
+            Ordering::SeqCst,
+            Ordering::SeqCst,
+        );
+    }
+
+    /// Stops the crawl after the page currently being fetched
+    pub fn stop(&self) {
+        self.state.store(Self::STOPPED, Ordering::SeqCst);
+    }
+
+    /// Returns the current state
+    pub fn state(&self) -> CrawlState {
+        match self.state.load(Ordering::SeqCst) {
+            Self::RUNNING => CrawlState::Running,
+            Self::PAUSED => CrawlState::Paused,
+            _ => CrawlState::Stopped,
+        }
+    }
+
+    /// Waits while paused and returns true if the crawl should continue
+    async fn wait_while_paused(&self) -> bool {
+        loop {
+            match self.state() {
+                CrawlState::Running => return true,
+                CrawlState::Stopped => return false,
+                CrawlState::Paused => async_std::task::sleep(PAUSE_POLL_INTERVAL).await,
+            }
+        }
+    }
+}
+
+/// A closure run once when a crawl ends
+///
+/// Wraps the closure so it can be stored in (and cloned along with)
+/// `CrawlConfig`. Clones share the closure, so it runs at most once even if
+/// the config is reused.
+///
+/// # Examples
+///
+/// ```
+/// use spiderman::core::FinishHook;
+///
+/// let hook = FinishHook::new(|result| println!("{} pages", result.pages_crawled));
+/// ```
+#[derive(Clone)]
+pub struct FinishHook(Arc<Mutex<Option<FinishFn>>>);
+
+/// The closure wrapped by a `FinishHook`
+type FinishFn = Box<dyn FnOnce(&CrawlResult) + Send>;
+
+impl FinishHook {
+    /// Creates a hook from a closure
+    pub fn new<F>(hook: F) -> Self
+    where
+        F: FnOnce(&CrawlResult) + Send + 'static,
+    {
+        Self(Arc::new(Mutex::new(Some(Box::new(hook)))))
+    }
+
+    /// Runs the closure, unless it already ran
+    fn run(&self, result: &CrawlResult) {
+        let hook = match self.0.lock() {
+            Ok(mut hook) => hook.take(),
+            Err(_) => None,
+        };
+        if let Some(hook) = hook {
+            hook(result);
+        }
+    }
+}
+
+impl fmt::Debug for FinishHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("FinishHook(..)")
+    }
+}
+
+/// How failures are counted against `CrawlConfig::max_failures`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FailureCount {
+    /// Every failed page counts, for the whole crawl
+    #[default]
+    Total,
+
+    /// Only failures in a row count; a successful page resets the count
+    Consecutive,
 }
 
 impl Default for CrawlConfig {
     fn default() -> Self {
         Self {
             max_pages: Some(50),
+            budget_counts_failures: true,
             allowed_domains: None,
+            max_hosts: None,
+            per_depth_limits: None,
+            strategy: CrawlStrategy::default(),
+            max_query_growth: None,
             output_dir: "output".to_string(),
             output_file: "crawl.jsonl".to_string(),
+            output_format: OutputFormat::Jsonl,
+            split_output_by_domain: false,
             store_raw_html: false,
+            sanitize_raw_html: false,
             verbose: true,
+            send_referer: false,
+            accept: None,
+            store_status_codes: None,
+            max_failures: None,
+            failure_count: FailureCount::Total,
+            max_title_len: None,
+            max_description_len: None,
+            strip_boilerplate: false,
+            include_noscript: None,
+            readability: false,
+            control: None,
+            max_redirects: DEFAULT_MAX_REDIRECTS,
+            url_rewriter: None,
+            normalize_options: NormalizeOptions::default(),
+            prioritize_next: false,
+            min_content_length: None,
+            delay: None,
+            delay_jitter: 0.0,
+            jitter_seed: None,
+            default_port: None,
+            save_assets_dir: None,
+            max_filename_len: DEFAULT_MAX_FILENAME_LEN,
+            allowed_content_types: None,
+            http_log: None,
+            follow_forms: false,
+            follow_feeds: false,
+            respect_meta_charset_override: false,
+            inventory_assets: false,
+            no_discover_patterns: Vec::new(),
+            paywall_signals: Vec::new(),
+            skip_gated_links: false,
+            canonical_dedup: false,
+            concurrency: 1,
+            adaptive_concurrency: false,
+            max_retries: DEFAULT_MAX_RETRIES,
+            max_retry_after: DEFAULT_MAX_RETRY_AFTER,
+            min_text_density: None,
+            max_html_bytes_for_conversion: None,
+            sink: None,
+            max_buffered_documents: DEFAULT_MAX_BUFFERED_DOCUMENTS,
+            export_batch_size: 1,
+            events: None,
+            on_finish: None,
         }
     }
 }
@@ -65,12 +524,81 @@ impl CrawlConfig {
         self
     }
 
+    /// Sets whether failed fetches count towards `max_pages`
+    ///
+    /// By default every fetched URL uses up the budget. When disabled, the
+    /// crawl keeps going until `max_pages` pages were crawled successfully
+    /// (or the queue runs dry), however many fetches fail on the way.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spiderman::core::CrawlConfig;
+    ///
+    /// // Collect 20 documents, skipping over broken links
+    /// let config = CrawlConfig::new()
+    ///     .with_max_pages(20)
+    ///     .with_budget_counts_failures(false);
+    /// ```
+    pub fn with_budget_counts_failures(mut self, counts: bool) -> Self {
+        self.budget_counts_failures = counts;
+        self
+    }
+
+    /// Returns the budget of successful pages enforced by the crawl loop
+    ///
+    /// Only set when failures don't count; otherwise the URL manager
+    /// enforces `max_pages` on every fetched URL.
+    fn success_budget(&self) -> Option<usize> {
+        if self.budget_counts_failures {
+            None
+        } else {
+            self.max_pages
+        }
+    }
+
     /// Sets the allowed domains
     pub fn with_allowed_domains(mut self, domains: Vec<String>) -> Self {
         self.allowed_domains = Some(domains);
         self
     }
 
+    /// Sets the maximum number of distinct hosts to crawl
+    ///
+    /// Links to a new host are ignored once `max` hosts have been seen;
+    /// pages on the hosts already seen are still crawled.
+    pub fn with_max_hosts(mut self, max: usize) -> Self {
+        self.max_hosts = Some(max);
+        self
+    }
+
+    /// Limits chains of same-path URLs with growing query strings
+    ///
+    /// See `UrlManager::set_max_query_growth`.
+    pub fn with_max_query_growth(mut self, max: usize) -> Self {
+        self.max_query_growth = Some(max);
+        self
+    }
+
+    /// Caps the number of pages crawled at each link depth
+    ///
+    /// `limits[0]` applies to the seed, `limits[1]` to the pages it links
+    /// to, and so on; deeper levels are unlimited. See
+    /// `UrlManager::set_per_depth_limits`.
+    pub fn with_per_depth_limits(mut self, limits: Vec<usize>) -> Self {
+        self.per_depth_limits = Some(limits);
+        self
+    }
+
+    /// Sets the order in which queued URLs are crawled
+    ///
+    /// `CrawlStrategy::RoundRobinHosts` takes one URL per host in turn,
+    /// which spreads requests when one host dominates the queue.
+    pub fn with_strategy(mut self, strategy: CrawlStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
     /// Sets the output directory
     pub fn with_output_dir(mut self, dir: &str) -> Self {
         self.output_dir = dir.to_string();
@@ -83,323 +611,5631 @@ impl CrawlConfig {
         self
     }
 
+    /// Sets the output format
+    ///
+    /// JSONL is appended page by page; JSON array, CSV and XML files are
+    /// written once the crawl finishes. Remember to pick a matching
+    /// `output_file` extension.
+    pub fn with_output_format(mut self, format: OutputFormat) -> Self {
+        self.output_format = format;
+        self
+    }
+
+    /// Writes each domain's documents to its own file
+    ///
+    /// Files are named after the domain, with the extension of
+    /// `output_file` (e.g. `example.com.jsonl`).
+    pub fn with_split_output_by_domain(mut self, split: bool) -> Self {
+        self.split_output_by_domain = split;
+        self
+    }
+
     /// Enables storing raw HTML in documents
     pub fn with_raw_html(mut self, store: bool) -> Self {
         self.store_raw_html = store;
         self
     }
 
+    /// Enables sanitizing raw HTML before it is stored
+    ///
+    /// Only has an effect together with `with_raw_html(true)`.
+    pub fn with_sanitize_raw_html(mut self, sanitize: bool) -> Self {
+        self.sanitize_raw_html = sanitize;
+        self
+    }
+
     /// Enables or disables verbose output
     pub fn with_verbose(mut self, verbose: bool) -> Self {
         self.verbose = verbose;
         self
     }
-}
-
-/// Result of a crawl operation
-///
-/// Contains statistics and the list of crawled documents.
-#[derive(Debug, Clone)]
-pub struct CrawlResult {
-    /// Number of pages successfully crawled
-    pub pages_crawled: usize,
 
-    /// Number of pages that failed
-    pub pages_failed: usize,
+    /// Enables sending the parent page's URL as the `Referer` header
+    ///
+    /// The seed URL is always fetched without a `Referer`.
+    pub fn with_referer(mut self, send: bool) -> Self {
+        self.send_referer = send;
+        self
+    }
 
-    /// Total number of unique URLs discovered
-    pub urls_discovered: usize,
+    /// Sets the `Accept` header sent with every request
+    ///
+    /// The default asks for HTML; crawling a JSON API or feeds needs e.g.
+    /// `"application/json"`. JSON and feed responses are stored verbatim
+    /// rather than converted to Markdown, whatever was asked for.
+    pub fn with_accept(mut self, accept: &str) -> Self {
+        self.accept = Some(accept.to_string());
+        self
+    }
 
-    /// List of all crawled documents
-    pub documents: Vec<Document>,
-}
+    /// Sets whether a page's `<meta charset>` overrides its header charset
+    ///
+    /// Some servers label every page `charset=utf-8` even when it is stored
+    /// as e.g. Windows-1252, which garbles accented characters. When
+    /// enabled, an HTML body whose `<meta charset>` (within its first 1024
+    /// bytes) names a different encoding is decoded again with that one.
+    pub fn with_respect_meta_charset_override(mut self, respect: bool) -> Self {
+        self.respect_meta_charset_override = respect;
+        self
+    }
 
-impl<'a> Spiderman<'a> {
-    /// Crawls the website starting from the seed URL
+    /// Sets additional status codes whose pages should be stored
     ///
-    /// This is the main entry point for the crawler. It orchestrates all components
-    /// to fetch, parse, and export web pages.
+    /// Useful for archival crawls that also want to record error pages
+    /// (e.g. `vec![404, 410]`).
+    pub fn with_store_status_codes(mut self, codes: Vec<u16>) -> Self {
+        self.store_status_codes = Some(codes);
+        self
+    }
+
+    /// Sets the number of failures after which the crawl is aborted
     ///
-    /// # How It Works
+    /// Once reached, `crawl` stops and returns `CrawlError::TooManyFailures`
+    /// carrying the partial result.
+    pub fn with_max_failures(mut self, max: usize) -> Self {
+        self.max_failures = Some(max);
+        self
+    }
+
+    /// Sets whether `max_failures` counts total or consecutive failures
+    pub fn with_failure_count(mut self, count: FailureCount) -> Self {
+        self.failure_count = count;
+        self
+    }
+
+    /// Sets the maximum title length
     ///
-    /// 1. Initialize URL Manager with seed URL
-    /// 2. Loop while there are URLs to crawl:
-    ///    a. Get next URL from queue
-    ///    b. Fetch HTML content
-    ///    c. Extract links and add to queue
-    ///    d. Convert HTML to Markdown
-    ///    e. Extract metadata
-    ///    f. Create Document
-    ///    g. Export Document
-    /// 3. Return crawl results
+    /// Longer titles are cut at a word boundary and end with an ellipsis.
+    pub fn with_max_title_len(mut self, max: usize) -> Self {
+        self.max_title_len = Some(max);
+        self
+    }
+
+    /// Sets the maximum description length
     ///
-    /// # Arguments
+    /// Longer descriptions are cut at a word boundary and end with an ellipsis.
+    pub fn with_max_description_len(mut self, max: usize) -> Self {
+        self.max_description_len = Some(max);
+        self
+    }
+
+    /// Enables stripping page boilerplate before conversion
     ///
-    /// * `config` - Crawl configuration options
+    /// Removes `<nav>`, `<header>`, `<footer>` and `<aside>` elements so the
+    /// Markdown holds only the main content. Links are still extracted from
+    /// the full page.
+    pub fn with_strip_boilerplate(mut self, strip: bool) -> Self {
+        self.strip_boilerplate = strip;
+        self
+    }
+
+    /// Sets whether `<noscript>` content is included in the Markdown
     ///
-    /// # Returns
+    /// We don't run JavaScript, so pages that put their real content in
+    /// `<noscript>` need this enabled: the content is then converted like
+    /// the rest of the page. When disabled, `<noscript>` elements are
+    /// dropped before conversion. Unless set, `<noscript>` content is left
+    /// to the converter, which keeps it as raw text, markup included.
+    pub fn with_include_noscript(mut self, include: bool) -> Self {
+        self.include_noscript = Some(include);
+        self
+    }
+
+    /// Enables converting only the page's main article
     ///
-    /// * `Ok(CrawlResult)` - Successful crawl with statistics
-    /// * `Err` - If a critical error occurs
+    /// The article is found by scoring containers by their prose and link
+    /// density (see `readability::extract_main`), which also works on pages
+    /// without `<nav>`/`<aside>` markup. Pages where no article is found
+    /// are converted whole. Links are still extracted from the full page.
+    pub fn with_readability(mut self, readability: bool) -> Self {
+        self.readability = readability;
+        self
+    }
+
+    /// Sets a handle for pausing, resuming or stopping the crawl
+    pub fn with_control(mut self, control: CrawlControl) -> Self {
+        self.control = Some(control);
+        self
+    }
+
+    /// Sends progress events to a channel while crawling
     ///
-    /// # Examples
+    /// Events are sent without waiting, so use an unbounded channel (or one
+    /// large enough for the whole crawl); events that don't fit are dropped.
+    pub fn with_events(mut self, sender: Sender<CrawlEvent>) -> Self {
+        self.events = Some(sender);
+        self
+    }
+
+    /// Sets a hook run once with the final result when the crawl ends
     ///
-    /// ```no_run
-    /// use spiderman::core::{Spiderman, CrawlConfig};
+    /// The hook runs after documents are exported, whether the crawl
+    /// completed, was stopped through its `CrawlControl` or was aborted by
+    /// `max_failures`, which makes it the place to trigger indexing,
+    /// notifications or moving the output files.
     ///
-    /// async_std::task::block_on(async {
-    ///     let mut spider = Spiderman::new("example.com");
-    ///     let config = CrawlConfig::default().with_max_pages(10);
-    ///     let result = spider.crawl(config).await.unwrap();
+    /// ```
+    /// use spiderman::core::CrawlConfig;
     ///
+    /// let config = CrawlConfig::default().with_on_finish(|result| {
     ///     println!("Crawled {} pages", result.pages_crawled);
     /// });
     /// ```
-    pub async fn crawl(
-        &mut self,
-        config: CrawlConfig,
-    ) -> Result<CrawlResult, Box<dyn std::error::Error>> {
-        if config.verbose {
-            println!("🕷️  Starting Spiderman Web Crawler");
-            println!("📍 Seed URL: {}", self.url);
-            println!("📁 Output: {}/{}", config.output_dir, config.output_file);
-            if let Some(max) = config.max_pages {
-                println!("📊 Max pages: {}", max);
-            }
-            println!();
-        }
-
-        // Initialize URL Manager
-        let mut manager = UrlManager::new(self.url);
+    pub fn with_on_finish<F>(mut self, hook: F) -> Self
+    where
+        F: FnOnce(&CrawlResult) + Send + 'static,
+    {
+        self.on_finish = Some(FinishHook::new(hook));
+        self
+    }
 
-        // Configure URL Manager
-        if let Some(max) = config.max_pages {
+    /// Sets the maximum number of redirects followed per page
+    ///
+    /// A page redirecting more often than this counts as a failure.
+    pub fn with_max_redirects(mut self, max: usize) -> Self {
+        self.max_redirects = max;
+        self
+    }
+
+    /// Sets a rewrite applied to every URL before deduplication and queuing
+    ///
+    /// The rewrite runs on the normalized URL and its output is normalized
+    /// again, so e.g. a vanity domain can be mapped onto the canonical one:
+    ///
+    /// ```
+    /// use spiderman::core::CrawlConfig;
+    ///
+    /// let config = CrawlConfig::default().with_url_rewriter(|url| {
+    ///     url.replace("://vanity.example.com", "://example.com")
+    /// });
+    /// ```
+    pub fn with_url_rewriter<F>(mut self, rewrite: F) -> Self
+    where
+        F: Fn(&str) -> String + Send + Sync + 'static,
+    {
+        self.url_rewriter = Some(UrlRewriter::new(rewrite));
+        self
+    }
+
+    /// Sets how query parameters are handled when deduplicating URLs
+    ///
+    /// The options are handed to the crawl's `UrlManager`, which applies
+    /// them to every URL it queues or looks up.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spiderman::core::url_manager::NormalizeOptions;
+    /// use spiderman::core::CrawlConfig;
+    ///
+    /// // `?a=1&b=2` and `?b=2&a=1` are crawled once
+    /// let config = CrawlConfig::new()
+    ///     .with_normalize_options(NormalizeOptions::new().with_sort_params(true));
+    /// ```
+    pub fn with_normalize_options(mut self, options: NormalizeOptions) -> Self {
+        self.normalize_options = options;
+        self
+    }
+
+    /// Enables priority mode for pagination
+    ///
+    /// A page's `rel="next"` link is moved to the front of the queue so a
+    /// paginated series is traversed before other discovered links.
+    pub fn with_prioritize_next(mut self, prioritize: bool) -> Self {
+        self.prioritize_next = prioritize;
+        self
+    }
+
+    /// Sets the minimum Markdown content length for a page to be kept
+    ///
+    /// Shorter pages (nav stubs, redirect shims) still have their links
+    /// followed and count as crawled, but are neither exported nor included
+    /// in `CrawlResult::documents`.
+    pub fn with_min_content_length(mut self, min: usize) -> Self {
+        self.min_content_length = Some(min);
+        self
+    }
+
+    /// Sets the minimum text density for a page to be kept
+    ///
+    /// Text density is the Markdown size divided by the HTML size (see
+    /// `Document::text_density`). Pages below the threshold — mostly markup,
+    /// such as huge inlined script bundles — are flagged as low-content and
+    /// skipped like pages below `min_content_length`.
+    pub fn with_min_text_density(mut self, min: f32) -> Self {
+        self.min_text_density = Some(min);
+        self
+    }
+
+    /// Caps how much of a page's HTML is converted to Markdown
+    ///
+    /// Larger pages are cut at a tag boundary before conversion, which keeps
+    /// multi-megabyte pages from slowing the crawl down, and their documents
+    /// are marked `truncated`. Stored raw HTML is not affected.
+    pub fn with_max_html_bytes_for_conversion(mut self, max: usize) -> Self {
+        self.max_html_bytes_for_conversion = Some(max);
+        self
+    }
+
+    /// Sends documents to a custom sink instead of the output file
+    ///
+    /// The sink is written from a background thread. Documents handed to it
+    /// are not collected in `CrawlResult::documents`, so memory use stays
+    /// bounded by `max_buffered_documents`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use spiderman::core::document::Document;
+    /// use spiderman::core::export::DocumentSink;
+    /// use spiderman::core::CrawlConfig;
+    /// use std::io;
+    ///
+    /// struct Printer;
+    ///
+    /// impl DocumentSink for Printer {
+    ///     fn write_document(&mut self, document: &Document) -> io::Result<()> {
+    ///         println!("{}", document.url());
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// let config = CrawlConfig::new().with_sink(Printer);
+    /// ```
+    pub fn with_sink<S: DocumentSink + 'static>(mut self, sink: S) -> Self {
+        self.sink = Some(SharedSink::new(sink));
+        self
+    }
+
+    /// Sets how many documents may wait for the sink
+    ///
+    /// When the queue is full the crawl stops fetching until the sink
+    /// catches up. Values below 1 are treated as 1.
+    pub fn with_max_buffered_documents(mut self, max: usize) -> Self {
+        self.max_buffered_documents = max.max(1);
+        self
+    }
+
+    /// Sets how many JSONL documents are written to the output file at once
+    ///
+    /// Crawled pages are collected and appended together with
+    /// `Exporter::export_batch`, opening the file once per batch instead of
+    /// once per page. The last, partial batch is written when the crawl
+    /// ends. Values below 1 are treated as 1.
+    pub fn with_export_batch_size(mut self, size: usize) -> Self {
+        self.export_batch_size = size.max(1);
+        self
+    }
+
+    /// Sets the politeness delay between requests
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = Some(delay);
+        self
+    }
+
+    /// Sets the delay jitter fraction
+    ///
+    /// Each delay is multiplied by a random factor in
+    /// `[1 - jitter, 1 + jitter]` so requests don't follow a regular
+    /// pattern. Values are clamped to `0.0..=1.0`; NaN and infinite values
+    /// disable jitter.
+    pub fn with_delay_jitter(mut self, jitter: f64) -> Self {
+        self.delay_jitter = clamp_jitter(jitter);
+        self
+    }
+
+    /// Sets the seed for delay jitter, making the delays reproducible
+    pub fn with_jitter_seed(mut self, seed: u64) -> Self {
+        self.jitter_seed = Some(seed);
+        self
+    }
+
+    /// Sets the port used for URLs that don't name one
+    ///
+    /// Useful for staging or test servers listening on e.g. port 8080.
+    pub fn with_default_port(mut self, port: u16) -> Self {
+        self.default_port = Some(port);
+        self
+    }
+
+    /// Sets the directory where non-HTML resources are saved
+    ///
+    /// Responses whose `Content-Type` isn't HTML are written as raw bytes to
+    /// `<dir>/<slugified-url>`, and their document records the local path
+    /// (see `Document::asset_path`) with empty content.
+    pub fn with_save_assets_dir(mut self, dir: &str) -> Self {
+        self.save_assets_dir = Some(dir.to_string());
+        self
+    }
+
+    /// Sets the maximum length of file names derived from URLs
+    ///
+    /// Longer names are shortened and end with a hash of the URL (see
+    /// `slugify_url_with_max_len`).
+    pub fn with_max_filename_len(mut self, max: usize) -> Self {
+        self.max_filename_len = max;
+        self
+    }
+
+    /// Restricts which responses are processed by media type
+    ///
+    /// Rules may use `*` wildcards (`text/*`, `application/*+xml`, `*/*`;
+    /// see `mime_type_matches`). Other responses are skipped without being
+    /// stored, saved or counted as failures.
+    pub fn with_allowed_content_types(mut self, types: Vec<String>) -> Self {
+        self.allowed_content_types = Some(types);
+        self
+    }
+
+    /// Appends a transcript of every HTTP exchange to a file
+    ///
+    /// The request line and headers, and the response status line and
+    /// headers (not the body), are written for each fetch, including
+    /// redirect hops.
+    pub fn with_http_log(mut self, path: &str) -> Self {
+        self.http_log = Some(path.to_string());
+        self
+    }
+
+    /// Sets whether GET form `action` targets are queued like links
+    ///
+    /// POST forms are never followed.
+    pub fn with_follow_forms(mut self, follow: bool) -> Self {
+        self.follow_forms = follow;
+        self
+    }
+
+    /// Sets whether the RSS/Atom feeds declared by pages are followed
+    ///
+    /// Each feed a page links with `<link rel="alternate"
+    /// type="application/rss+xml">` (or Atom) is fetched once, and the
+    /// articles it lists are queued as links of that page. The feed itself
+    /// is not exported. Feeds on hosts outside `allowed_domains`/`max_hosts`
+    /// are skipped, and feed fetches wait `delay` like page fetches.
+    pub fn with_follow_feeds(mut self, follow: bool) -> Self {
+        self.follow_feeds = follow;
+        self
+    }
+
+    /// Sets whether each page's subresources are inventoried
+    ///
+    /// The `<script src>`, stylesheet and `<img src>` URLs of every page are
+    /// stored in `Document::assets`. They are only listed, never fetched.
+    pub fn with_inventory_assets(mut self, inventory: bool) -> Self {
+        self.inventory_assets = inventory;
+        self
+    }
+
+    /// Sets the patterns of pages that are crawled for content only
+    ///
+    /// A page whose final URL (after redirects) matches any pattern is
+    /// fetched and exported as usual, but none of its links are queued,
+    /// as if it had a `nofollow` robots directive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use regex::Regex;
+    /// use spiderman::core::CrawlConfig;
+    ///
+    /// let config = CrawlConfig::default()
+    ///     .with_no_discover_patterns(vec![Regex::new(r"/archive/").unwrap()]);
+    /// ```
+    pub fn with_no_discover_patterns(mut self, patterns: Vec<Regex>) -> Self {
+        self.no_discover_patterns = patterns;
+        self
+    }
+
+    /// Sets the phrases that mark a page as gated (login wall or paywall)
+    ///
+    /// A page whose visible text contains any phrase (ignoring case and
+    /// spacing), such as `"Sign in to continue"`, is still exported, but
+    /// flagged with `Document::is_gated`.
+    pub fn with_paywall_signals(mut self, signals: Vec<String>) -> Self {
+        self.paywall_signals = signals;
+        self
+    }
+
+    /// Sets whether links found on gated pages are left unqueued
+    ///
+    /// Login walls tend to link back to the login flow, so following them
+    /// mostly wastes budget.
+    pub fn with_skip_gated_links(mut self, skip: bool) -> Self {
+        self.skip_gated_links = skip;
+        self
+    }
+
+    /// Sets whether pages are deduplicated by their canonical URL
+    ///
+    /// A page whose `<link rel="canonical">` chain ends at an already
+    /// exported page is skipped (its links are still queued). Canonicals
+    /// that point in a cycle (A → B → A) are ignored with a warning, so
+    /// every page in the cycle is kept under its own URL.
+    ///
+    /// Pages are only compared with those exported before them, so a
+    /// duplicate crawled ahead of its canonical page is still kept.
+    pub fn with_canonical_dedup(mut self, dedup: bool) -> Self {
+        self.canonical_dedup = dedup;
+        self
+    }
+
+    /// Sets how many pages are fetched in parallel
+    ///
+    /// Fetches are polled from the crawl task itself, so this cap holds
+    /// however many worker threads the runtime has. The `async_std` pool
+    /// size is configured separately, through the `ASYNC_STD_THREAD_COUNT`
+    /// environment variable (read when the runtime starts). Values below 1
+    /// are treated as 1.
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Sets whether concurrency adapts to the error rate
+    ///
+    /// The crawl keeps track of the last fetches: when too many of them
+    /// fail (connection errors, 429 and 5xx responses) the number of
+    /// parallel fetches is halved, and while nearly all succeed it grows
+    /// by one again, up to `concurrency`. Each change is reported as a
+    /// `CrawlEvent::ConcurrencyChanged`.
+    pub fn with_adaptive_concurrency(mut self, adaptive: bool) -> Self {
+        self.adaptive_concurrency = adaptive;
+        self
+    }
+
+    /// Sets how often a throttled request is retried
+    ///
+    /// A 429 or 503 response with a `Retry-After` header (in seconds or as
+    /// an HTTP date) is fetched again after the requested wait. 0 disables
+    /// retries, so such responses fail right away.
+    pub fn with_max_retries(mut self, max: usize) -> Self {
+        self.max_retries = max;
+        self
+    }
+
+    /// Sets the longest `Retry-After` wait honored
+    ///
+    /// Servers asking for longer waits are retried after this duration.
+    pub fn with_max_retry_after(mut self, max: Duration) -> Self {
+        self.max_retry_after = max;
+        self
+    }
+
+    /// Returns the link extraction options for this config
+    pub fn link_options(&self) -> LinkOptions {
+        LinkOptions::new().with_forms(self.follow_forms)
+    }
+
+    /// Returns the fetch options (port, redirect limit, HTTP log) for this
+    /// config
+    pub fn fetch_options(&self) -> FetchOptions {
+        let options = FetchOptions {
+            default_port: self.default_port,
+            max_redirects: self.max_redirects,
+            http_log: self.http_log.clone(),
+            max_retries: self.max_retries,
+            max_retry_after: self.max_retry_after,
+            respect_meta_charset: self.respect_meta_charset_override,
+            ..FetchOptions::default()
+        };
+
+        match self.accept {
+            Some(ref accept) => options.with_header("Accept", accept),
+            None => options,
+        }
+    }
+
+    /// Returns the extraction details recorded on crawled documents
+    pub fn extraction_info(&self) -> ExtractionInfo {
+        ExtractionInfo {
+            stripped_boilerplate: self.strip_boilerplate,
+            ..ExtractionInfo::default()
+        }
+    }
+
+    /// Returns the output file a document at `url` is exported to
+    ///
+    /// `output_file`, unless output is split by domain.
+    fn output_file_for(&self, url: &str) -> String {
+        let domain = extract_domain(url).filter(|_| self.split_output_by_domain);
+
+        match domain {
+            Some(domain) => {
+                let extension = Path::new(&self.output_file)
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .unwrap_or("jsonl");
+                format!("{}.{}", domain, extension)
+            }
+            None => self.output_file.clone(),
+        }
+    }
+
+    /// Returns whether a response's media type is one the crawl keeps
+    fn allows_content_type(&self, response: &FetchResponse) -> bool {
+        self.allowed_content_types
+            .as_ref()
+            .is_none_or(|types| response.matches_content_type(types))
+    }
+
+    /// Returns whether a response is saved as a file instead of converted
+    fn saves_as_asset(&self, response: &FetchResponse) -> bool {
+        self.save_assets_dir.is_some() && !response.is_html()
+    }
+
+    /// Returns whether a page's HTML contains one of the paywall signals
+    fn is_gated(&self, html: &str) -> bool {
+        if self.paywall_signals.is_empty() {
+            return false;
+        }
+
+        let text = visible_text(html).to_lowercase();
+        self.paywall_signals.iter().any(|signal| {
+            let signal = signal.split_whitespace().collect::<Vec<_>>().join(" ");
+            !signal.is_empty() && text.contains(&signal.to_lowercase())
+        })
+    }
+
+    /// Returns whether links found on the page at `url` may be queued
+    fn discovers_links(&self, url: &str) -> bool {
+        !self.no_discover_patterns.iter().any(|re| re.is_match(url))
+    }
+
+    /// Returns the seed URL with `http://` prepended, if it has no scheme
+    fn seed_url(&self, seed: &str) -> String {
+        apply_default_scheme(seed, DEFAULT_SCHEME)
+    }
+
+    /// Normalizes the seed URL and checks that it can be crawled
+    ///
+    /// `http://` is prepended first, so `example.com` is accepted;
+    /// the result must use `http` or `https` and name a host. Like any
+    /// other URL, an `https` seed is fetched over plain HTTP.
+    pub(crate) fn validate_seed(&self, seed: &str) -> Result<String, CrawlError> {
+        let invalid = |reason: String| CrawlError::InvalidSeed {
+            url: seed.to_string(),
+            reason,
+        };
+
+        let url = self.seed_url(seed);
+        let (scheme, rest) = url
+            .split_once("://")
+            .ok_or_else(|| invalid("missing scheme".to_string()))?;
+        if !scheme.eq_ignore_ascii_case("http") && !scheme.eq_ignore_ascii_case("https") {
+            return Err(invalid(format!("unsupported scheme `{}`", scheme)));
+        }
+
+        let host = rest.split(['/', '?', '#']).next().unwrap_or("");
+        if extract_domain(&url).is_none() || host.is_empty() || host.starts_with(':') {
+            return Err(invalid("empty host".to_string()));
+        }
+        if host.contains(char::is_whitespace) {
+            return Err(invalid(format!("invalid host `{}`", host)));
+        }
+
+        Ok(url)
+    }
+
+    /// Sends a progress event, if an event channel is set
+    fn emit(&self, event: CrawlEvent) {
+        if let Some(ref events) = self.events {
+            // A full or closed channel only means nobody is listening
+            let _ = events.try_send(event);
+        }
+    }
+
+    /// Returns true if a page with the given status code should be stored
+    ///
+    /// 2xx responses are always stored; other codes only when listed in
+    /// `store_status_codes`.
+    pub fn should_store_status(&self, status: u16) -> bool {
+        (200..300).contains(&status)
+            || self
+                .store_status_codes
+                .as_ref()
+                .is_some_and(|codes| codes.contains(&status))
+    }
+}
+
+/// Result of a crawl operation
+///
+/// Contains statistics and the list of crawled documents.
+#[derive(Debug, Clone, Default)]
+pub struct CrawlResult {
+    /// Number of pages successfully crawled
+    pub pages_crawled: usize,
+
+    /// Number of pages that failed
+    pub pages_failed: usize,
+
+    /// Total number of unique URLs discovered
+    pub urls_discovered: usize,
+
+    /// List of all crawled documents
+    pub documents: Vec<Document>,
+
+    /// How each page compares to the previous run, keyed by final URL
+    ///
+    /// Only filled in by `Spiderman::recrawl`.
+    pub changes: HashMap<String, PageChange>,
+
+    /// Duration of every fetch (including redirects and failed fetches),
+    /// in the order the fetches completed
+    pub fetch_durations: Vec<Duration>,
+}
+
+/// How a re-crawled page compares to the previous run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PageChange {
+    /// The page was not part of the previous run
+    New,
+
+    /// The page's content changed since the previous run
+    Updated,
+
+    /// The server answered `304 Not Modified`, or the content hash matches
+    Unchanged,
+}
+
+impl CrawlResult {
+    /// Ranks URLs by how many crawled pages link to them
+    ///
+    /// Builds the in-degree of every link target from the crawled documents'
+    /// links. Each page counts at most once per target and links from a page
+    /// to itself are ignored. URLs are compared in normalized form.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - Maximum number of entries to return
+    ///
+    /// # Returns
+    ///
+    /// Up to `n` `(normalized_url, inlink_count)` pairs, most linked first
+    /// (ties ordered by URL)
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use spiderman::core::{CrawlConfig, Spiderman};
+    ///
+    /// async_std::task::block_on(async {
+    ///     let mut spider = Spiderman::new("example.com");
+    ///     let result = spider.crawl(CrawlConfig::default()).await.unwrap();
+    ///
+    ///     for (url, inlinks) in result.top_pages_by_inlinks(10) {
+    ///         println!("{} ← {} pages", url, inlinks);
+    ///     }
+    /// });
+    /// ```
+    pub fn top_pages_by_inlinks(&self, n: usize) -> Vec<(String, usize)> {
+        let mut inlinks: HashMap<String, usize> = HashMap::new();
+
+        for doc in &self.documents {
+            let source = normalize_url_for_storage(doc.url());
+            let targets: HashSet<String> = doc
+                .links()
+                .iter()
+                .map(|link| normalize_url_for_storage(link))
+                .filter(|target| *target != source)
+                .collect();
+
+            for target in targets {
+                *inlinks.entry(target).or_insert(0) += 1;
+            }
+        }
+
+        let mut ranked: Vec<(String, usize)> = inlinks.into_iter().collect();
+        ranked.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        ranked.truncate(n);
+        ranked
+    }
+
+    /// Groups the crawled documents by domain
+    ///
+    /// Documents are keyed by `extract_domain(doc.url())` and keep their
+    /// crawl order within each domain. Documents whose URL has no domain
+    /// are left out.
+    ///
+    /// # Returns
+    ///
+    /// A map from domain to that domain's documents
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use spiderman::core::{CrawlConfig, Spiderman};
+    ///
+    /// async_std::task::block_on(async {
+    ///     let mut spider = Spiderman::new("example.com");
+    ///     let result = spider.crawl(CrawlConfig::default()).await.unwrap();
+    ///
+    ///     for (domain, docs) in result.documents_by_domain() {
+    ///         println!("{}: {} pages", domain, docs.len());
+    ///     }
+    /// });
+    /// ```
+    pub fn documents_by_domain(&self) -> HashMap<String, Vec<&Document>> {
+        let mut by_domain: HashMap<String, Vec<&Document>> = HashMap::new();
+
+        for doc in &self.documents {
+            if let Some(domain) = extract_domain(doc.url()) {
+                by_domain.entry(domain).or_default().push(doc);
+            }
+        }
+
+        by_domain
+    }
+
+    /// Returns the 50th, 90th and 99th percentile fetch durations
+    ///
+    /// Uses the nearest-rank method on `fetch_durations`, so every
+    /// percentile is an actually observed duration.
+    ///
+    /// # Returns
+    ///
+    /// `(p50, p90, p99)`, all zero if nothing was fetched
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use spiderman::core::{CrawlConfig, Spiderman};
+    ///
+    /// async_std::task::block_on(async {
+    ///     let mut spider = Spiderman::new("example.com");
+    ///     let result = spider.crawl(CrawlConfig::default()).await.unwrap();
+    ///
+    ///     let (p50, p90, p99) = result.latency_percentiles();
+    ///     println!("p50 {:?}, p90 {:?}, p99 {:?}", p50, p90, p99);
+    /// });
+    /// ```
+    pub fn latency_percentiles(&self) -> (Duration, Duration, Duration) {
+        let mut durations = self.fetch_durations.clone();
+        durations.sort_unstable();
+
+        let percentile = |p: usize| {
+            // Nearest rank: the smallest value with at least p% at or below it
+            let rank = (p * durations.len()).div_ceil(100).max(1);
+            durations.get(rank - 1).copied().unwrap_or_default()
+        };
+
+        (percentile(50), percentile(90), percentile(99))
+    }
+
+    /// Combines this result with the result of another crawl shard
+    ///
+    /// Counts are summed and documents, change records and fetch durations
+    /// are concatenated (`self` first). Results only carry a count of
+    /// discovered URLs, so the union is estimated: URLs crawled by both
+    /// shards are counted once, other overlaps can't be detected. Call
+    /// `dedup_documents` to drop pages both shards exported.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The result to append
+    ///
+    /// # Returns
+    ///
+    /// The combined result
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use spiderman::core::{CrawlConfig, Spiderman};
+    ///
+    /// async_std::task::block_on(async {
+    ///     let mut blog = Spiderman::new("example.com/blog");
+    ///     let mut docs = Spiderman::new("example.com/docs");
+    ///     let config = CrawlConfig::default();
+    ///
+    ///     let mut result = blog
+    ///         .crawl(config.clone())
+    ///         .await
+    ///         .unwrap()
+    ///         .merge(docs.crawl(config).await.unwrap());
+    ///     result.dedup_documents();
+    /// });
+    /// ```
+    pub fn merge(mut self, other: CrawlResult) -> CrawlResult {
+        let crawled: HashSet<String> = self
+            .documents
+            .iter()
+            .map(|doc| normalize_url_for_storage(doc.url()))
+            .collect();
+        let shared = other
+            .documents
+            .iter()
+            .map(|doc| normalize_url_for_storage(doc.url()))
+            .collect::<HashSet<_>>()
+            .intersection(&crawled)
+            .count();
+
+        self.pages_crawled += other.pages_crawled;
+        self.pages_failed += other.pages_failed;
+        self.urls_discovered =
+            (self.urls_discovered + other.urls_discovered).saturating_sub(shared);
+        self.documents.extend(other.documents);
+        for (url, change) in other.changes {
+            self.changes.entry(url).or_insert(change);
+        }
+        self.fetch_durations.extend(other.fetch_durations);
+        self
+    }
+
+    /// Removes documents whose URL appeared earlier in `documents`
+    ///
+    /// URLs are compared in normalized form and the first document for
+    /// each URL is kept. The page counts are left unchanged, as they count
+    /// fetches.
+    ///
+    /// # Returns
+    ///
+    /// The number of documents removed
+    pub fn dedup_documents(&mut self) -> usize {
+        let before = self.documents.len();
+        let mut seen = HashSet::new();
+        self.documents
+            .retain(|doc| seen.insert(normalize_url_for_storage(doc.url())));
+
+        before - self.documents.len()
+    }
+}
+
+impl<'a> Spiderman<'a> {
+    /// Crawls the website starting from the seed URL
+    ///
+    /// This is the main entry point for the crawler. It orchestrates all components
+    /// to fetch, parse, and export web pages.
+    ///
+    /// # How It Works
+    ///
+    /// 1. Initialize URL Manager with seed URL
+    /// 2. Loop while there are URLs to crawl:
+    ///    a. Get the next URLs from the queue (up to `concurrency`)
+    ///    b. Fetch their HTML content in parallel
+    ///    c. Extract links and add to queue
+    ///    d. Convert HTML to Markdown
+    ///    e. Extract metadata
+    ///    f. Create Document
+    ///    g. Export Document
+    /// 3. Return crawl results
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - Crawl configuration options
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(CrawlResult)` - Successful crawl with statistics
+    /// * `Err(CrawlError::InvalidSeed)` - If the seed URL is malformed;
+    ///   nothing is fetched
+    /// * `Err(CrawlError::TooManyFailures)` - If `max_failures` was reached;
+    ///   the partial result is attached
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use spiderman::core::{Spiderman, CrawlConfig};
+    ///
+    /// async_std::task::block_on(async {
+    ///     let mut spider = Spiderman::new("example.com");
+    ///     let config = CrawlConfig::default().with_max_pages(10);
+    ///     let result = spider.crawl(config).await.unwrap();
+    ///
+    ///     println!("Crawled {} pages", result.pages_crawled);
+    /// });
+    /// ```
+    pub async fn crawl(&mut self, config: CrawlConfig) -> Result<CrawlResult, CrawlError> {
+        config.validate_seed(self.url)?;
+        if config.verbose {
+            println!("🕷️  Starting Spiderman Web Crawler");
+            println!("📍 Seed URL: {}", self.url);
+        }
+
+        let manager = self.seed_manager(&config);
+        self.run_crawl(manager, config, PriorRun::default()).await
+    }
+
+    /// Crawls the website with the config given to `Spiderman::with_config`
+    ///
+    /// Same as `crawl`; a crawler created with `Spiderman::new` runs with
+    /// `CrawlConfig::default()`.
+    ///
+    /// # Returns
+    ///
+    /// Same as `crawl`
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use spiderman::core::{CrawlConfig, Spiderman};
+    ///
+    /// async_std::task::block_on(async {
+    ///     let config = CrawlConfig::default().with_max_pages(10);
+    ///     let mut spider = Spiderman::with_config("example.com", config);
+    ///     let result = spider.run().await.unwrap();
+    ///
+    ///     println!("Crawled {} pages", result.pages_crawled);
+    /// });
+    /// ```
+    pub async fn run(&mut self) -> Result<CrawlResult, CrawlError> {
+        let config = self.config.clone().unwrap_or_default();
+        self.crawl(config).await
+    }
+
+    /// Builds a URL manager for the seed URL, configured from `config`
+    fn seed_manager(&self, config: &CrawlConfig) -> UrlManager {
+        let mut manager = UrlManager::new(&config.seed_url(self.url));
+
+        if let Some(max) = config.max_pages.filter(|_| config.budget_counts_failures) {
             manager.set_max_pages(max);
         }
-        if let Some(ref domains) = config.allowed_domains {
-            manager.set_allowed_domains(domains.clone());
+        if let Some(ref domains) = config.allowed_domains {
+            manager.set_allowed_domains(domains.clone());
+        }
+        if let Some(max) = config.max_hosts {
+            manager.set_max_hosts(max);
+        }
+        if let Some(max) = config.max_query_growth {
+            manager.set_max_query_growth(max);
+        }
+        if let Some(ref limits) = config.per_depth_limits {
+            manager.set_per_depth_limits(limits.clone());
+        }
+        manager.set_strategy(config.strategy);
+        if let Some(ref rewriter) = config.url_rewriter {
+            manager.set_url_rewriter(rewriter.clone());
+        }
+        manager.set_normalize_options(config.normalize_options.clone());
+
+        manager
+    }
+
+    /// Crawls from a pre-built URL manager
+    ///
+    /// Like `crawl`, but the queue, visited set, limits and filters come from
+    /// `manager` as given, instead of being built from the seed URL and the
+    /// config. Use this for several seeds, a visited set carried over from
+    /// an earlier crawl, or custom filters. The config's `max_pages`,
+    /// `allowed_domains`, `max_hosts`, `max_query_growth`,
+    /// `per_depth_limits`, `strategy`, `url_rewriter` and
+    /// `normalize_options` are not applied to the manager (except for a
+    /// `max_pages` that doesn't count failures, which the crawl loop
+    /// enforces itself).
+    ///
+    /// The first queued URL is treated as the seed (fetched on its own,
+    /// with its redirect target's host allowed). A resumed manager that has
+    /// already crawled the crawler's seed (it is visited but no longer
+    /// queued) has no seed: the seed is not fetched again, and the remaining
+    /// queue is crawled like any other pages.
+    ///
+    /// # Arguments
+    ///
+    /// * `manager` - The URL manager to crawl from
+    /// * `config` - Crawl configuration options
+    ///
+    /// # Returns
+    ///
+    /// Same as `crawl`
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use spiderman::core::url_manager::UrlManager;
+    /// use spiderman::core::{CrawlConfig, Spiderman};
+    ///
+    /// async_std::task::block_on(async {
+    ///     let mut manager = UrlManager::new("http://example.com");
+    ///     manager.add_url("http://example.com/news");
+    ///     manager.mark_visited("http://example.com/archive");
+    ///
+    ///     let mut spider = Spiderman::new("http://example.com");
+    ///     let result = spider
+    ///         .crawl_with_manager(manager, CrawlConfig::default())
+    ///         .await
+    ///         .unwrap();
+    /// });
+    /// ```
+    pub async fn crawl_with_manager(
+        &mut self,
+        manager: UrlManager,
+        config: CrawlConfig,
+    ) -> Result<CrawlResult, CrawlError> {
+        if config.verbose {
+            println!("🕷️  Starting Spiderman Web Crawler");
+        }
+
+        self.run_crawl(manager, config, PriorRun::default()).await
+    }
+
+    /// Crawls only pages that are new since a previous run
+    ///
+    /// Every URL in `prior_urls` (e.g. the document URLs of an earlier
+    /// `CrawlResult`) counts as already visited, so it is neither fetched
+    /// nor exported. The seed is the exception: it is always fetched so its
+    /// new links are discovered, but if it was part of the prior run its
+    /// own document is not exported again.
+    ///
+    /// # Arguments
+    ///
+    /// * `prior_urls` - URLs crawled in previous runs (any spelling)
+    /// * `config` - Crawl configuration options
+    ///
+    /// # Returns
+    ///
+    /// Same as `crawl`; `documents` only holds the new pages
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use spiderman::core::{CrawlConfig, Spiderman};
+    /// use std::collections::HashSet;
+    ///
+    /// async_std::task::block_on(async {
+    ///     let mut spider = Spiderman::new("http://example.com");
+    ///     let first = spider.crawl(CrawlConfig::default()).await.unwrap();
+    ///
+    ///     let prior: HashSet<String> =
+    ///         first.documents.iter().map(|d| d.url().to_string()).collect();
+    ///     let delta = spider.crawl_delta(&prior, CrawlConfig::default()).await.unwrap();
+    ///     println!("{} new pages", delta.documents.len());
+    /// });
+    /// ```
+    pub async fn crawl_delta(
+        &mut self,
+        prior_urls: &HashSet<String>,
+        config: CrawlConfig,
+    ) -> Result<CrawlResult, CrawlError> {
+        config.validate_seed(self.url)?;
+        if config.verbose {
+            println!("🕷️  Starting Spiderman Web Crawler");
+            println!("📍 Seed URL: {}", self.url);
+            println!("🗂️  Prior URLs: {}", prior_urls.len());
+        }
+
+        let mut manager = self.seed_manager(&config);
+
+        // Keep the seed queued so its links can be discovered
+        let seed_key = manager.storage_key(&config.seed_url(self.url));
+        let mut known = HashSet::new();
+        for url in prior_urls {
+            let key = manager.storage_key(url);
+            if key != seed_key {
+                manager.mark_visited(url);
+            }
+            known.insert(key);
+        }
+
+        self.run_crawl(manager, config, PriorRun { known, pages: None })
+            .await
+    }
+
+    /// Re-crawls a site, skipping pages unchanged since a previous run
+    ///
+    /// Every document of `prior` is queued again. Pages that had an `ETag`
+    /// are requested with `If-None-Match`; a `304 Not Modified` answer
+    /// reuses the prior document's links without downloading the page.
+    /// Other pages are compared by content hash. Only new and updated pages
+    /// are exported, and `changes` records the classification of every
+    /// page crawled.
+    ///
+    /// # Arguments
+    ///
+    /// * `prior` - Result of the previous crawl
+    /// * `config` - Crawl configuration options
+    ///
+    /// # Returns
+    ///
+    /// Same as `crawl`; `documents` only holds new and updated pages
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use spiderman::core::crawl::PageChange;
+    /// use spiderman::core::{CrawlConfig, Spiderman};
+    ///
+    /// async_std::task::block_on(async {
+    ///     let mut spider = Spiderman::new("http://example.com");
+    ///     let first = spider.crawl(CrawlConfig::default()).await.unwrap();
+    ///
+    ///     let second = spider.recrawl(&first, CrawlConfig::default()).await.unwrap();
+    ///     for (url, change) in &second.changes {
+    ///         if *change != PageChange::Unchanged {
+    ///             println!("{:?}: {}", change, url);
+    ///         }
+    ///     }
+    /// });
+    /// ```
+    pub async fn recrawl(
+        &mut self,
+        prior: &CrawlResult,
+        config: CrawlConfig,
+    ) -> Result<CrawlResult, CrawlError> {
+        config.validate_seed(self.url)?;
+        if config.verbose {
+            println!("🕷️  Starting Spiderman Web Crawler");
+            println!("📍 Seed URL: {}", self.url);
+            println!("🗂️  Prior pages: {}", prior.documents.len());
+        }
+
+        let mut manager = self.seed_manager(&config);
+
+        let mut pages = HashMap::new();
+        for doc in &prior.documents {
+            manager.add_url(doc.url());
+            pages.insert(
+                manager.storage_key(doc.url()),
+                PriorPage {
+                    etag: doc.etag().map(str::to_string),
+                    content_hash: doc.content_hash().map(str::to_string),
+                    links: doc.links().to_vec(),
+                },
+            );
+        }
+
+        let prior = PriorRun {
+            known: HashSet::new(),
+            pages: Some(pages),
+        };
+        self.run_crawl(manager, config, prior).await
+    }
+
+    /// Starts a crawl in the background and returns a feed of its progress
+    ///
+    /// The crawl runs on its own task, so a UI can poll the receiver (e.g.
+    /// with `try_recv`) while it is in progress. The last event is always
+    /// `CrawlEvent::Finished` with the final result; the channel closes
+    /// after it.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - Crawl configuration options (any event channel already
+    ///   set is replaced)
+    ///
+    /// # Returns
+    ///
+    /// A handle resolving to the crawl result, and the event receiver. A
+    /// crawl aborted by `max_failures` resolves to its partial result, one
+    /// that fails in any other way (an invalid seed URL) to an empty result.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use spiderman::core::crawl::CrawlEvent;
+    /// use spiderman::core::{CrawlConfig, Spiderman};
+    ///
+    /// async_std::task::block_on(async {
+    ///     let spider = Spiderman::new("example.com");
+    ///     let (handle, events) = spider.crawl_with_progress(CrawlConfig::default());
+    ///
+    ///     while let Ok(event) = events.recv().await {
+    ///         if let CrawlEvent::PageCrawled { url, .. } = event {
+    ///             println!("✓ {}", url);
+    ///         }
+    ///     }
+    ///
+    ///     let result = handle.await;
+    ///     println!("Crawled {} pages", result.pages_crawled);
+    /// });
+    /// ```
+    pub fn crawl_with_progress(
+        &self,
+        config: CrawlConfig,
+    ) -> (JoinHandle<CrawlResult>, Receiver<CrawlEvent>) {
+        let (sender, receiver) = channel::unbounded();
+        let config = config.with_events(sender);
+        let seed = self.url.to_string();
+
+        let handle = async_std::task::spawn(async move {
+            let events = config.events.clone();
+            let mut spider = Spiderman::new(&seed);
+            match spider.crawl(config).await {
+                Ok(result) => result,
+                Err(CrawlError::TooManyFailures { result, .. }) => *result,
+                // Other errors (an invalid seed) end the crawl before it
+                // starts, with an empty result
+                Err(_) => {
+                    let result = CrawlResult::default();
+                    if let Some(events) = events {
+                        let _ = events.try_send(CrawlEvent::Finished(result.clone()));
+                    }
+                    result
+                }
+            }
+        });
+
+        (handle, receiver)
+    }
+
+    /// Runs the crawl loop
+    ///
+    /// Pages whose storage key is in `prior.known` are fetched (for their
+    /// links) but not exported. Pages of `prior.pages` are fetched
+    /// conditionally and classified in `CrawlResult::changes`.
+    async fn run_crawl(
+        &mut self,
+        mut manager: UrlManager,
+        config: CrawlConfig,
+        prior: PriorRun,
+    ) -> Result<CrawlResult, CrawlError> {
+        if config.verbose {
+            println!("📥 Queued URLs: {}", manager.queue_size());
+            println!("📁 Output: {}/{}", config.output_dir, config.output_file);
+            // The limit in effect: the loop's success budget, or the manager's
+            if let Some(max) = config.success_budget().or(manager.max_pages()) {
+                println!("📊 Max pages: {}", max);
+            }
+            println!();
+        }
+
+        // Initialize Exporter
+        let exporter = Exporter::new(&config.output_dir);
+        let sink_queue = config
+            .sink
+            .clone()
+            .map(|sink| SinkQueue::start(sink, config.max_buffered_documents));
+
+        // Statistics
+        let mut pages_crawled = 0;
+        let mut pages_failed = 0;
+        let mut consecutive_failures = 0;
+        let mut aborted_after = None;
+        let mut documents = Vec::new();
+        let mut pending_exports = Vec::new();
+        let mut changes = HashMap::new();
+        let mut fetch_durations = Vec::new();
+        let mut discovery_anchors = HashMap::new();
+        let mut canonical_graph = HashMap::new();
+        let mut exported_keys = HashSet::new();
+        let mut fetched_feeds = HashSet::new();
+        let seed_key = manager.storage_key(&config.seed_url(self.url));
+        let mut is_seed = !manager.is_visited(&seed_key)
+            || manager.iter_queued().any(|queued| queued == seed_key);
+        let mut crawled_targets = HashSet::new();
+        let mut jitter = DelayJitter::new(config.delay_jitter, config.jitter_seed);
+        let mut adaptive = config
+            .adaptive_concurrency
+            .then(|| AdaptiveConcurrency::new(config.concurrency));
+
+        // Main crawl loop
+        'crawl: loop {
+            // Honor pause/stop requests before dispatching the next fetch
+            if let Some(ref control) = config.control {
+                if !control.wait_while_paused().await {
+                    if config.verbose {
+                        println!("⏹️  Crawl stopped");
+                        println!();
+                    }
+                    break;
+                }
+            }
+
+            // Take the next batch of URLs. The seed is fetched on its own,
+            // since a redirect there can change which hosts are in scope.
+            let mut batch_size = match adaptive {
+                _ if is_seed => 1,
+                Some(ref adaptive) => adaptive.limit(),
+                None => config.concurrency.max(1),
+            };
+
+            // Don't fetch more pages than the success budget has left
+            if let Some(max) = config.success_budget() {
+                batch_size = batch_size.min(max.saturating_sub(pages_crawled));
+            }
+            let mut batch = Vec::with_capacity(batch_size);
+            while batch.len() < batch_size {
+                let Some(url) = manager.get_next() else {
+                    break;
+                };
+                batch.push(url);
+            }
+            if batch.is_empty() {
+                break;
+            }
+
+            // Be polite between requests (nothing to wait for before the seed)
+            if let Some(delay) = config.delay {
+                if !is_seed {
+                    async_std::task::sleep(jitter.apply(delay)).await;
+                }
+            }
+
+            let (total, _, processed) = manager.stats();
+            let first_number = processed + 1 - batch.len();
+
+            let mut requests = Vec::with_capacity(batch.len());
+            for (i, current_url) in batch.into_iter().enumerate() {
+                if config.verbose {
+                    println!("[{}/{}] Crawling: {}", first_number + i, total, current_url);
+                }
+
+                // Send the discovering page as Referer (the seed has none)
+                let mut options = config.fetch_options();
+                if config.send_referer {
+                    if let Some(parent) = manager.parent_of(&current_url) {
+                        options = options.with_header("Referer", parent);
+                    }
+                }
+
+                // Revalidate pages the previous run saw with an ETag
+                if let Some(etag) = prior
+                    .page(&manager.storage_key(&current_url))
+                    .and_then(|page| page.etag.as_deref())
+                {
+                    options = options.with_header("If-None-Match", etag);
+                }
+                requests.push((current_url, options));
+            }
+
+            // Fetch the whole batch together, so at most `concurrency`
+            // requests are in flight however many worker threads exist.
+            // Errors become `CrawlError`s right away, which (unlike boxed
+            // errors) can be held across awaits on a spawned task.
+            let responses = join_all(requests.iter().map(|(url, options)| async move {
+                let started = Instant::now();
+                let response = fetch_following_redirects(url, options)
+                    .await
+                    .map_err(CrawlError::from);
+                (started.elapsed(), response)
+            }))
+            .await;
+
+            if let Some(ref mut adaptive) = adaptive {
+                for (_, response) in &responses {
+                    adaptive.record(matches!(
+                        response,
+                        Ok((_, response)) if response.status != 429 && response.status < 500
+                    ));
+                }
+                if let Some(concurrency) = adaptive.adjust() {
+                    if config.verbose {
+                        println!("⚖️  Concurrency now {}", concurrency);
+                        println!();
+                    }
+                    config.emit(CrawlEvent::ConcurrencyChanged { concurrency });
+                }
+            }
+
+            for ((current_url, _), (duration, response)) in requests.iter().zip(responses) {
+                fetch_durations.push(duration);
+
+                // Fetch HTML (following redirects), treating unwanted status
+                // codes as failures
+                let fetched = response.and_then(|(chain, response)| {
+                    let final_url = response.final_url.clone();
+
+                    // A seed redirected to another host (e.g. `example.com`
+                    // → `www.example.com`) brings that host into scope
+                    if is_seed {
+                        if let Some(domain) = extract_domain(&final_url) {
+                            manager.allow_domain(&domain);
+                        }
+                    }
+
+                    // Redirect hops and the target count as visited so they
+                    // aren't fetched again when linked from other pages
+                    for url in &chain[1..] {
+                        manager.mark_redirect(url, &chain[0]);
+                    }
+
+                    // A `304` is only meaningful for a page of the prior run
+                    let not_modified = response.status == 304
+                        && prior.page(&manager.storage_key(&final_url)).is_some();
+
+                    if config.should_store_status(response.status) || not_modified {
+                        Ok((final_url, response))
+                    } else {
+                        Err(CrawlError::Fetch(format!(
+                            "HTTP status {}",
+                            response.status
+                        )))
+                    }
+                });
+                is_seed = false;
+
+                match fetched {
+                    // Another redirect already led to this target
+                    Ok((final_url, _))
+                        if !crawled_targets.insert(manager.storage_key(&final_url)) =>
+                    {
+                        if config.verbose {
+                            println!("  └─ ↪ Already crawled via redirect: {}", final_url);
+                        }
+                    }
+                    // Unchanged since the previous run: reuse its links
+                    Ok((final_url, response))
+                        if response.status == 304
+                            && prior.page(&manager.storage_key(&final_url)).is_some() =>
+                    {
+                        if let Some(page) = prior.page(&manager.storage_key(&final_url)) {
+                            manager.add_urls_with_parent(&page.links, &final_url);
+                        }
+
+                        if config.verbose {
+                            println!("  └─ ⊘ Skipped: not modified since the previous run");
+                        }
+
+                        changes.insert(final_url.clone(), PageChange::Unchanged);
+                        config.emit(CrawlEvent::PageCrawled {
+                            url: final_url,
+                            status: response.status,
+                        });
+                        pages_crawled += 1;
+                        consecutive_failures = 0;
+                    }
+                    // A media type the crawl isn't interested in
+                    Ok((_, response)) if !config.allows_content_type(&response) => {
+                        if config.verbose {
+                            println!(
+                                "  └─ ⊘ Skipped: content type {} not allowed",
+                                response.mime_type().unwrap_or_default()
+                            );
+                        }
+                    }
+                    // A non-HTML resource: save its bytes instead of converting
+                    Ok((final_url, response)) if config.saves_as_asset(&response) => {
+                        match save_asset(&config, &final_url, &response.raw_body) {
+                            Ok(path) => {
+                                let doc =
+                                    asset_document(&config, &final_url, response.status, &path)
+                                        .with_discovery_anchor(
+                                            discovery_anchors
+                                                .remove(&manager.storage_key(current_url)),
+                                        );
+
+                                if config.verbose {
+                                    println!("  └─ ✓ Saved asset to {}", path.display());
+                                }
+
+                                if let Some(ref queue) = sink_queue {
+                                    queue.push(doc).await;
+                                } else {
+                                    // Non-streaming formats are written when the
+                                    // crawl finishes
+                                    if config.output_format.is_streaming() {
+                                        if config.export_batch_size > 1 {
+                                            push_pending(
+                                                &exporter,
+                                                &config,
+                                                &mut pending_exports,
+                                                doc.clone(),
+                                            );
+                                        } else if let Err(e) = exporter.export_document(
+                                            &doc,
+                                            &config.output_file_for(doc.url()),
+                                        ) {
+                                            eprintln!("  ├─ ⚠️  Export error: {}", e);
+                                        }
+                                    }
+                                    documents.push(doc);
+                                }
+                                config.emit(CrawlEvent::PageCrawled {
+                                    url: final_url,
+                                    status: response.status,
+                                });
+                                pages_crawled += 1;
+                                consecutive_failures = 0;
+                            }
+                            Err(e) => {
+                                if config.verbose {
+                                    eprintln!("  └─ ✗ Error saving asset: {}", e);
+                                }
+                                config.emit(CrawlEvent::PageFailed {
+                                    url: current_url.clone(),
+                                    error: e.to_string(),
+                                });
+                                pages_failed += 1;
+                                consecutive_failures += 1;
+                            }
+                        }
+                    }
+                    Ok((final_url, response)) => {
+                        let status = response.status;
+                        let header_robots = response.robots_directives();
+                        let etag = response.header("etag").map(str::to_string);
+                        let data_mime = data_mime(&response);
+                        let html = response.body;
+
+                        // Extract metadata
+                        let metadata = extract_metadata(&html);
+
+                        // Robots directives from the X-Robots-Tag header and the
+                        // meta robots tag
+                        let robots = header_robots.merge(metadata.robots());
+
+                        // Extract links and add to queue (unless nofollow)
+                        // Links are relative to where the page actually is,
+                        // after redirects
+                        let links: Vec<String> =
+                            extract_links_with_options(&html, &final_url, &config.link_options())
+                                .into_iter()
+                                .map(|link| apply_default_scheme(&link, DEFAULT_SCHEME))
+                                .collect();
+                        let gated = config.is_gated(&html);
+                        let follow = !robots.nofollow
+                            && config.discovers_links(&final_url)
+                            && !(gated && config.skip_gated_links);
+                        if !follow {
+                            if config.verbose {
+                                let reason = if robots.nofollow {
+                                    "nofollow"
+                                } else if gated && config.skip_gated_links {
+                                    "gated"
+                                } else {
+                                    "no-discover"
+                                };
+                                println!("  ├─ {}: {} links not queued", reason, links.len());
+                            }
+                        } else {
+                            let added = manager.add_urls_with_parent(&links, &final_url);
+
+                            if config.verbose && added > 0 {
+                                println!("  ├─ Found {} links ({} new)", links.len(), added);
+                            }
+
+                            // Remember the anchor text of the links this page
+                            // discovered, for the children's documents
+                            if added > 0 {
+                                let anchors = extract_anchor_texts(&html, &final_url);
+                                for link in &links {
+                                    if manager.parent_of(link) != Some(final_url.as_str()) {
+                                        continue;
+                                    }
+                                    if let Some(text) =
+                                        anchors.get(&normalize_url_for_storage(link))
+                                    {
+                                        discovery_anchors
+                                            .entry(manager.storage_key(link))
+                                            .or_insert_with(|| text.clone());
+                                    }
+                                }
+                            }
+
+                            // Queue the articles of the feeds the page
+                            // declares (each feed is read once)
+                            if config.follow_feeds {
+                                let feeds = metadata
+                                    .feeds
+                                    .iter()
+                                    .filter_map(|feed| normalize_url(feed, &final_url));
+                                for feed in feeds {
+                                    // Feeds on hosts out of scope are not read
+                                    if !manager.is_in_scope(&feed)
+                                        || !fetched_feeds.insert(manager.storage_key(&feed))
+                                    {
+                                        continue;
+                                    }
+
+                                    if let Some(delay) = config.delay {
+                                        async_std::task::sleep(jitter.apply(delay)).await;
+                                    }
+                                    let started = Instant::now();
+                                    let fetched =
+                                        fetch_following_redirects(&feed, &config.fetch_options())
+                                            .await
+                                            .map_err(CrawlError::from);
+                                    fetch_durations.push(started.elapsed());
+                                    match fetched {
+                                        Ok((_, response)) if response.is_success() => {
+                                            let articles: Vec<String> = parse_feed(&response.body)
+                                                .iter()
+                                                .filter_map(|article| {
+                                                    normalize_url(article, &response.final_url)
+                                                })
+                                                .collect();
+                                            let added =
+                                                manager.add_urls_with_parent(&articles, &final_url);
+                                            if config.verbose {
+                                                println!(
+                                                    "  ├─ Feed {}: {} articles ({} new)",
+                                                    feed,
+                                                    articles.len(),
+                                                    added
+                                                );
+                                            }
+                                        }
+                                        Ok((_, response)) => {
+                                            if config.verbose {
+                                                eprintln!(
+                                                    "  ├─ ✗ Feed {}: HTTP status {}",
+                                                    feed, response.status
+                                                );
+                                            }
+                                        }
+                                        Err(e) => {
+                                            if config.verbose {
+                                                eprintln!("  ├─ ✗ Feed {}: {}", feed, e);
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+
+                        // In priority mode, crawl the next page of a series first
+                        if config.prioritize_next && follow {
+                            if let Some(next) = metadata
+                                .next
+                                .as_deref()
+                                .and_then(|next| normalize_url(next, &final_url))
+                            {
+                                manager.add_priority_url(&next, &final_url);
+                            }
+                        }
+
+                        // JSON and feeds are kept as they are
+                        let doc = if let Some(ref mime) = data_mime {
+                            data_document(&config, &final_url, status, mime, html, links)
+                        } else {
+                            page_document(
+                                &config, &final_url, status, html, metadata, links, robots,
+                            )
+                        };
+                        let doc = doc.with_etag(etag).with_gated(gated).with_discovery_anchor(
+                            discovery_anchors.remove(&manager.storage_key(current_url)),
+                        );
+
+                        // Compare with the previous run, if re-crawling
+                        let change = prior.pages.as_ref().map(|pages| {
+                            match pages.get(&manager.storage_key(&final_url)) {
+                                None => PageChange::New,
+                                Some(page)
+                                    if page.content_hash.as_deref() == doc.content_hash() =>
+                                {
+                                    PageChange::Unchanged
+                                }
+                                Some(_) => PageChange::Updated,
+                            }
+                        });
+                        if let Some(change) = change {
+                            changes.insert(final_url.clone(), change);
+                        }
+
+                        // Follow the canonical chain to the page this one
+                        // duplicates, if it was already exported
+                        let page_key = manager.storage_key(&final_url);
+                        let mut canonical_duplicate = None;
+                        if config.canonical_dedup {
+                            if let Some(canonical) =
+                                doc.canonical_url().map(|c| manager.storage_key(c))
+                            {
+                                if canonical != page_key {
+                                    canonical_graph.insert(page_key.clone(), canonical);
+                                }
+                            }
+                            match resolve_canonical(&canonical_graph, &page_key) {
+                                Some(target) if exported_keys.contains(&target) => {
+                                    canonical_duplicate = Some(target);
+                                }
+                                Some(_) => {}
+                                None => eprintln!(
+                                    "  ├─ ⚠️  Canonical cycle at {}; keeping the fetched URL",
+                                    final_url
+                                ),
+                            }
+                        }
+
+                        // Export document, unless it is too thin to keep (its
+                        // links have already been queued)
+                        let too_short = config
+                            .min_content_length
+                            .is_some_and(|min| doc.content_length() < min);
+                        let low_density = config
+                            .min_text_density
+                            .is_some_and(|min| doc.text_density() < min);
+
+                        if robots.noindex {
+                            if config.verbose {
+                                println!("  └─ ⊘ Skipped: noindex");
+                            }
+                        } else if prior.known.contains(&manager.storage_key(&final_url)) {
+                            if config.verbose {
+                                println!("  └─ ⊘ Skipped: crawled in a previous run");
+                            }
+                        } else if change == Some(PageChange::Unchanged) {
+                            if config.verbose {
+                                println!(
+                                    "  └─ ⊘ Skipped: content unchanged since the previous run"
+                                );
+                            }
+                        } else if too_short {
+                            if config.verbose {
+                                println!(
+                                    "  └─ ⊘ Skipped: content shorter than {} bytes",
+                                    config.min_content_length.unwrap_or_default()
+                                );
+                            }
+                        } else if low_density {
+                            if config.verbose {
+                                println!(
+                                    "  └─ ⊘ Skipped: low text density ({:.3})",
+                                    doc.text_density()
+                                );
+                            }
+                        } else if let Some(target) = canonical_duplicate {
+                            if config.verbose {
+                                println!("  └─ ⊘ Skipped: duplicate of canonical {}", target);
+                            }
+                        } else if let Some(ref queue) = sink_queue {
+                            exported_keys.insert(page_key);
+                            // Waits while the sink's queue is full
+                            queue.push(doc).await;
+                            if config.verbose {
+                                println!("  └─ ✓ Sent to sink");
+                            }
+                        } else {
+                            if config.output_format.is_streaming() {
+                                if config.export_batch_size > 1 {
+                                    push_pending(
+                                        &exporter,
+                                        &config,
+                                        &mut pending_exports,
+                                        doc.clone(),
+                                    );
+                                    if config.verbose {
+                                        println!("  └─ ✓ Converted (export batched)");
+                                    }
+                                } else if let Err(e) = exporter
+                                    .export_document(&doc, &config.output_file_for(doc.url()))
+                                {
+                                    eprintln!("  ├─ ⚠️  Export error: {}", e);
+                                } else if config.verbose {
+                                    println!(
+                                        "  └─ ✓ Exported to {}/{}",
+                                        config.output_dir,
+                                        config.output_file_for(doc.url())
+                                    );
+                                }
+                            } else if config.verbose {
+                                // Non-streaming formats are written when the
+                                // crawl finishes
+                                println!("  └─ ✓ Converted");
+                            }
+
+                            exported_keys.insert(page_key);
+                            documents.push(doc);
+                        }
+                        config.emit(CrawlEvent::PageCrawled {
+                            url: final_url,
+                            status,
+                        });
+                        pages_crawled += 1;
+                        consecutive_failures = 0;
+                    }
+                    Err(e) => {
+                        if config.verbose {
+                            eprintln!("  └─ ✗ Error: {}", e);
+                        }
+                        config.emit(CrawlEvent::PageFailed {
+                            url: current_url.clone(),
+                            error: e.to_string(),
+                        });
+                        pages_failed += 1;
+                        consecutive_failures += 1;
+                    }
+                }
+
+                if config.verbose {
+                    println!();
+                }
+
+                // Abort if too many pages are failing
+                if let Some(max) = config.max_failures {
+                    let failures = match config.failure_count {
+                        FailureCount::Total => pages_failed,
+                        FailureCount::Consecutive => consecutive_failures,
+                    };
+                    if failures >= max {
+                        if config.verbose {
+                            println!("⛔ Aborting crawl after {} failures", failures);
+                            println!();
+                        }
+                        aborted_after = Some(failures);
+                        break 'crawl;
+                    }
+                }
+            }
+        }
+
+        // Let the sink catch up with the queued documents
+        if let Some(queue) = sink_queue {
+            queue.finish();
+        }
+
+        // Write the last, partial export batch
+        export_pending(&exporter, &config, &mut pending_exports);
+
+        // Write the formats that aren't streamed page by page
+        if config.sink.is_none() && !config.output_format.is_streaming() {
+            let export = |documents: &[Document], file: &str| match exporter.export_as(
+                documents,
+                file,
+                config.output_format,
+            ) {
+                Ok(()) if config.verbose => println!(
+                    "📁 Exported {} documents to {}/{}",
+                    documents.len(),
+                    config.output_dir,
+                    file
+                ),
+                Ok(()) => {}
+                Err(e) => eprintln!("⚠️  Export error: {}", e),
+            };
+
+            if config.split_output_by_domain {
+                let mut by_file: BTreeMap<String, Vec<Document>> = BTreeMap::new();
+                for doc in &documents {
+                    by_file
+                        .entry(config.output_file_for(doc.url()))
+                        .or_default()
+                        .push(doc.clone());
+                }
+                for (file, documents) in &by_file {
+                    export(documents, file);
+                }
+            } else {
+                export(&documents, &config.output_file);
+            }
+        }
+
+        // Final statistics
+        let (total_urls, _, _) = manager.stats();
+
+        if config.verbose && aborted_after.is_none() {
+            println!("✅ Crawl Complete!");
+            println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+            println!("📈 Statistics:");
+            println!("   • Pages crawled: {}", pages_crawled);
+            println!("   • Pages failed: {}", pages_failed);
+            println!("   • URLs discovered: {}", total_urls);
+            println!("   • Output: {}/{}", config.output_dir, config.output_file);
+            println!();
+        }
+
+        let result = CrawlResult {
+            pages_crawled,
+            pages_failed,
+            urls_discovered: total_urls,
+            documents,
+            changes,
+            fetch_durations,
+        };
+        config.emit(CrawlEvent::Finished(result.clone()));
+        if let Some(ref hook) = config.on_finish {
+            hook.run(&result);
+        }
+
+        match aborted_after {
+            Some(failures) => Err(CrawlError::TooManyFailures {
+                failures,
+                result: Box::new(result),
+            }),
+            None => Ok(result),
+        }
+    }
+
+    /// Fetches and converts a fixed list of URLs, without following links
+    ///
+    /// Each URL is fetched (following redirects) and stored like a crawled
+    /// page, but no links are extracted or queued, so exactly the given URLs
+    /// are requested. URLs without a scheme get `http://`, like a seed.
+    /// Fetches honor `concurrency`, `delay`, the accepted status codes and
+    /// content types, and `save_assets_dir`; JSON and feeds are kept
+    /// verbatim. The seed URL of this `Spiderman` is not used. Documents are
+    /// returned, not exported.
+    ///
+    /// # Arguments
+    ///
+    /// * `urls` - The URLs to fetch
+    /// * `config` - Crawl configuration options
+    ///
+    /// # Returns
+    ///
+    /// One result per URL, in the order given
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use spiderman::core::{CrawlConfig, Spiderman};
+    ///
+    /// async_std::task::block_on(async {
+    ///     let spider = Spiderman::new("example.com");
+    ///     let urls = vec![
+    ///         "http://example.com/a".to_string(),
+    ///         "http://example.com/b".to_string(),
+    ///     ];
+    ///
+    ///     for result in spider.fetch_all(urls, CrawlConfig::default()).await {
+    ///         match result {
+    ///             Ok(doc) => println!("{}: {}", doc.url(), doc.title()),
+    ///             Err(e) => eprintln!("{}", e),
+    ///         }
+    ///     }
+    /// });
+    /// ```
+    pub async fn fetch_all(
+        &self,
+        urls: Vec<String>,
+        config: CrawlConfig,
+    ) -> Vec<Result<Document, CrawlError>> {
+        let options = config.fetch_options();
+        let mut jitter = DelayJitter::new(config.delay_jitter, config.jitter_seed);
+        let mut results = Vec::with_capacity(urls.len());
+
+        for (i, batch) in urls.chunks(config.concurrency.max(1)).enumerate() {
+            // Be polite between batches
+            if let Some(delay) = config.delay {
+                if i > 0 {
+                    async_std::task::sleep(jitter.apply(delay)).await;
+                }
+            }
+
+            let urls: Vec<Result<String, CrawlError>> =
+                batch.iter().map(|url| config.validate_seed(url)).collect();
+            if config.verbose {
+                for url in urls.iter().flatten() {
+                    println!("Fetching: {}", url);
+                }
+            }
+
+            let fetches = urls.into_iter().map(|url| async {
+                let (_, response) = fetch_following_redirects(&url?, &options).await?;
+                let final_url = response.final_url.clone();
+                fetched_document(&config, &final_url, response)
+            });
+
+            for result in join_all(fetches).await {
+                if config.verbose {
+                    if let Err(ref e) = result {
+                        eprintln!("  └─ ✗ Error: {}", e);
+                    }
+                }
+                results.push(result);
+            }
+        }
+
+        results
+    }
+}
+
+/// Adds a document to the export batch, writing the batch once it holds
+/// `export_batch_size` documents
+fn push_pending(
+    exporter: &Exporter,
+    config: &CrawlConfig,
+    pending: &mut Vec<Document>,
+    doc: Document,
+) {
+    pending.push(doc);
+    if pending.len() >= config.export_batch_size {
+        export_pending(exporter, config, pending);
+    }
+}
+
+/// Appends the batched documents to their JSONL output files
+///
+/// Each output file is opened once for its share of the batch. `pending` is
+/// empty afterwards.
+fn export_pending(exporter: &Exporter, config: &CrawlConfig, pending: &mut Vec<Document>) {
+    let mut by_file: BTreeMap<String, Vec<Document>> = BTreeMap::new();
+    for doc in pending.drain(..) {
+        by_file
+            .entry(config.output_file_for(doc.url()))
+            .or_default()
+            .push(doc);
+    }
+
+    for (file, documents) in &by_file {
+        match exporter.export_batch(documents, file) {
+            Ok(()) if config.verbose => println!(
+                "📁 Exported {} documents to {}/{}",
+                documents.len(),
+                config.output_dir,
+                file
+            ),
+            Ok(()) => {}
+            Err(e) => eprintln!("⚠️  Export error: {}", e),
+        }
+    }
+}
+
+/// Converts a fetched HTML page into a `Document`
+///
+/// Applies the config's extraction options: boilerplate stripping, title
+/// and description length limits, and raw HTML storage. The page's
+/// `noarchive` and `nosnippet` directives override the config, dropping the
+/// raw HTML and the description respectively.
+fn page_document(
+    config: &CrawlConfig,
+    url: &str,
+    status: u16,
+    html: String,
+    metadata: Metadata,
+    links: Vec<String>,
+    robots: RobotsDirectives,
+) -> Document {
+    let favicon = metadata.favicon_url(url);
+    let canonical_url = metadata
+        .canonical
+        .as_deref()
+        .and_then(|canonical| normalize_url(canonical, url));
+    let content_hash = hash_content(html.as_bytes());
+
+    // Only convert up to the configured size
+    let source = match config.max_html_bytes_for_conversion {
+        Some(max) => truncate_html(&html, max),
+        None => &html,
+    };
+    let truncated = source.len() < html.len();
+
+    // Convert HTML to Markdown
+    let mut resolved = match config.include_noscript {
+        Some(include) => resolve_noscript(source, include),
+        None => Cow::Borrowed(source),
+    };
+    let main_content = match config.readability.then(|| extract_main(&resolved)) {
+        Some(Some(main)) => {
+            resolved = Cow::Owned(main);
+            true
+        }
+        _ => false,
+    };
+    let markdown = if config.strip_boilerplate {
+        parser(strip_boilerplate(&resolved))
+    } else {
+        parser(resolved.into_owned())
+    };
+
+    let title = metadata.title.unwrap_or_else(|| {
+        // Fallback: extract from URL
+        url.split('/').next_back().unwrap_or("Untitled").to_string()
+    });
+
+    // Apply configured length limits
+    let title = match config.max_title_len {
+        Some(max) => truncate_at_word(&title, max),
+        None => title,
+    };
+    let description = match config.max_description_len {
+        _ if robots.nosnippet => None,
+        Some(max) => metadata.description.map(|d| truncate_at_word(&d, max)),
+        None => metadata.description,
+    };
+
+    let density = text_density(&markdown, source);
+
+    // Create document
+    let mut doc = Document::new(url, markdown, links)
+        .with_text_density(density)
+        .with_truncated(truncated)
+        .with_status(status)
+        .with_extraction(ExtractionInfo {
+            main_content,
+            ..config.extraction_info()
+        })
+        .with_title(title)
+        .with_description(description)
+        .with_published_at(metadata.published_at)
+        .with_favicon(favicon)
+        .with_canonical_url(canonical_url)
+        .with_content_hash(content_hash);
+
+    // Add metadata
+    if let Some(keywords) = metadata.keywords {
+        doc = doc.with_metadata("keywords", &keywords);
+    }
+    if let Some(author) = metadata.author {
+        doc = doc.with_metadata("author", &author);
+    }
+    if let Some(viewport) = metadata.viewport {
+        doc = doc.with_metadata("viewport", &viewport);
+    }
+    if config.inventory_assets {
+        doc = doc.with_assets(extract_assets(&html, url));
+    }
+
+    // Store raw HTML if configured and the page allows archiving
+    if config.store_raw_html && !robots.noarchive {
+        let raw_html = if config.sanitize_raw_html {
+            sanitize_html(&html)
+        } else {
+            html
+        };
+        doc = doc.with_raw_html(raw_html);
+    }
+
+    doc
+}
+
+/// Follows the canonical chain starting at `key` to the page it ends at
+///
+/// `graph` maps a page's storage key to the key of its canonical URL
+/// (self-referential canonicals are left out). Returns `key` itself when
+/// the page has no canonical, and `None` when the chain runs into a cycle.
+fn resolve_canonical(graph: &HashMap<String, String>, key: &str) -> Option<String> {
+    let mut seen = HashSet::new();
+    let mut current = key;
+    while let Some(next) = graph.get(current) {
+        if !seen.insert(current) {
+            return None;
+        }
+        current = next;
+    }
+    Some(current.to_string())
+}
+
+/// Converts a response fetched by `fetch_all` into a document
+///
+/// Dispatches on the status and content type like the crawl loop: unwanted
+/// statuses and content types are errors, non-HTML resources are saved when
+/// `save_assets_dir` is set, and JSON and feeds are kept verbatim.
+fn fetched_document(
+    config: &CrawlConfig,
+    url: &str,
+    response: FetchResponse,
+) -> Result<Document, CrawlError> {
+    if !config.should_store_status(response.status) {
+        return Err(CrawlError::Fetch(format!(
+            "HTTP status {}",
+            response.status
+        )));
+    }
+    if !config.allows_content_type(&response) {
+        return Err(CrawlError::Fetch(format!(
+            "content type {} not allowed",
+            response.mime_type().unwrap_or_default()
+        )));
+    }
+    if config.saves_as_asset(&response) {
+        let path = save_asset(config, url, &response.raw_body)?;
+        return Ok(asset_document(config, url, response.status, &path));
+    }
+    if let Some(mime) = data_mime(&response) {
+        return Ok(data_document(
+            config,
+            url,
+            response.status,
+            &mime,
+            response.body,
+            Vec::new(),
+        ));
+    }
+
+    let metadata = extract_metadata(&response.body);
+    let robots = response.robots_directives().merge(metadata.robots());
+    Ok(page_document(
+        config,
+        url,
+        response.status,
+        response.body,
+        metadata,
+        Vec::new(),
+        robots,
+    ))
+}
+
+/// Returns the media type of a JSON or feed response, which is stored
+/// verbatim
+fn data_mime(response: &FetchResponse) -> Option<String> {
+    response.mime_type().filter(|_| response.is_data())
+}
+
+/// Saves a non-HTML response's bytes under `save_assets_dir`
+fn save_asset(config: &CrawlConfig, url: &str, bytes: &[u8]) -> io::Result<PathBuf> {
+    Exporter::new(config.save_assets_dir.as_deref().unwrap_or_default())
+        .with_max_filename_len(config.max_filename_len)
+        .save_asset(url, bytes)
+}
+
+/// Builds the document of a saved asset, pointing at its file
+fn asset_document(config: &CrawlConfig, url: &str, status: u16, path: &Path) -> Document {
+    let title = url.split('/').next_back().unwrap_or("Untitled").to_string();
+
+    Document::new(url, String::new(), vec![])
+        .with_status(status)
+        .with_extraction(config.extraction_info())
+        .with_title(title)
+        .with_asset_path(path.to_string_lossy().to_string())
+}
+
+/// Builds the document of a JSON or feed response, keeping its body verbatim
+///
+/// The body is all content, so its text density is 1.0 and
+/// `min_text_density` never drops it. No converter runs, so the extraction
+/// info only records the body's format (`json` or `xml`).
+fn data_document(
+    config: &CrawlConfig,
+    url: &str,
+    status: u16,
+    mime: &str,
+    body: String,
+    links: Vec<String>,
+) -> Document {
+    let format = if mime.contains("json") { "json" } else { "xml" };
+    let content_hash = hash_content(body.as_bytes());
+    let title = url.split('/').next_back().unwrap_or("Untitled").to_string();
+    let title = match config.max_title_len {
+        Some(max) => truncate_at_word(&title, max),
+        None => title,
+    };
+
+    Document::new(url, body, links)
+        .with_text_density(1.0)
+        .with_status(status)
+        .with_extraction(ExtractionInfo::verbatim(format))
+        .with_title(title)
+        .with_content_hash(content_hash)
+}
+
+/// Returns the size of the Markdown relative to the HTML it came from
+fn text_density(markdown: &str, html: &str) -> f32 {
+    if html.is_empty() {
+        return 0.0;
+    }
+    markdown.len() as f32 / html.len() as f32
+}
+
+/// What `run_crawl` knows about earlier crawls
+#[derive(Default)]
+struct PriorRun {
+    /// Storage keys of pages fetched but not exported (see `crawl_delta`)
+    known: HashSet<String>,
+
+    /// Pages of the previous run by storage key (see `recrawl`)
+    pages: Option<HashMap<String, PriorPage>>,
+}
+
+impl PriorRun {
+    /// Returns the previous run's page for a storage key, if any
+    fn page(&self, key: &str) -> Option<&PriorPage> {
+        self.pages.as_ref().and_then(|pages| pages.get(key))
+    }
+}
+
+/// A page of the previous run, as needed to re-crawl it
+struct PriorPage {
+    /// `ETag` the page was served with
+    etag: Option<String>,
+
+    /// Hash of the page's body
+    content_hash: Option<String>,
+
+    /// Links found on the page, queued again when it is unchanged
+    links: Vec<String>,
+}
+
+/// Feeds documents to a sink on a background thread
+///
+/// The queue between the crawl and the sink holds at most `capacity`
+/// documents; `push` waits while it is full, so a slow sink slows the
+/// crawl down instead of letting documents pile up in memory.
+struct SinkQueue {
+    /// Sending side of the bounded queue
+    sender: Sender<Document>,
+
+    /// Thread writing queued documents to the sink
+    worker: thread::JoinHandle<()>,
+}
+
+impl SinkQueue {
+    /// Starts the writer thread
+    fn start(sink: SharedSink, capacity: usize) -> Self {
+        let (sender, receiver) = channel::bounded::<Document>(capacity.max(1));
+
+        let worker = thread::spawn(move || {
+            while let Ok(doc) = async_std::task::block_on(receiver.recv()) {
+                if let Err(e) = sink.write_document(&doc) {
+                    eprintln!("  ├─ ⚠️  Sink error for {}: {}", doc.url(), e);
+                }
+            }
+        });
+
+        Self { sender, worker }
+    }
+
+    /// Queues a document, waiting while the queue is full
+    async fn push(&self, doc: Document) {
+        // Only fails if the writer thread died (e.g. a panicking sink)
+        if self.sender.send(doc).await.is_err() {
+            eprintln!("  ├─ ⚠️  Sink stopped; document dropped");
+        }
+    }
+
+    /// Closes the queue and waits until the sink has written everything
+    fn finish(self) {
+        drop(self.sender);
+        let _ = self.worker.join();
+    }
+}
+
+/// Number of recent fetches adaptive concurrency looks at
+const ADAPTIVE_WINDOW: usize = 10;
+
+/// Fewest fetches adaptive concurrency decides on
+const ADAPTIVE_MIN_SAMPLES: usize = 5;
+
+/// Scales the number of parallel fetches with the recent success rate
+///
+/// Additive increase, multiplicative decrease: below 50% successes the
+/// limit is halved (and the window restarts, so the same failures don't
+/// halve it twice); at 90% or more it grows by one.
+struct AdaptiveConcurrency {
+    /// Upper bound (`CrawlConfig::concurrency`)
+    max: usize,
+
+    /// Current number of parallel fetches
+    limit: usize,
+
+    /// Outcomes of the most recent fetches (true = success)
+    window: VecDeque<bool>,
+}
+
+impl AdaptiveConcurrency {
+    /// Creates a controller starting at full concurrency
+    fn new(max: usize) -> Self {
+        let max = max.max(1);
+        Self {
+            max,
+            limit: max,
+            window: VecDeque::with_capacity(ADAPTIVE_WINDOW),
+        }
+    }
+
+    /// Returns the current number of parallel fetches
+    fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Records the outcome of a fetch
+    fn record(&mut self, success: bool) {
+        if self.window.len() == ADAPTIVE_WINDOW {
+            self.window.pop_front();
+        }
+        self.window.push_back(success);
+    }
+
+    /// Updates the limit from the recorded outcomes
+    ///
+    /// Returns the new limit if it changed.
+    fn adjust(&mut self) -> Option<usize> {
+        if self.window.len() < ADAPTIVE_MIN_SAMPLES {
+            return None;
+        }
+
+        let successes = self.window.iter().filter(|success| **success).count();
+        let rate = successes as f64 / self.window.len() as f64;
+        let previous = self.limit;
+
+        if rate < 0.5 {
+            self.limit = (self.limit / 2).max(1);
+            self.window.clear();
+        } else if rate >= 0.9 {
+            self.limit = (self.limit + 1).min(self.max);
+        }
+
+        (self.limit != previous).then_some(self.limit)
+    }
+}
+
+/// Randomizes politeness delays by a jitter fraction
+///
+/// Uses a small seeded SplitMix64 generator so a fixed seed gives the same
+/// sequence of delays on every run.
+struct DelayJitter {
+    /// Jitter fraction (0.0–1.0)
+    jitter: f64,
+
+    /// Generator state
+    state: u64,
+}
+
+impl DelayJitter {
+    /// Creates a jitter source, seeded from the clock when no seed is given
+    fn new(jitter: f64, seed: Option<u64>) -> Self {
+        let seed = seed.unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_nanos() as u64)
+        });
+
+        Self {
+            jitter: clamp_jitter(jitter),
+            state: seed,
+        }
+    }
+
+    /// Returns the next random number in `[0, 1)`
+    fn next_unit(&mut self) -> f64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+
+        // Use the top 53 bits for a uniformly distributed f64
+        (z >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Returns `base` scaled by a random factor in `[1 - jitter, 1 + jitter]`
+    fn apply(&mut self, base: Duration) -> Duration {
+        if self.jitter == 0.0 {
+            return base;
+        }
+
+        let factor = 1.0 + self.jitter * (2.0 * self.next_unit() - 1.0);
+        base.mul_f64(factor)
+    }
+}
+
+/// Limits a jitter fraction to `0.0..=1.0`, treating NaN and infinite
+/// values as no jitter
+fn clamp_jitter(jitter: f64) -> f64 {
+    if jitter.is_finite() {
+        jitter.clamp(0.0, 1.0)
+    } else {
+        0.0
+    }
+}
+
+/// Truncates text to at most `max` characters at a word boundary
+///
+/// Text that is already short enough is returned unchanged. Otherwise the
+/// text is cut at the last whitespace that fits and an ellipsis (`…`) is
+/// appended; the result, ellipsis included, is at most `max` characters.
+/// A single word longer than the limit is cut mid-word.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(truncate_at_word("The quick brown fox", 12), "The quick…");
+/// assert_eq!(truncate_at_word("Short", 12), "Short");
+/// ```
+fn truncate_at_word(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+    if max == 0 {
+        return String::new();
+    }
+
+    // Leave room for the ellipsis
+    let cut: String = text.chars().take(max - 1).collect();
+
+    // Back up to the last word boundary, if there is one
+    let truncated = match cut.rfind(char::is_whitespace) {
+        Some(pos) if !cut[..pos].trim_end().is_empty() => cut[..pos].trim_end(),
+        _ => cut.as_str(),
+    };
+
+    format!("{}…", truncated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_server::{html, response, response_bytes, TestServer};
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::Mutex;
+    use tempfile::TempDir;
+
+    /// Returns a quiet config writing into a temporary directory
+    fn test_config(temp_dir: &TempDir) -> CrawlConfig {
+        CrawlConfig::new()
+            .with_output_dir(temp_dir.path().to_str().unwrap())
+            .with_verbose(false)
+    }
+
+    #[test]
+    fn test_crawl_config_default() {
+        let config = CrawlConfig::default();
+        assert_eq!(config.max_pages, Some(50));
+        assert!(config.budget_counts_failures);
+        assert_eq!(config.max_hosts, None);
+        assert_eq!(config.output_dir, "output");
+        assert_eq!(config.output_file, "crawl.jsonl");
+        assert!(config.verbose);
+    }
+
+    #[test]
+    fn test_crawl_config_builder() {
+        let config = CrawlConfig::new()
+            .with_max_pages(100)
+            .with_output_dir("custom_output")
+            .with_output_file("results.jsonl")
+            .with_verbose(false);
+
+        assert_eq!(config.max_pages, Some(100));
+        assert_eq!(config.output_dir, "custom_output");
+        assert_eq!(config.output_file, "results.jsonl");
+        assert!(!config.verbose);
+    }
+
+    #[test]
+    fn test_crawl_config_with_domains() {
+        let domains = vec!["example.com".to_string(), "test.com".to_string()];
+        let config = CrawlConfig::new().with_allowed_domains(domains.clone());
+
+        assert_eq!(config.allowed_domains, Some(domains));
+    }
+
+    #[test]
+    fn test_crawl_config_normalize_options() {
+        let options = NormalizeOptions::new().with_sort_params(true);
+        let config = CrawlConfig::new().with_normalize_options(options.clone());
+        assert_eq!(config.normalize_options, options);
+
+        let mut manager = Spiderman::new("http://a.com").seed_manager(&config);
+        assert!(manager.add_url("http://a.com/s?x=1&y=2"));
+        assert!(!manager.add_url("http://a.com/s?y=2&x=1"));
+    }
+
+    #[test]
+    fn test_crawl_config_max_hosts() {
+        let config = CrawlConfig::new().with_max_hosts(3);
+        assert_eq!(config.max_hosts, Some(3));
+        assert_eq!(
+            Spiderman::new("http://a.com")
+                .seed_manager(&config)
+                .host_count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_crawl_config_raw_html() {
+        let config = CrawlConfig::new().with_raw_html(true);
+        assert!(config.store_raw_html);
+    }
+
+    #[test]
+    fn test_validate_seed() {
+        let config = CrawlConfig::new();
+        assert_eq!(
+            config.validate_seed("example.com").unwrap(),
+            "http://example.com"
+        );
+        assert_eq!(
+            config.validate_seed(" http://localhost:8080/a ").unwrap(),
+            "http://localhost:8080/a"
+        );
+        assert_eq!(
+            config.validate_seed("https://example.com").unwrap(),
+            "https://example.com"
+        );
+
+        for seed in [
+            "",
+            "   ",
+            "htp://example",
+            "ftp://example.com",
+            "http://",
+            "http://:80/",
+        ] {
+            assert!(
+                matches!(config.validate_seed(seed), Err(CrawlError::InvalidSeed { ref url, .. }) if url == seed),
+                "{:?} should be rejected",
+                seed
+            );
+        }
+    }
+
+    #[test]
+    fn test_crawl_https_seed() {
+        let server = linked_server();
+        let seed = server.url("/").replace("http://", "https://");
+        let temp_dir = TempDir::new().unwrap();
+
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            spider.crawl(test_config(&temp_dir)).await.unwrap()
+        });
+
+        // Fetched over plain HTTP, like discovered https links
+        assert_eq!(result.pages_crawled, 4);
+        assert!(server.request_for("/a").is_some());
+    }
+
+    #[test]
+    fn test_crawl_rejects_invalid_seed() {
+        for seed in ["", "htp://example"] {
+            let temp_dir = TempDir::new().unwrap();
+            let events = channel::unbounded();
+            let config = test_config(&temp_dir).with_events(events.0);
+
+            let err = async_std::task::block_on(async {
+                let mut spider = Spiderman::new(seed);
+                spider.crawl(config).await.unwrap_err()
+            });
+
+            assert!(matches!(err, CrawlError::InvalidSeed { .. }), "{:?}", err);
+            // Rejected before crawling: no events, nothing exported
+            assert!(events.1.try_recv().is_err());
+            assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 0);
+        }
+    }
+
+    #[test]
+    fn test_seed_manager_uses_strategy() {
+        let spider = Spiderman::new("http://a.com");
+        let config = CrawlConfig::new().with_strategy(CrawlStrategy::RoundRobinHosts);
+        let mut manager = spider.seed_manager(&config);
+        manager.add_url("http://a.com/1");
+        manager.add_url("http://b.com/1");
+
+        manager.get_next();
+        assert_eq!(manager.get_next(), Some("http://b.com/1".to_string()));
+    }
+
+    #[test]
+    fn test_crawl_config_accept() {
+        assert!(CrawlConfig::default().accept.is_none());
+
+        let options = CrawlConfig::new()
+            .with_accept("application/json")
+            .fetch_options();
+        assert_eq!(
+            options.headers,
+            [("Accept".to_string(), "application/json".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_crawl_follows_feeds() {
+        let feed = r#"<rss><channel>
+            <link>/</link>
+            <item><link>/posts/1</link></item>
+            <item><link>/posts/2</link></item>
+        </channel></rss>"#;
+        let server = TestServer::start(vec![
+            (
+                "/",
+                html(
+                    r#"<head><link rel="alternate" type="application/rss+xml" href="/feed.xml"></head>
+                    <p>Blog home, no post links</p>"#,
+                ),
+            ),
+            (
+                "/feed.xml",
+                response(200, &[("Content-Type", "application/rss+xml")], feed),
+            ),
+            ("/posts/1", html("<p>One</p>")),
+            ("/posts/2", html("<p>Two</p>")),
+        ]);
+        let seed = server.url("/");
+
+        for follow in [false, true] {
+            let temp_dir = TempDir::new().unwrap();
+            let result = async_std::task::block_on(async {
+                let mut spider = Spiderman::new(&seed);
+                let config = test_config(&temp_dir).with_follow_feeds(follow);
+                spider.crawl(config).await.unwrap()
+            });
+
+            let urls: Vec<&str> = result.documents.iter().map(|d| d.url()).collect();
+            if follow {
+                assert_eq!(
+                    urls,
+                    [
+                        server.url(""),
+                        server.url("/posts/1"),
+                        server.url("/posts/2")
+                    ]
+                );
+            } else {
+                assert_eq!(urls, [server.url("")]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_crawl_skips_out_of_scope_feeds() {
+        let other = TestServer::start(vec![(
+            "/feed.xml",
+            response(
+                200,
+                &[("Content-Type", "application/rss+xml")],
+                "<rss><channel><item><link>/elsewhere</link></item></channel></rss>",
+            ),
+        )]);
+        let off_site = other.url("/feed.xml").replace("127.0.0.1", "localhost");
+        let page = format!(
+            r#"<head><link rel="alternate" type="application/rss+xml" href="{}">
+            <link rel="alternate" type="application/atom+xml" href="/feed.atom"></head>"#,
+            off_site
+        );
+        let server = TestServer::start(vec![
+            ("/", html(&page)),
+            (
+                "/feed.atom",
+                response(
+                    200,
+                    &[("Content-Type", "application/atom+xml")],
+                    r#"<feed><entry><link href="/posts/1"/></entry></feed>"#,
+                ),
+            ),
+            ("/posts/1", html("<p>One</p>")),
+        ]);
+        let seed = server.url("/");
+        let temp_dir = TempDir::new().unwrap();
+
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            let config = test_config(&temp_dir)
+                .with_follow_feeds(true)
+                .with_allowed_domains(vec!["127.0.0.1".to_string()])
+                .with_delay(Duration::from_millis(1));
+            spider.crawl(config).await.unwrap()
+        });
+
+        assert!(other.requests().is_empty());
+        assert!(server.request_for("/feed.atom").is_some());
+        assert_eq!(result.pages_crawled, 2);
+        // The seed, the in-scope feed and the article
+        assert_eq!(result.fetch_durations.len(), 3);
+    }
+
+    #[test]
+    fn test_crawl_inventories_assets() {
+        let temp_dir = TempDir::new().unwrap();
+        let server = TestServer::start(vec![(
+            "/",
+            html(
+                r#"<head><link rel="stylesheet" href="/site.css"><script src="/app.js"></script></head>
+                <body><img src="/logo.png"></body>"#,
+            ),
+        )]);
+        let seed = server.url("/");
+
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            let config = test_config(&temp_dir).with_inventory_assets(true);
+            spider.crawl(config).await.unwrap()
+        });
+
+        let assets = result.documents[0].assets().unwrap();
+        assert_eq!(assets.scripts, [server.url("/app.js")]);
+        assert_eq!(assets.styles, [server.url("/site.css")]);
+        assert_eq!(assets.images, [server.url("/logo.png")]);
+        // Listed only, never fetched
+        assert!(server.request_for("/app.js").is_none());
+        assert_eq!(result.pages_crawled, 1);
+    }
+
+    #[test]
+    fn test_crawl_stores_json_verbatim() {
+        let temp_dir = TempDir::new().unwrap();
+        let body = r#"{"items": [{"id": 1, "name": "*widget*"}]}"#;
+        let server = TestServer::start(vec![(
+            "/api/items",
+            response(200, &[("Content-Type", "application/json")], body),
+        )]);
+        let seed = server.url("/api/items");
+
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            let config = test_config(&temp_dir)
+                .with_accept("application/json")
+                .with_strip_boilerplate(true);
+            spider.crawl(config).await.unwrap()
+        });
+
+        let request = server.request_for("/api/items").unwrap();
+        assert!(request.contains("Accept: application/json\r\n"));
+        assert!(!request.contains("text/html"));
+
+        assert_eq!(result.documents.len(), 1);
+        assert_eq!(result.documents[0].content(), body);
+
+        // Nothing was converted or stripped
+        let extraction = result.documents[0].extraction();
+        assert_eq!(extraction.converter, "");
+        assert_eq!(extraction.content_format, "json");
+        assert!(!extraction.stripped_boilerplate);
+        assert!(!extraction.main_content);
+    }
+
+    #[test]
+    fn test_crawl_data_document_density_and_title() {
+        let temp_dir = TempDir::new().unwrap();
+        let body = r#"{"id": 1}"#;
+        let server = TestServer::start(vec![(
+            "/api/items-and-more",
+            response(200, &[("Content-Type", "application/json")], body),
+        )]);
+        let seed = server.url("/api/items-and-more");
+
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            let config = test_config(&temp_dir)
+                .with_accept("application/json")
+                .with_min_text_density(0.9)
+                .with_max_title_len(5);
+            spider.crawl(config).await.unwrap()
+        });
+
+        assert_eq!(result.documents.len(), 1);
+        assert_eq!(result.documents[0].text_density(), 1.0);
+        assert_eq!(result.documents[0].title(), "item…");
+    }
+
+    #[test]
+    fn test_crawl_respects_meta_charset_override() {
+        let mut body =
+            b"<html><head><meta charset=\"windows-1252\"><title>Caf\xe9</title></head>".to_vec();
+        body.extend_from_slice(b"<body><p>Cr\xe8me br\xfbl\xe9e</p></body></html>");
+        let server = TestServer::start(vec![(
+            "/",
+            response_bytes(200, &[("Content-Type", "text/html; charset=utf-8")], &body),
+        )]);
+        let seed = server.url("/");
+
+        let crawl = |respect: bool| {
+            let temp_dir = TempDir::new().unwrap();
+            async_std::task::block_on(async {
+                let mut spider = Spiderman::new(&seed);
+                let config = test_config(&temp_dir).with_respect_meta_charset_override(respect);
+                spider.crawl(config).await.unwrap()
+            })
+        };
+
+        let result = crawl(true);
+        assert_eq!(result.documents[0].title(), "Café");
+        assert!(result.documents[0].content().contains("Crème brûlée"));
+
+        // Without the override the header charset wins
+        let result = crawl(false);
+        assert_eq!(result.documents[0].title(), "Caf\u{fffd}");
+    }
+
+    #[test]
+    fn test_crawl_config_referer() {
+        assert!(!CrawlConfig::default().send_referer);
+        assert!(CrawlConfig::new().with_referer(true).send_referer);
+    }
+
+    #[test]
+    fn test_crawl_sends_parent_as_referer() {
+        let server = TestServer::start(vec![
+            ("/", html(r#"<a href="/child">Child</a>"#)),
+            ("/child", html("<p>Child page</p>")),
+        ]);
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
+
+        async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            let result = spider
+                .crawl(test_config(&temp_dir).with_referer(true))
+                .await
+                .unwrap();
+            assert_eq!(result.pages_crawled, 2);
+        });
+
+        let seed_request = server.request_for("/").unwrap();
+        assert!(!seed_request.contains("Referer:"));
+
+        let child_request = server.request_for("/child").unwrap();
+        assert!(child_request.contains(&format!("Referer: {}\r\n", server.url(""))));
+    }
+
+    #[test]
+    fn test_crawl_without_referer() {
+        let server = TestServer::start(vec![
+            ("/", html(r#"<a href="/child">Child</a>"#)),
+            ("/child", html("<p>Child page</p>")),
+        ]);
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
+
+        async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            spider.crawl(test_config(&temp_dir)).await.unwrap();
+        });
+
+        let child_request = server.request_for("/child").unwrap();
+        assert!(!child_request.contains("Referer:"));
+    }
+
+    #[test]
+    fn test_should_store_status() {
+        let config = CrawlConfig::default();
+        assert!(config.should_store_status(200));
+        assert!(!config.should_store_status(404));
+
+        let config = config.with_store_status_codes(vec![404, 301]);
+        assert!(config.should_store_status(200));
+        assert!(config.should_store_status(404));
+        assert!(config.should_store_status(301));
+        assert!(!config.should_store_status(500));
+    }
+
+    /// Serves a seed page linking to a page that returns 404
+    fn not_found_server() -> TestServer {
+        TestServer::start(vec![
+            ("/", html(r#"<a href="/missing">Missing</a>"#)),
+            (
+                "/missing",
+                response(404, &[], "<title>Not Found</title><p>Gone</p>"),
+            ),
+        ])
+    }
+
+    #[test]
+    fn test_crawl_counts_404_as_failure_by_default() {
+        let server = not_found_server();
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
+
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            spider.crawl(test_config(&temp_dir)).await.unwrap()
+        });
+
+        assert_eq!(result.pages_crawled, 1);
+        assert_eq!(result.pages_failed, 1);
+        assert_eq!(result.documents[0].status(), Some(200));
+    }
+
+    #[test]
+    fn test_crawl_stores_allowed_status_codes() {
+        let server = not_found_server();
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
+
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            let config = test_config(&temp_dir).with_store_status_codes(vec![404]);
+            spider.crawl(config).await.unwrap()
+        });
+
+        assert_eq!(result.pages_crawled, 2);
+        assert_eq!(result.pages_failed, 0);
+
+        let missing = result
+            .documents
+            .iter()
+            .find(|doc| doc.url().ends_with("/missing"))
+            .unwrap();
+        assert_eq!(missing.status(), Some(404));
+        assert_eq!(missing.title(), "Not Found");
+
+        let exported = std::fs::read_to_string(temp_dir.path().join("crawl.jsonl")).unwrap();
+        assert_eq!(exported.lines().count(), 2);
+        assert!(exported.contains(r#""status":404"#));
+    }
+
+    /// Serves a seed page linking to the given paths, all of which return 500
+    /// except `/ok`
+    fn failing_server(paths: &[&str]) -> TestServer {
+        let links: String = paths
+            .iter()
+            .map(|path| format!(r#"<a href="{}">link</a>"#, path))
+            .collect();
+        let mut routes = vec![("/", html(&links)), ("/ok", html("<p>Fine</p>"))];
+        for path in paths.iter().filter(|path| **path != "/ok") {
+            routes.push((path, response(500, &[], "Server Error")));
+        }
+        TestServer::start(routes)
+    }
+
+    #[test]
+    fn test_crawl_config_max_failures() {
+        let config = CrawlConfig::default();
+        assert_eq!(config.max_failures, None);
+        assert_eq!(config.failure_count, FailureCount::Total);
+
+        let config = config
+            .with_max_failures(5)
+            .with_failure_count(FailureCount::Consecutive);
+        assert_eq!(config.max_failures, Some(5));
+        assert_eq!(config.failure_count, FailureCount::Consecutive);
+    }
+
+    #[test]
+    fn test_crawl_aborts_after_total_failures() {
+        let server = failing_server(&["/f1", "/f2", "/ok", "/f3", "/f4", "/f5"]);
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
+
+        let err = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            let config = test_config(&temp_dir).with_max_failures(3);
+            spider.crawl(config).await.unwrap_err()
+        });
+
+        match err {
+            CrawlError::TooManyFailures { failures, result } => {
+                assert_eq!(failures, 3);
+                assert_eq!(result.pages_failed, 3);
+                assert_eq!(result.pages_crawled, 2);
+            }
+            other => panic!("unexpected error: {}", other),
+        }
+        assert!(server.request_for("/f4").is_none());
+    }
+
+    #[test]
+    fn test_crawl_aborts_after_consecutive_failures() {
+        let server = failing_server(&["/f1", "/f2", "/ok", "/f3", "/f4", "/f5"]);
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
+
+        let err = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            let config = test_config(&temp_dir)
+                .with_max_failures(3)
+                .with_failure_count(FailureCount::Consecutive);
+            spider.crawl(config).await.unwrap_err()
+        });
+
+        match err {
+            CrawlError::TooManyFailures { failures, result } => {
+                assert_eq!(failures, 3);
+                assert_eq!(result.pages_failed, 5);
+                assert_eq!(result.pages_crawled, 2);
+            }
+            other => panic!("unexpected error: {}", other),
+        }
+    }
+
+    #[test]
+    fn test_crawl_budget_counts_failures_by_default() {
+        let server = failing_server(&["/f1", "/f2", "/ok", "/f3", "/ok2"]);
+        server.route("/ok2", html("<p>Fine</p>"));
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
+
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            let config = test_config(&temp_dir).with_max_pages(4);
+            spider.crawl(config).await.unwrap()
+        });
+
+        assert_eq!(result.pages_crawled, 2);
+        assert_eq!(result.pages_failed, 2);
+        assert!(server.request_for("/f3").is_none());
+    }
+
+    #[test]
+    fn test_crawl_budget_of_successful_pages() {
+        let server = failing_server(&["/f1", "/f2", "/ok", "/f3", "/ok2", "/ok3", "/ok4"]);
+        for path in ["/ok2", "/ok3", "/ok4"] {
+            server.route(path, html("<p>Fine</p>"));
+        }
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
+
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            let config = test_config(&temp_dir)
+                .with_max_pages(4)
+                .with_budget_counts_failures(false)
+                .with_concurrency(3);
+            spider.crawl(config).await.unwrap()
+        });
+
+        assert_eq!(result.pages_crawled, 4);
+        assert_eq!(result.documents.len(), 4);
+        assert_eq!(result.pages_failed, 3);
+        assert!(server.request_for("/ok4").is_none());
+    }
+
+    // ===== Progress Event Tests =====
+
+    #[test]
+    fn test_crawl_with_progress_ends_with_finished() {
+        let server = failing_server(&["/ok", "/f1"]);
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
+
+        let (result, events) = async_std::task::block_on(async {
+            let spider = Spiderman::new(&seed);
+            let (handle, receiver) = spider.crawl_with_progress(test_config(&temp_dir));
+
+            let mut events = Vec::new();
+            while let Ok(event) = receiver.recv().await {
+                events.push(event);
+            }
+            (handle.await, events)
+        });
+
+        assert_eq!(events.len(), 4);
+        assert!(matches!(
+            events[0],
+            CrawlEvent::PageCrawled { status: 200, .. }
+        ));
+        assert!(matches!(
+            events[1],
+            CrawlEvent::PageCrawled { ref url, status: 200 } if url.ends_with("/ok")
+        ));
+        assert!(matches!(
+            events[2],
+            CrawlEvent::PageFailed { ref url, ref error }
+                if url.ends_with("/f1") && error.contains("HTTP status 500")
+        ));
+
+        match events.last() {
+            Some(CrawlEvent::Finished(finished)) => {
+                assert_eq!(finished.pages_crawled, 2);
+                assert_eq!(finished.pages_failed, 1);
+                assert_eq!(finished.documents.len(), result.documents.len());
+            }
+            other => panic!("unexpected last event: {:?}", other),
+        }
+        assert_eq!(result.pages_crawled, 2);
+    }
+
+    #[test]
+    fn test_crawl_with_progress_invalid_seed_finishes() {
+        let temp_dir = TempDir::new().unwrap();
+        let spider = Spiderman::new("htp://example");
+
+        let (result, events) = async_std::task::block_on(async {
+            let (handle, receiver) = spider.crawl_with_progress(test_config(&temp_dir));
+            let mut events = Vec::new();
+            while let Ok(event) = receiver.recv().await {
+                events.push(event);
+            }
+            (handle.await, events)
+        });
+
+        assert_eq!(result.pages_crawled, 0);
+        assert_eq!(events.len(), 1);
+        assert!(
+            matches!(events[0], CrawlEvent::Finished(ref result) if result.documents.is_empty())
+        );
+    }
+
+    #[test]
+    fn test_crawl_with_progress_after_abort() {
+        let server = failing_server(&["/f1", "/f2", "/ok"]);
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
+
+        let (result, last) = async_std::task::block_on(async {
+            let spider = Spiderman::new(&seed);
+            let config = test_config(&temp_dir).with_max_failures(2);
+            let (handle, receiver) = spider.crawl_with_progress(config);
+
+            let mut last = None;
+            while let Ok(event) = receiver.recv().await {
+                last = Some(event);
+            }
+            (handle.await, last)
+        });
+
+        // The partial result is returned and reported
+        assert_eq!(result.pages_failed, 2);
+        assert!(matches!(last, Some(CrawlEvent::Finished(ref r)) if r.pages_failed == 2));
+    }
+
+    #[test]
+    fn test_crawl_below_failure_limit_succeeds() {
+        let server = failing_server(&["/f1", "/ok"]);
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
+
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            let config = test_config(&temp_dir).with_max_failures(2);
+            spider.crawl(config).await.unwrap()
+        });
+
+        assert_eq!(result.pages_failed, 1);
+        assert_eq!(result.pages_crawled, 2);
+    }
+
+    // ===== Control Tests =====
+
+    /// Serves a seed linking to three pages
+    fn linked_server() -> TestServer {
+        TestServer::start(vec![
+            (
+                "/",
+                html(r#"<a href="/a">A</a><a href="/b">B</a><a href="/c">C</a>"#),
+            ),
+            ("/a", html("<p>A</p>")),
+            ("/b", html("<p>B</p>")),
+            ("/c", html("<p>C</p>")),
+        ])
+    }
+
+    #[test]
+    fn test_crawl_control_state_transitions() {
+        let control = CrawlControl::new();
+        assert_eq!(control.state(), CrawlState::Running);
+
+        control.pause();
+        assert_eq!(control.clone().state(), CrawlState::Paused);
+
+        control.resume();
+        assert_eq!(control.state(), CrawlState::Running);
+
+        control.stop();
+        control.resume();
+        assert_eq!(control.state(), CrawlState::Stopped);
+    }
+
+    #[test]
+    fn test_crawl_pause_and_resume_crawls_all_pages() {
+        let uninterrupted = {
+            let server = linked_server();
+            let temp_dir = TempDir::new().unwrap();
+            let seed = server.url("/");
+            async_std::task::block_on(async {
+                let mut spider = Spiderman::new(&seed);
+                spider.crawl(test_config(&temp_dir)).await.unwrap()
+            })
+        };
+
+        let server = linked_server();
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
+        let control = CrawlControl::new();
+        control.pause();
+
+        let config = test_config(&temp_dir).with_control(control.clone());
+        let handle = std::thread::spawn(move || {
+            async_std::task::block_on(async {
+                let mut spider = Spiderman::new(&seed);
+                spider.crawl(config).await.unwrap()
+            })
+        });
+
+        // Nothing is fetched while paused
+        std::thread::sleep(Duration::from_millis(150));
+        assert!(server.requests().is_empty());
+
+        control.resume();
+        let resumed = handle.join().unwrap();
+
+        assert_eq!(resumed.pages_crawled, uninterrupted.pages_crawled);
+        assert_eq!(resumed.pages_crawled, 4);
+    }
+
+    #[test]
+    fn test_crawl_stopped_before_start() {
+        let server = linked_server();
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
+        let control = CrawlControl::new();
+        control.stop();
+
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            let config = test_config(&temp_dir).with_control(control);
+            spider.crawl(config).await.unwrap()
+        });
+
+        assert_eq!(result.pages_crawled, 0);
+        assert!(server.requests().is_empty());
+    }
+
+    // ===== Finish Hook Tests =====
+
+    #[test]
+    fn test_crawl_runs_finish_hook() {
+        let server = linked_server();
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
+        let finished = Arc::new(Mutex::new(None));
+
+        let config = test_config(&temp_dir).with_on_finish({
+            let finished = Arc::clone(&finished);
+            move |result| *finished.lock().unwrap() = Some(result.pages_crawled)
+        });
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            spider.crawl(config).await.unwrap()
+        });
+
+        assert_eq!(*finished.lock().unwrap(), Some(result.pages_crawled));
+    }
+
+    #[test]
+    fn test_crawl_runs_finish_hook_when_stopped() {
+        let server = linked_server();
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
+        let control = CrawlControl::new();
+        control.stop();
+        let finished = Arc::new(Mutex::new(None));
+
+        let config = test_config(&temp_dir)
+            .with_control(control)
+            .with_on_finish({
+                let finished = Arc::clone(&finished);
+                move |result| *finished.lock().unwrap() = Some(result.pages_crawled)
+            });
+        let mut spider = Spiderman::new(&seed);
+        async_std::task::block_on(spider.crawl(config.clone())).unwrap();
+        assert!(server.requests().is_empty());
+        assert_eq!(*finished.lock().unwrap(), Some(0));
+
+        // The hook runs once, even when the config is reused
+        *finished.lock().unwrap() = None;
+        async_std::task::block_on(spider.crawl(config)).unwrap();
+        assert_eq!(*finished.lock().unwrap(), None);
+    }
+
+    // ===== Pagination Tests =====
+
+    /// Serves a list page whose `rel="next"` link comes after other links
+    fn paginated_server() -> TestServer {
+        TestServer::start(vec![
+            (
+                "/",
+                html(
+                    r#"<a href="/a">A</a><a href="/b">B</a>
+                       <a rel="next" href="/page/2">Next</a>"#,
+                ),
+            ),
+            ("/a", html("<p>A</p>")),
+            ("/b", html("<p>B</p>")),
+            ("/page/2", html("<p>Page 2</p>")),
+        ])
+    }
+
+    fn crawled_paths(server: &TestServer) -> Vec<String> {
+        server
+            .requests()
+            .iter()
+            .filter_map(|r| r.split_whitespace().nth(1).map(|p| p.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_crawl_prioritizes_rel_next() {
+        let server = paginated_server();
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
+
+        async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            let config = test_config(&temp_dir).with_prioritize_next(true);
+            spider.crawl(config).await.unwrap();
+        });
+
+        assert_eq!(crawled_paths(&server), vec!["/", "/page/2", "/a", "/b"]);
+    }
+
+    #[test]
+    fn test_crawl_without_priority_keeps_link_order() {
+        let server = paginated_server();
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
+
+        async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            spider.crawl(test_config(&temp_dir)).await.unwrap();
+        });
+
+        assert_eq!(crawled_paths(&server), vec!["/", "/a", "/b", "/page/2"]);
+    }
+
+    // ===== URL Rewriter Tests =====
+
+    #[test]
+    fn test_crawl_url_rewriter_dedups_hosts() {
+        // `localhost` plays the vanity domain, `127.0.0.1` the canonical one
+        let server = TestServer::start(vec![("/page", html("<p>Page</p>"))]);
+        let vanity = server.url("/page").replace("127.0.0.1", "localhost");
+        server.route(
+            "/",
+            html(&format!(
+                r#"<a href="{}">Vanity</a><a href="{}">Canonical</a>"#,
+                vanity,
+                server.url("/page")
+            )),
+        );
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
+
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            let config = test_config(&temp_dir)
+                .with_url_rewriter(|url| url.replace("://localhost", "://127.0.0.1"));
+            spider.crawl(config).await.unwrap()
+        });
+
+        assert_eq!(result.pages_crawled, 2);
+        let page_fetches = server
+            .requests()
+            .iter()
+            .filter(|r| r.starts_with("GET /page "))
+            .count();
+        assert_eq!(page_fetches, 1);
+        assert_eq!(result.documents[1].url(), server.url("/page"));
+    }
+
+    // ===== Default Port Tests =====
+
+    #[test]
+    fn test_crawl_uses_default_port() {
+        let server = TestServer::start(vec![
+            ("/", html(r#"<a href="/child">Child</a>"#)),
+            ("/child", html("<p>Child</p>")),
+        ]);
+        let port: u16 = server.url("").rsplit(':').next().unwrap().parse().unwrap();
+        let temp_dir = TempDir::new().unwrap();
+
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new("http://127.0.0.1/");
+            let config = test_config(&temp_dir).with_default_port(port);
+            spider.crawl(config).await.unwrap()
+        });
+
+        assert_eq!(result.pages_crawled, 2);
+        assert!(server.request_for("/child").is_some());
+    }
+
+    #[test]
+    fn test_crawl_schemeless_seed_uses_http() {
+        let server = TestServer::start(vec![
+            ("/", html(r#"<a href="/child">Child</a>"#)),
+            ("/child", html("<p>Child</p>")),
+        ]);
+        let host = server.url("").replace("http://", "");
+        let temp_dir = TempDir::new().unwrap();
+
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&host);
+            spider.crawl(test_config(&temp_dir)).await.unwrap()
+        });
+
+        // The seed and the links resolved against it use http
+        assert_eq!(result.pages_crawled, 2);
+        assert!(server.request_for("/child").is_some());
+        let seed = format!("http://{}", host);
+        assert!(result.documents.iter().any(|d| d.url() == seed));
+        assert!(result
+            .documents
+            .iter()
+            .any(|d| d.url() == format!("{}/child", seed)));
+    }
+
+    #[test]
+    fn test_crawl_explicit_http_seed_keeps_scheme() {
+        let server = linked_server();
+        let seed = server.url("/");
+        let temp_dir = TempDir::new().unwrap();
+
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            spider.crawl(test_config(&temp_dir)).await.unwrap()
+        });
+
+        assert!(!result.documents.is_empty());
+        assert!(result
+            .documents
+            .iter()
+            .all(|d| d.url().starts_with("http://")));
+    }
+
+    #[test]
+    fn test_crawl_follows_get_forms() {
+        let server = TestServer::start(vec![
+            (
+                "/",
+                html(
+                    r#"<form method="get" action="/search"></form>
+                       <form method="post" action="/login"></form>"#,
+                ),
+            ),
+            ("/search", html("<p>Results</p>")),
+            ("/login", html("<p>Login</p>")),
+        ]);
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
+
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            let config = test_config(&temp_dir).with_follow_forms(true);
+            spider.crawl(config).await.unwrap()
+        });
+
+        assert_eq!(result.pages_crawled, 2);
+        assert!(server.request_for("/search").is_some());
+        assert!(server.request_for("/login").is_none());
+    }
+
+    #[test]
+    fn test_run_with_config() {
+        let server = TestServer::start(vec![
+            ("/", html(r#"<a href="/a">A</a><a href="/b">B</a>"#)),
+            ("/a", html("<p>A</p>")),
+            ("/b", html("<p>B</p>")),
+        ]);
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
+
+        let result = async_std::task::block_on(async {
+            let config = test_config(&temp_dir).with_max_pages(2);
+            Spiderman::with_config(&seed, config).run().await.unwrap()
+        });
+
+        // The stored config's page limit applies
+        assert_eq!(result.pages_crawled, 2);
+        assert!(temp_dir.path().join("crawl.jsonl").exists());
+    }
+
+    // ===== Custom Manager Tests =====
+
+    #[test]
+    fn test_crawl_with_manager_skips_visited() {
+        let server = TestServer::start(vec![
+            ("/", html(r#"<a href="/b">B</a><a href="/d">D</a>"#)),
+            ("/a", html("<p>A</p>")),
+            ("/b", html("<p>B</p>")),
+            ("/c", html("<p>C</p>")),
+            ("/d", html("<p>D</p>")),
+        ]);
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
+
+        let mut manager = UrlManager::new(&seed);
+        manager.add_url(&server.url("/a"));
+        manager.add_url(&server.url("/c"));
+        manager.mark_visited(&server.url("/b"));
+        assert_eq!(manager.queue_size(), 3);
+
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            spider
+                .crawl_with_manager(manager, test_config(&temp_dir))
+                .await
+                .unwrap()
+        });
+
+        // The three queued URLs and the newly discovered /d
+        assert_eq!(result.pages_crawled, 4);
+        assert!(server.request_for("/a").is_some());
+        assert!(server.request_for("/c").is_some());
+        assert!(server.request_for("/d").is_some());
+        assert!(server.request_for("/b").is_none());
+    }
+
+    #[test]
+    fn test_crawl_with_manager_resumes_after_seed() {
+        let server = TestServer::start(vec![
+            ("/", html(r#"<a href="/a">A</a><a href="/b">B</a>"#)),
+            ("/a", html(r#"<a href="/c">C</a>"#)),
+            ("/b", html("<p>B</p>")),
+            ("/c", html("<p>C</p>")),
+        ]);
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
+
+        // A manager saved right after the seed was crawled
+        let mut manager = UrlManager::new(&seed);
+        manager.get_next();
+        manager.add_url(&server.url("/a"));
+        manager.add_url(&server.url("/b"));
+
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            spider
+                .crawl_with_manager(manager, test_config(&temp_dir))
+                .await
+                .unwrap()
+        });
+
+        assert!(server.request_for("/").is_none());
+        assert_eq!(result.pages_crawled, 3);
+        let urls: Vec<&str> = result.documents.iter().map(|d| d.url()).collect();
+        assert_eq!(urls, [server.url("/a"), server.url("/b"), server.url("/c")]);
+    }
+
+    #[test]
+    fn test_crawl_with_manager_resumed_redirect_does_not_widen_scope() {
+        let server = TestServer::start(vec![]);
+        server.route(
+            "/jump",
+            response(302, &[("Location", &server.url("/landing"))], ""),
+        );
+        server.route(
+            "/landing",
+            html(&format!(r#"<a href="{}">Next</a>"#, server.url("/next"))),
+        );
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/").replace("127.0.0.1", "localhost");
+        let jump = server.url("/jump").replace("127.0.0.1", "localhost");
+
+        // The seed was crawled before; the next queued URL is no seed, so
+        // its redirect to another host doesn't bring that host into scope
+        let mut manager = UrlManager::new(&seed);
+        manager.set_allowed_domains(vec!["localhost".to_string()]);
+        manager.get_next();
+        manager.add_url(&jump);
+
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            spider
+                .crawl_with_manager(manager, test_config(&temp_dir))
+                .await
+                .unwrap()
+        });
+
+        assert_eq!(result.pages_crawled, 1);
+        assert!(server.request_for("/").is_none());
+        assert!(server.request_for("/next").is_none());
+    }
+
+    #[test]
+    fn test_crawl_delta_fetches_only_new_children() {
+        let server = TestServer::start(vec![
+            ("/", html(r#"<a href="/old">Old</a><a href="/new">New</a>"#)),
+            ("/old", html("<p>Old</p>")),
+            ("/new", html("<p>New</p>")),
+        ]);
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
+        let prior: HashSet<String> = [server.url(""), server.url("/old/")].into_iter().collect();
+
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            spider
+                .crawl_delta(&prior, test_config(&temp_dir))
+                .await
+                .unwrap()
+        });
+
+        // The seed is fetched for its links but not exported again
+        assert!(server.request_for("/").is_some());
+        assert!(server.request_for("/old").is_none());
+        assert!(server.request_for("/new").is_some());
+
+        assert_eq!(result.documents.len(), 1);
+        assert!(result.documents[0].url().ends_with("/new"));
+        let exported = std::fs::read_to_string(temp_dir.path().join("crawl.jsonl")).unwrap();
+        assert_eq!(exported.lines().count(), 1);
+    }
+
+    #[test]
+    fn test_recrawl_classifies_pages() {
+        let server = TestServer::start(vec![
+            (
+                "/",
+                html(r#"<a href="/a">A</a><a href="/b">B</a><a href="/d">D</a>"#),
+            ),
+            (
+                "/a",
+                response(
+                    200,
+                    &[("Content-Type", "text/html"), ("ETag", "\"v1\"")],
+                    r#"<p>A</p><a href="/e">E</a>"#,
+                ),
+            ),
+            ("/b", html("<p>B, first version</p>")),
+            ("/d", html("<p>D</p>")),
+            ("/e", html("<p>E</p>")),
+        ]);
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
+
+        let first = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            spider.crawl(test_config(&temp_dir)).await.unwrap()
+        });
+        assert_eq!(first.documents.len(), 5);
+        assert!(first.changes.is_empty());
+
+        // The seed gains a link, /a is not modified and /b changes
+        server.route(
+            "/",
+            html(r#"<a href="/a">A</a><a href="/b">B</a><a href="/c">C</a><a href="/d">D</a>"#),
+        );
+        server.route("/a", response(304, &[("ETag", "\"v1\"")], ""));
+        server.route("/b", html("<p>B, second version</p>"));
+        server.route("/c", html("<p>C</p>"));
+
+        let second_dir = TempDir::new().unwrap();
+        let second = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            spider
+                .recrawl(&first, test_config(&second_dir))
+                .await
+                .unwrap()
+        });
+
+        let change = |path: &str| second.changes.get(&server.url(path)).copied();
+        assert_eq!(change(""), Some(PageChange::Updated));
+        assert_eq!(change("/a"), Some(PageChange::Unchanged));
+        assert_eq!(change("/b"), Some(PageChange::Updated));
+        assert_eq!(change("/c"), Some(PageChange::New));
+        assert_eq!(change("/d"), Some(PageChange::Unchanged));
+        assert_eq!(change("/e"), Some(PageChange::Unchanged));
+
+        // /a was revalidated with its ETag
+        let requests: Vec<String> = server
+            .requests()
+            .into_iter()
+            .filter(|r| r.starts_with("GET /a "))
+            .collect();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[1].contains("If-None-Match: \"v1\""));
+
+        // Only new and updated pages are exported
+        let mut exported: Vec<&str> = second.documents.iter().map(|d| d.url()).collect();
+        exported.sort();
+        assert_eq!(
+            exported,
+            vec![server.url(""), server.url("/b"), server.url("/c")]
+        );
+        assert_eq!(second.pages_crawled, 6);
+    }
+
+    // ===== Output Format Tests =====
+
+    #[test]
+    fn test_crawl_splits_output_by_domain() {
+        // `127.0.0.1` and `localhost` play two sites on the same server
+        let server = TestServer::start(vec![("/a", html("<p>A</p>")), ("/b", html("<p>B</p>"))]);
+        let other = server.url("/b").replace("127.0.0.1", "localhost");
+        server.route(
+            "/",
+            html(&format!(r#"<a href="/a">A</a><a href="{}">B</a>"#, other)),
+        );
+        let seed = server.url("/");
+
+        for (format, extension) in [
+            (OutputFormat::Jsonl, "jsonl"),
+            (OutputFormat::JsonArray, "json"),
+        ] {
+            let temp_dir = TempDir::new().unwrap();
+            let result = async_std::task::block_on(async {
+                let mut spider = Spiderman::new(&seed);
+                let config = test_config(&temp_dir)
+                    .with_output_file(&format!("crawl.{}", extension))
+                    .with_output_format(format)
+                    .with_allowed_domains(vec!["127.0.0.1".to_string(), "localhost".to_string()])
+                    .with_split_output_by_domain(true);
+                spider.crawl(config).await.unwrap()
+            });
+            assert_eq!(result.documents.len(), 3);
+
+            let read = |domain: &str| -> Vec<String> {
+                let path = temp_dir.path().join(format!("{}.{}", domain, extension));
+                let content = std::fs::read_to_string(path).unwrap();
+                let documents: Vec<Document> = match format {
+                    OutputFormat::Jsonl => content
+                        .lines()
+                        .map(|line| serde_json::from_str(line).unwrap())
+                        .collect(),
+                    _ => serde_json::from_str(&content).unwrap(),
+                };
+                documents.iter().map(|d| d.url().to_string()).collect()
+            };
+
+            let mut local = read("127.0.0.1");
+            local.sort();
+            assert_eq!(local, vec![server.url(""), server.url("/a")]);
+            assert_eq!(read("localhost"), vec![other.clone()]);
+            assert!(!temp_dir
+                .path()
+                .join(format!("crawl.{}", extension))
+                .exists());
+        }
+    }
+
+    #[test]
+    fn test_crawl_output_formats() {
+        let server = linked_server();
+        let seed = server.url("/");
+
+        for (format, file) in [
+            (OutputFormat::Jsonl, "crawl.jsonl"),
+            (OutputFormat::JsonArray, "crawl.json"),
+            (OutputFormat::Csv, "crawl.csv"),
+            (OutputFormat::Xml, "crawl.xml"),
+        ] {
+            let temp_dir = TempDir::new().unwrap();
+            let result = async_std::task::block_on(async {
+                let mut spider = Spiderman::new(&seed);
+                let config = test_config(&temp_dir)
+                    .with_output_file(file)
+                    .with_output_format(format);
+                spider.crawl(config).await.unwrap()
+            });
+            let count = result.documents.len();
+            assert_eq!(count, 4);
+
+            let content = std::fs::read_to_string(temp_dir.path().join(file)).unwrap();
+            match format {
+                OutputFormat::Jsonl => {
+                    let lines: Vec<&str> = content.lines().collect();
+                    assert_eq!(lines.len(), count);
+                    for line in lines {
+                        serde_json::from_str::<Document>(line).unwrap();
+                    }
+                }
+                OutputFormat::JsonArray => {
+                    let docs: Vec<Document> = serde_json::from_str(&content).unwrap();
+                    assert_eq!(docs.len(), count);
+                }
+                OutputFormat::Csv => {
+                    assert!(content.starts_with("url,title,"));
+                    for doc in &result.documents {
+                        assert!(content.contains(doc.url()));
+                    }
+                }
+                OutputFormat::Xml => {
+                    assert!(content.starts_with("<?xml"));
+                    assert_eq!(content.matches("<document>").count(), count);
+                    assert_eq!(content.matches("</document>").count(), count);
+                }
+            }
+        }
+    }
+
+    // ===== Fetch List Tests =====
+
+    #[test]
+    fn test_fetch_all_fetches_only_given_urls() {
+        let server = TestServer::start(vec![
+            ("/a", html(r#"<title>A</title><a href="/x">X</a>"#)),
+            ("/b", html(r#"<title>B</title><a href="/y">Y</a>"#)),
+            ("/c", html(r#"<title>C</title><a href="/a">A</a>"#)),
+            ("/x", html("<p>X</p>")),
+            ("/y", html("<p>Y</p>")),
+        ]);
+        let urls = vec![server.url("/a"), server.url("/b"), server.url("/c")];
+        let temp_dir = TempDir::new().unwrap();
+
+        let results = async_std::task::block_on(async {
+            let spider = Spiderman::new("unused.example");
+            let config = test_config(&temp_dir).with_concurrency(2);
+            spider.fetch_all(urls.clone(), config).await
+        });
+
+        let docs: Vec<Document> = results.into_iter().map(|r| r.unwrap()).collect();
+        assert_eq!(docs.len(), 3);
+        let titles: Vec<&str> = docs.iter().map(|d| d.title()).collect();
+        assert_eq!(titles, vec!["A", "B", "C"]);
+        assert!(docs.iter().all(|d| d.links().is_empty()));
+
+        assert_eq!(server.requests().len(), 3);
+        assert!(server.request_for("/x").is_none());
+        assert!(server.request_for("/y").is_none());
+    }
+
+    #[test]
+    fn test_fetch_all_reports_failures_per_url() {
+        let server = TestServer::start(vec![("/ok", html("<p>OK</p>"))]);
+        let urls = vec![server.url("/ok"), server.url("/missing")];
+        let temp_dir = TempDir::new().unwrap();
+
+        let results = async_std::task::block_on(async {
+            let spider = Spiderman::new("unused.example");
+            spider.fetch_all(urls, test_config(&temp_dir)).await
+        });
+
+        assert!(results[0].is_ok());
+        assert!(matches!(results[1], Err(CrawlError::Fetch(ref msg)) if msg == "HTTP status 404"));
+    }
+
+    #[test]
+    fn test_fetch_all_dispatches_on_content_type() {
+        let body = r#"{"id": 1}"#;
+        let server = TestServer::start(vec![
+            ("/page", html("<h1>Page</h1>")),
+            (
+                "/data",
+                response(200, &[("Content-Type", "application/json")], body),
+            ),
+            (
+                "/image",
+                response(200, &[("Content-Type", "image/png")], "png"),
+            ),
+        ]);
+        let host = server.url("").replace("http://", "");
+        let urls = vec![
+            format!("{}/page", host),
+            server.url("/data"),
+            server.url("/image"),
+        ];
+        let temp_dir = TempDir::new().unwrap();
+
+        let results = async_std::task::block_on(async {
+            let spider = Spiderman::new("unused.example");
+            let config = test_config(&temp_dir).with_allowed_content_types(vec![
+                "text/html".to_string(),
+                "application/json".to_string(),
+            ]);
+            spider.fetch_all(urls, config).await
+        });
+
+        // The schemeless URL gets http, like a seed
+        let page = results[0].as_ref().unwrap();
+        assert_eq!(page.url(), format!("http://{}/page", host));
+        assert!(page.content().contains("Page"));
+
+        let data = results[1].as_ref().unwrap();
+        assert_eq!(data.content(), body);
+        assert_eq!(data.extraction().content_format, "json");
+
+        assert!(matches!(results[2], Err(CrawlError::Fetch(ref msg)) if msg.contains("image/png")));
+    }
+
+    // ===== Concurrency Tests =====
+
+    /// Serves a seed page linking to `count` child pages
+    fn fan_out_server(count: usize) -> TestServer {
+        let links: String = (0..count)
+            .map(|i| format!(r#"<a href="/page{}">Page {}</a>"#, i, i))
+            .collect();
+        let mut routes = vec![("/".to_string(), html(&links))];
+        for i in 0..count {
+            routes.push((format!("/page{}", i), html("<p>Child</p>")));
+        }
+
+        TestServer::start(
+            routes
+                .iter()
+                .map(|(path, body)| (path.as_str(), body.clone()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_crawl_per_depth_limits() {
+        // The seed links to /a0..a4, and each /aN to /aN/b0../aN/b4
+        let mut routes = vec![(
+            "/".to_string(),
+            html(
+                &(0..5)
+                    .map(|i| format!(r#"<a href="/a{}">A</a>"#, i))
+                    .collect::<String>(),
+            ),
+        )];
+        for i in 0..5 {
+            let links: String = (0..5)
+                .map(|j| format!(r#"<a href="/a{}/b{}">B</a>"#, i, j))
+                .collect();
+            routes.push((format!("/a{}", i), html(&links)));
+            for j in 0..5 {
+                routes.push((format!("/a{}/b{}", i, j), html("<p>Leaf</p>")));
+            }
+        }
+        let server = TestServer::start(
+            routes
+                .iter()
+                .map(|(path, body)| (path.as_str(), body.clone()))
+                .collect(),
+        );
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
+
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            let config = test_config(&temp_dir)
+                .with_max_pages(100)
+                .with_per_depth_limits(vec![1, 2, 3]);
+            spider.crawl(config).await.unwrap()
+        });
+
+        let depth_of = |doc: &Document| doc.url().matches('/').count() - 2;
+        let at_depth = |depth: usize| {
+            result
+                .documents
+                .iter()
+                .filter(|doc| depth_of(doc) == depth)
+                .count()
+        };
+        assert_eq!(at_depth(0), 1);
+        assert_eq!(at_depth(1), 2);
+        assert_eq!(at_depth(2), 3);
+        assert_eq!(result.pages_crawled, 6);
+    }
+
+    #[test]
+    fn test_adaptive_concurrency_drops_and_recovers() {
+        let mut adaptive = AdaptiveConcurrency::new(8);
+        assert_eq!(adaptive.limit(), 8);
+
+        // Too few samples to judge
+        for _ in 0..4 {
+            adaptive.record(false);
+        }
+        assert_eq!(adaptive.adjust(), None);
+
+        adaptive.record(false);
+        assert_eq!(adaptive.adjust(), Some(4));
+        for _ in 0..5 {
+            adaptive.record(false);
+        }
+        assert_eq!(adaptive.adjust(), Some(2));
+        for _ in 0..5 {
+            adaptive.record(false);
+        }
+        assert_eq!(adaptive.adjust(), Some(1));
+        for _ in 0..5 {
+            adaptive.record(false);
+        }
+        assert_eq!(adaptive.adjust(), None);
+
+        // Healthy again: one step up per check, capped at the maximum
+        let mut limits = Vec::new();
+        for _ in 0..12 {
+            adaptive.record(true);
+            limits.extend(adaptive.adjust());
+        }
+        assert_eq!(limits, [2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(adaptive.limit(), 8);
+
+        // A mixed window keeps the limit
+        for success in [
+            true, false, true, true, false, true, true, false, true, true,
+        ] {
+            adaptive.record(success);
+        }
+        assert_eq!(adaptive.adjust(), None);
+    }
+
+    #[test]
+    fn test_crawl_adaptive_concurrency() {
+        // The first ten pages fail, the next twenty succeed
+        let server = fan_out_server(30);
+        for i in 0..10 {
+            server.route(&format!("/page{}", i), response(500, &[], "Server Error"));
+        }
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
+
+        let (result, events) = async_std::task::block_on(async {
+            let spider = Spiderman::new(&seed);
+            let config = test_config(&temp_dir)
+                .with_max_pages(100)
+                .with_concurrency(4)
+                .with_adaptive_concurrency(true);
+            let (handle, receiver) = spider.crawl_with_progress(config);
+
+            let mut events = Vec::new();
+            while let Ok(event) = receiver.recv().await {
+                events.push(event);
+            }
+            (handle.await, events)
+        });
+
+        let limits: Vec<usize> = events
+            .iter()
+            .filter_map(|event| match event {
+                CrawlEvent::ConcurrencyChanged { concurrency } => Some(*concurrency),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(limits, [2, 1, 2, 3, 4]);
+        assert_eq!(result.pages_failed, 10);
+        assert_eq!(result.pages_crawled, 21);
+    }
+
+    #[test]
+    fn test_crawl_batched_export() {
+        let server = fan_out_server(24);
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
+
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            let config = test_config(&temp_dir)
+                .with_max_pages(100)
+                .with_concurrency(4)
+                .with_export_batch_size(10);
+            spider.crawl(config).await.unwrap()
+        });
+        assert_eq!(result.pages_crawled, 25);
+
+        // Two full batches plus the final partial one
+        let exported = std::fs::read_to_string(temp_dir.path().join("crawl.jsonl")).unwrap();
+        let urls: HashSet<String> = exported
+            .lines()
+            .map(|line| Document::from_json(line).unwrap().url().to_string())
+            .collect();
+        assert_eq!(exported.lines().count(), 25);
+        assert_eq!(urls.len(), 25);
+        assert!(urls.contains(&server.url("/page23")));
+    }
+
+    #[test]
+    fn test_crawl_respects_concurrency_cap() {
+        let server = fan_out_server(12);
+        server.set_latency(Duration::from_millis(50));
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
+
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            let config = test_config(&temp_dir).with_concurrency(4);
+            spider.crawl(config).await.unwrap()
+        });
+
+        assert_eq!(result.pages_crawled, 13);
+        assert!(
+            server.max_in_flight() <= 4,
+            "{} in flight",
+            server.max_in_flight()
+        );
+        assert!(server.max_in_flight() > 1, "fetches weren't parallel");
+    }
+
+    #[test]
+    fn test_crawl_is_sequential_by_default() {
+        let server = fan_out_server(4);
+        server.set_latency(Duration::from_millis(10));
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
+
+        async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            spider.crawl(test_config(&temp_dir)).await.unwrap()
+        });
+
+        assert_eq!(server.max_in_flight(), 1);
+    }
+
+    #[test]
+    fn test_with_concurrency_floors_at_one() {
+        assert_eq!(CrawlConfig::new().with_concurrency(0).concurrency, 1);
+    }
+
+    // ===== Text Density Tests =====
+
+    /// A page that is almost entirely inlined script
+    fn script_heavy_page() -> String {
+        format!(
+            "<html><body><script>{}</script><p>Hi</p></body></html>",
+            "var x = 1;".repeat(500)
+        )
+    }
+
+    /// A page that is almost entirely prose
+    fn prose_page() -> String {
+        format!(
+            "<html><body><p>{}</p></body></html>",
+            "The quick brown fox jumps over the lazy dog. ".repeat(50)
+        )
+    }
+
+    #[test]
+    fn test_text_density_script_heavy_vs_prose() {
+        let script = script_heavy_page();
+        let prose = prose_page();
+
+        let low = text_density(&parser(script.clone()), &script);
+        let high = text_density(&parser(prose.clone()), &prose);
+
+        assert!(low < 0.05, "script-heavy density {}", low);
+        assert!(high > 0.8, "prose density {}", high);
+        assert_eq!(text_density("", ""), 0.0);
+    }
+
+    #[test]
+    fn test_crawl_skips_low_density_pages() {
+        let server = TestServer::start(vec![]);
+        server.route(
+            "/",
+            html(&format!(
+                r#"<a href="/prose">Prose</a>{}"#,
+                script_heavy_page()
+            )),
+        );
+        server.route("/prose", html(&prose_page()));
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
+
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            let config = test_config(&temp_dir).with_min_text_density(0.1);
+            spider.crawl(config).await.unwrap()
+        });
+
+        // The script-heavy seed is crawled (its link followed) but not kept
+        assert_eq!(result.pages_crawled, 2);
+        assert_eq!(result.documents.len(), 1);
+        assert!(result.documents[0].url().ends_with("/prose"));
+        assert!(result.documents[0].text_density() > 0.8);
+    }
+
+    #[test]
+    fn test_crawl_records_favicon() {
+        let server = TestServer::start(vec![(
+            "/",
+            html(r#"<head><link rel="icon" href="/static/icon.svg"></head><p>Hi</p>"#),
+        )]);
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
+
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            spider.crawl(test_config(&temp_dir)).await.unwrap()
+        });
+
+        assert_eq!(
+            result.documents[0].favicon(),
+            Some(server.url("/static/icon.svg").as_str())
+        );
+    }
+
+    // ===== Sink Tests =====
+
+    /// A sink that writes slowly and records how far the crawl got ahead
+    struct SlowSink {
+        server: Arc<TestServer>,
+        written: Arc<Mutex<Vec<String>>>,
+        max_ahead: Arc<AtomicUsize>,
+    }
+
+    impl DocumentSink for SlowSink {
+        fn write_document(&mut self, document: &Document) -> std::io::Result<()> {
+            // Pages fetched but not yet written by the sink
+            let mut written = self.written.lock().unwrap();
+            let ahead = self.server.requests().len() - written.len();
+            self.max_ahead.fetch_max(ahead, Ordering::SeqCst);
+
+            thread::sleep(Duration::from_millis(20));
+            written.push(document.url().to_string());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_crawl_sink_applies_backpressure() {
+        let server = Arc::new(fan_out_server(15));
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
+        let written = Arc::new(Mutex::new(Vec::new()));
+        let max_ahead = Arc::new(AtomicUsize::new(0));
+
+        let sink = SlowSink {
+            server: Arc::clone(&server),
+            written: Arc::clone(&written),
+            max_ahead: Arc::clone(&max_ahead),
+        };
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            let config = test_config(&temp_dir)
+                .with_sink(sink)
+                .with_max_buffered_documents(2);
+            spider.crawl(config).await.unwrap()
+        });
+
+        // Every page reached the sink, none were kept in memory
+        assert_eq!(result.pages_crawled, 16);
+        assert_eq!(written.lock().unwrap().len(), 16);
+        assert!(result.documents.is_empty());
+        assert!(!temp_dir.path().join("crawl.jsonl").exists());
+
+        // At most: 2 queued, 1 being written, 1 blocked on a full queue and
+        // 1 being fetched
+        let ahead = max_ahead.load(Ordering::SeqCst);
+        assert!(ahead <= 5, "crawl ran {} pages ahead of the sink", ahead);
+    }
+
+    // ===== Conversion Size Tests =====
+
+    #[test]
+    fn test_crawl_truncates_oversized_html() {
+        let big = format!(
+            "<html><body><p>Intro</p>{}<p>The end</p></body></html>",
+            "<p>Filler paragraph</p>".repeat(200)
+        );
+        let server = TestServer::start(vec![("/", html(&big)), ("/small", html("<p>Small</p>"))]);
+        server.route(
+            "/",
+            html(&big.replace("<p>Intro</p>", r#"<a href="/small">Small</a><p>Intro</p>"#)),
+        );
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
+
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            let config = test_config(&temp_dir).with_max_html_bytes_for_conversion(1000);
+            spider.crawl(config).await.unwrap()
+        });
+
+        let big_doc = &result.documents[0];
+        assert!(big_doc.truncated());
+        assert!(big_doc.content().contains("Intro"));
+        assert!(!big_doc.content().contains("The end"));
+
+        let small_doc = &result.documents[1];
+        assert!(!small_doc.truncated());
+        assert!(small_doc.content().contains("Small"));
+    }
+
+    // ===== Asset Tests =====
+
+    #[test]
+    fn test_crawl_saves_non_html_assets() {
+        let pdf: &[u8] = b"%PDF-1.7\n\x00\xff\xfe binary \x80\x81";
+        let server = TestServer::start(vec![
+            ("/", html(r#"<a href="/report.pdf">Report</a>"#)),
+            (
+                "/report.pdf",
+                response_bytes(200, &[("Content-Type", "application/pdf")], pdf),
+            ),
+        ]);
+        let temp_dir = TempDir::new().unwrap();
+        let assets_dir = temp_dir.path().join("assets");
+        let seed = server.url("/");
+
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            let config = test_config(&temp_dir).with_save_assets_dir(assets_dir.to_str().unwrap());
+            spider.crawl(config).await.unwrap()
+        });
+
+        assert_eq!(result.pages_crawled, 2);
+        let asset = result
+            .documents
+            .iter()
+            .find(|doc| doc.url().ends_with("/report.pdf"))
+            .unwrap();
+        assert_eq!(asset.content(), "");
+
+        let path = asset.asset_path().unwrap();
+        assert!(path.starts_with(assets_dir.to_str().unwrap()));
+        assert_eq!(std::fs::read(path).unwrap(), pdf);
+
+        // The HTML page is converted as usual
+        let page = result
+            .documents
+            .iter()
+            .find(|doc| doc.asset_path().is_none());
+        assert!(page.is_some_and(|doc| !doc.content().is_empty()));
+    }
+
+    #[test]
+    fn test_crawl_without_assets_dir_treats_all_as_html() {
+        let server = TestServer::start(vec![(
+            "/",
+            response(200, &[("Content-Type", "application/pdf")], "%PDF"),
+        )]);
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
+
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            spider.crawl(test_config(&temp_dir)).await.unwrap()
+        });
+
+        assert_eq!(result.documents.len(), 1);
+        assert_eq!(result.documents[0].asset_path(), None);
+    }
+
+    // ===== Delay Tests =====
+
+    #[test]
+    fn test_delay_jitter_within_bounds() {
+        let base = Duration::from_millis(1000);
+        let mut jitter = DelayJitter::new(0.25, Some(42));
+
+        let delays: Vec<Duration> = (0..200).map(|_| jitter.apply(base)).collect();
+
+        for delay in &delays {
+            assert!(
+                *delay >= Duration::from_millis(750),
+                "{:?} too short",
+                delay
+            );
+            assert!(
+                *delay <= Duration::from_millis(1250),
+                "{:?} too long",
+                delay
+            );
+        }
+
+        // Not a constant delay
+        assert!(delays.iter().any(|d| *d < base));
+        assert!(delays.iter().any(|d| *d > base));
+    }
+
+    #[test]
+    fn test_delay_jitter_reproducible_with_seed() {
+        let base = Duration::from_millis(500);
+        let mut first = DelayJitter::new(0.5, Some(7));
+        let mut second = DelayJitter::new(0.5, Some(7));
+        let mut other = DelayJitter::new(0.5, Some(8));
+
+        let a: Vec<Duration> = (0..10).map(|_| first.apply(base)).collect();
+        let b: Vec<Duration> = (0..10).map(|_| second.apply(base)).collect();
+        let c: Vec<Duration> = (0..10).map(|_| other.apply(base)).collect();
+
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn test_delay_without_jitter_is_exact() {
+        let base = Duration::from_millis(300);
+        let mut jitter = DelayJitter::new(0.0, Some(1));
+        assert_eq!(jitter.apply(base), base);
+    }
+
+    #[test]
+    fn test_crawl_config_delay_jitter_clamped() {
+        assert_eq!(CrawlConfig::new().with_delay_jitter(1.5).delay_jitter, 1.0);
+        assert_eq!(CrawlConfig::new().with_delay_jitter(-0.5).delay_jitter, 0.0);
+        assert_eq!(
+            CrawlConfig::new().with_delay_jitter(f64::NAN).delay_jitter,
+            0.0
+        );
+        assert_eq!(
+            CrawlConfig::new()
+                .with_delay_jitter(f64::INFINITY)
+                .delay_jitter,
+            0.0
+        );
+    }
+
+    #[test]
+    fn test_delay_jitter_nan_is_exact() {
+        // Set through the public field, bypassing the builder
+        let base = Duration::from_millis(300);
+        let mut jitter = DelayJitter::new(f64::NAN, Some(1));
+        assert_eq!(jitter.apply(base), base);
+    }
+
+    #[test]
+    fn test_crawl_waits_between_requests() {
+        let server = linked_server();
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
+
+        let started = std::time::Instant::now();
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            let config = test_config(&temp_dir)
+                .with_delay(Duration::from_millis(40))
+                .with_delay_jitter(0.5)
+                .with_jitter_seed(3);
+            spider.crawl(config).await.unwrap()
+        });
+
+        // Three delays of at least 20ms each (no delay before the seed)
+        assert_eq!(result.pages_crawled, 4);
+        assert!(started.elapsed() >= Duration::from_millis(60));
+    }
+
+    // ===== Content Length Tests =====
+
+    #[test]
+    fn test_crawl_skips_thin_pages() {
+        let long_text = "This page has plenty of real content. ".repeat(5);
+        let server = TestServer::start(vec![
+            ("/", html(r#"<a href="/article">Read</a>"#)),
+            ("/article", html(&format!("<p>{}</p>", long_text))),
+        ]);
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
+
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            let config = test_config(&temp_dir).with_min_content_length(100);
+            spider.crawl(config).await.unwrap()
+        });
+
+        // The link-only seed was used for discovery but not kept
+        assert_eq!(result.pages_crawled, 2);
+        assert_eq!(result.documents.len(), 1);
+        assert_eq!(result.documents[0].url(), server.url("/article"));
+
+        let exported = std::fs::read_to_string(temp_dir.path().join("crawl.jsonl")).unwrap();
+        assert_eq!(exported.lines().count(), 1);
+        assert!(exported.contains("/article"));
+    }
+
+    // ===== Robots Directive Tests =====
+
+    #[test]
+    fn test_crawl_x_robots_tag_noindex() {
+        let server = TestServer::start(vec![
+            (
+                "/",
+                response(
+                    200,
+                    &[("Content-Type", "text/html"), ("X-Robots-Tag", "noindex")],
+                    r#"<a href="/child">Child</a>"#,
+                ),
+            ),
+            ("/child", html("<p>Child</p>")),
+        ]);
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
+
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            spider.crawl(test_config(&temp_dir)).await.unwrap()
+        });
+
+        // Crawled for links, but not kept
+        assert_eq!(result.pages_crawled, 2);
+        assert_eq!(result.documents.len(), 1);
+        assert_eq!(result.documents[0].url(), server.url("/child"));
+
+        let exported = std::fs::read_to_string(temp_dir.path().join("crawl.jsonl")).unwrap();
+        assert_eq!(exported.lines().count(), 1);
+    }
+
+    #[test]
+    fn test_crawl_x_robots_tag_nofollow() {
+        let server = TestServer::start(vec![
+            (
+                "/",
+                response(
+                    200,
+                    &[("Content-Type", "text/html"), ("X-Robots-Tag", "nofollow")],
+                    r#"<a href="/child">Child</a>"#,
+                ),
+            ),
+            ("/child", html("<p>Child</p>")),
+        ]);
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
+
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            spider.crawl(test_config(&temp_dir)).await.unwrap()
+        });
+
+        assert_eq!(result.pages_crawled, 1);
+        assert_eq!(result.documents.len(), 1);
+        assert!(server.request_for("/child").is_none());
+    }
+
+    #[test]
+    fn test_crawl_no_discover_patterns() {
+        let server = TestServer::start(vec![
+            (
+                "/",
+                html(r#"<a href="/archive/2020">Archive</a><a href="/about">About</a>"#),
+            ),
+            (
+                "/archive/2020",
+                html(r#"<p>Old posts</p><a href="/archive/post">Post</a>"#),
+            ),
+            ("/about", html(r#"<p>About</p><a href="/team">Team</a>"#)),
+            ("/archive/post", html("<p>Post</p>")),
+            ("/team", html("<p>Team</p>")),
+        ]);
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
+
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            let config = test_config(&temp_dir)
+                .with_no_discover_patterns(vec![Regex::new(r"/archive/").unwrap()]);
+            spider.crawl(config).await.unwrap()
+        });
+
+        // The archive page is exported with its links, which aren't followed
+        let archive = result
+            .documents
+            .iter()
+            .find(|doc| doc.url().ends_with("/archive/2020"))
+            .expect("archive page exported");
+        assert!(archive.content().contains("Old posts"));
+        assert_eq!(archive.links(), [server.url("/archive/post")]);
+        assert!(server.request_for("/archive/post").is_none());
+
+        // Other pages still discover links
+        assert!(server.request_for("/team").is_some());
+        assert_eq!(result.pages_crawled, 4);
+    }
+
+    #[test]
+    fn test_crawl_flags_gated_pages() {
+        let server = TestServer::start(vec![
+            (
+                "/",
+                html(r#"<a href="/premium">Premium</a><a href="/free">Free</a>"#),
+            ),
+            (
+                "/premium",
+                html(
+                    r#"<h1>Members only</h1><p>Sign in
+                    to <b>continue</b></p><a href="/login">Log in</a>"#,
+                ),
+            ),
+            (
+                "/free",
+                html(r#"<p>Free article</p><a href="/more">More</a>"#),
+            ),
+            ("/login", html("<p>Login</p>")),
+            ("/more", html("<p>More</p>")),
+        ]);
+        let seed = server.url("/");
+        let signals = vec!["sign in to continue".to_string()];
+
+        for skip_links in [false, true] {
+            let temp_dir = TempDir::new().unwrap();
+            let result = async_std::task::block_on(async {
+                let mut spider = Spiderman::new(&seed);
+                let config = test_config(&temp_dir)
+                    .with_paywall_signals(signals.clone())
+                    .with_skip_gated_links(skip_links);
+                spider.crawl(config).await.unwrap()
+            });
+
+            let gated: Vec<&str> = result
+                .documents
+                .iter()
+                .filter(|doc| doc.is_gated())
+                .map(|doc| doc.url())
+                .collect();
+            assert_eq!(gated, [server.url("/premium")]);
+
+            let crawled_login = result
+                .documents
+                .iter()
+                .any(|doc| doc.url().contains("/login"));
+            assert_eq!(crawled_login, !skip_links);
+            assert!(result
+                .documents
+                .iter()
+                .any(|doc| doc.url().ends_with("/more")));
+        }
+    }
+
+    #[test]
+    fn test_crawl_noarchive_drops_raw_html() {
+        let server = TestServer::start(vec![
+            (
+                "/",
+                response(
+                    200,
+                    &[("Content-Type", "text/html"), ("X-Robots-Tag", "noarchive")],
+                    r#"<p>Seed</p><a href="/child">Child</a>"#,
+                ),
+            ),
+            (
+                "/child",
+                html(r#"<head><meta name="robots" content="noarchive"></head><p>Child</p>"#),
+            ),
+        ]);
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
+
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            let config = test_config(&temp_dir).with_raw_html(true);
+            spider.crawl(config).await.unwrap()
+        });
+
+        // Still exported, just without the HTML
+        assert_eq!(result.documents.len(), 2);
+        for doc in &result.documents {
+            assert_eq!(doc.raw_html(), None, "{}", doc.url());
         }
+    }
 
-        // Initialize Exporter
-        let exporter = Exporter::new(&config.output_dir);
+    #[test]
+    fn test_crawl_nosnippet_drops_description() {
+        let server = TestServer::start(vec![(
+            "/",
+            html(
+                r#"<head>
+                    <meta name="robots" content="nosnippet">
+                    <meta name="description" content="A summary of the page">
+                    <title>Page</title>
+                </head><p>Body</p>"#,
+            ),
+        )]);
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
 
-        // Statistics
-        let mut pages_crawled = 0;
-        let mut pages_failed = 0;
-        let mut documents = Vec::new();
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            spider.crawl(test_config(&temp_dir)).await.unwrap()
+        });
 
-        // Main crawl loop
-        while let Some(current_url) = manager.get_next() {
-            if config.verbose {
-                let (total, queued, processed) = manager.stats();
-                println!("[{}/{}] Crawling: {}", processed + 1, total, current_url);
-            }
+        let doc = &result.documents[0];
+        assert_eq!(doc.description(), None);
+        assert_eq!(doc.title(), "Page");
+    }
 
-            // Fetch HTML
-            match self.fetch_url(&current_url).await {
-                Ok(html) => {
-                    // Extract links and add to queue
-                    let links = extract_links(&html, &current_url);
-                    let mut added = 0;
-                    for link in &links {
-                        if manager.add_url(link) {
-                            added += 1;
-                        }
-                    }
+    #[test]
+    fn test_crawl_meta_robots_nofollow() {
+        let server = TestServer::start(vec![
+            (
+                "/",
+                html(
+                    r#"<head><meta name="robots" content="nofollow"></head><a href="/child">Child</a>"#,
+                ),
+            ),
+            ("/child", html("<p>Child</p>")),
+        ]);
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
 
-                    if config.verbose && added > 0 {
-                        println!("  ├─ Found {} links ({} new)", links.len(), added);
-                    }
+        async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            spider.crawl(test_config(&temp_dir)).await.unwrap();
+        });
 
-                    // Convert HTML to Markdown
-                    let markdown = parser(html.clone());
-
-                    // Extract metadata
-                    let metadata = extract_metadata(&html);
-                    let title = metadata.title.unwrap_or_else(|| {
-                        // Fallback: extract from URL
-                        current_url
-                            .split('/')
-                            .last()
-                            .unwrap_or("Untitled")
-                            .to_string()
-                    });
-
-                    // Create document
-                    let mut doc = Document::new(&current_url, markdown, links)
-                        .with_title(title)
-                        .with_description(metadata.description);
-
-                    // Add metadata
-                    if let Some(keywords) = metadata.keywords {
-                        doc = doc.with_metadata("keywords", &keywords);
-                    }
-                    if let Some(author) = metadata.author {
-                        doc = doc.with_metadata("author", &author);
-                    }
+        assert!(server.request_for("/child").is_none());
+    }
 
-                    // Store raw HTML if configured
-                    if config.store_raw_html {
-                        doc = doc.with_raw_html(html);
-                    }
+    // ===== Raw HTML Tests =====
 
-                    // Export document
-                    if let Err(e) = exporter.export_document(&doc, &config.output_file) {
-                        eprintln!("  ├─ ⚠️  Export error: {}", e);
-                    } else if config.verbose {
-                        println!(
-                            "  └─ ✓ Exported to {}/{}",
-                            config.output_dir, config.output_file
-                        );
-                    }
+    const ACTIVE_PAGE: &str = r#"<p onclick="track()">Hello</p><script>steal()</script>"#;
 
-                    documents.push(doc);
-                    pages_crawled += 1;
-                }
-                Err(e) => {
-                    if config.verbose {
-                        eprintln!("  └─ ✗ Error: {}", e);
-                    }
-                    pages_failed += 1;
+    fn crawl_raw_html(sanitize: bool) -> String {
+        let server = TestServer::start(vec![("/", html(ACTIVE_PAGE))]);
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
+
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            let config = test_config(&temp_dir)
+                .with_raw_html(true)
+                .with_sanitize_raw_html(sanitize);
+            spider.crawl(config).await.unwrap()
+        });
+
+        result.documents[0].raw_html().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_crawl_sanitizes_raw_html() {
+        let raw_html = crawl_raw_html(true);
+
+        assert_eq!(raw_html, "<p>Hello</p>");
+    }
+
+    #[test]
+    fn test_crawl_keeps_raw_html_unsanitized_by_default() {
+        assert_eq!(crawl_raw_html(false), ACTIVE_PAGE);
+    }
+
+    // ===== Extraction Tests =====
+
+    #[test]
+    fn test_crawl_records_stripped_boilerplate() {
+        let server = TestServer::start(vec![(
+            "/",
+            html("<nav>Menu</nav><main><p>Article body</p></main><footer>Legal</footer>"),
+        )]);
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
+
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            let config = test_config(&temp_dir).with_strip_boilerplate(true);
+            spider.crawl(config).await.unwrap()
+        });
+
+        let doc = &result.documents[0];
+        assert!(doc.extraction().stripped_boilerplate);
+        assert_eq!(doc.extraction().content_format, "markdown");
+        assert!(doc.content().contains("Article body"));
+        assert!(!doc.content().contains("Menu"));
+        assert!(!doc.content().contains("Legal"));
+    }
+
+    #[test]
+    fn test_crawl_include_noscript() {
+        let server = TestServer::start(vec![(
+            "/",
+            html("<p>Please enable JavaScript.</p><noscript><h1>Article</h1><p>Main text</p></noscript>"),
+        )]);
+        let seed = server.url("/");
+
+        let crawl = |include: Option<bool>| {
+            let temp_dir = TempDir::new().unwrap();
+            async_std::task::block_on(async {
+                let mut spider = Spiderman::new(&seed);
+                let mut config = test_config(&temp_dir);
+                if let Some(include) = include {
+                    config = config.with_include_noscript(include);
                 }
-            }
+                spider.crawl(config).await.unwrap()
+            })
+        };
 
-            if config.verbose {
-                println!();
-            }
+        // By default the content is kept, as the converter renders it
+        let default = crawl(None);
+        assert!(default.documents[0].content().contains("Main text"));
+
+        let included = crawl(Some(true));
+        let content = included.documents[0].content();
+        assert!(content.contains("# Article"));
+        assert!(content.contains("Main text"));
+        assert!(!content.contains("<noscript>"));
+
+        let excluded = crawl(Some(false));
+        let content = excluded.documents[0].content();
+        assert!(content.contains("Please enable JavaScript."));
+        assert!(!content.contains("Main text"));
+        assert!(!content.contains("<h1>"));
+    }
+
+    #[test]
+    fn test_crawl_readability_converts_main_article() {
+        let page = r#"<nav><a href="/">Home</a> | <a href="/archive">Archive</a></nav>
+            <div class="sidebar">
+              <p><a href="/popular">Popular: ten reasons to crawl, responsibly</a></p>
+              <p>Subscribe to our newsletter for weekly updates</p>
+            </div>
+            <article>
+              <h1>Field notes</h1>
+              <p>The main article text, long enough to score, with a comma or two.</p>
+              <p>A second paragraph, so the article clearly wins, with more commas.</p>
+            </article>"#;
+        let server = TestServer::start(vec![
+            ("/", html(page)),
+            ("/popular", html("<p>Popular</p>")),
+        ]);
+        let seed = server.url("/");
+
+        let crawl = |readability: bool| {
+            let temp_dir = TempDir::new().unwrap();
+            async_std::task::block_on(async {
+                let mut spider = Spiderman::new(&seed);
+                let config = test_config(&temp_dir)
+                    .with_readability(readability)
+                    .with_max_pages(1);
+                spider.crawl(config).await.unwrap()
+            })
+        };
+
+        let result = crawl(true);
+        let doc = &result.documents[0];
+        assert!(doc.extraction().main_content);
+        assert!(doc.content().contains("Field notes"));
+        assert!(doc.content().contains("The main article text"));
+        assert!(!doc.content().contains("newsletter"));
+        assert!(!doc.content().contains("Archive"));
+        // Links are still taken from the whole page
+        assert!(doc.links().iter().any(|link| link.ends_with("/popular")));
+
+        let result = crawl(false);
+        let doc = &result.documents[0];
+        assert!(!doc.extraction().main_content);
+        assert!(doc.content().contains("newsletter"));
+    }
+
+    #[test]
+    fn test_crawl_records_viewport() {
+        let server = TestServer::start(vec![
+            (
+                "/",
+                html(
+                    r#"<head><meta name="viewport" content="width=device-width, initial-scale=1"></head>
+                    <a href="/desktop">Desktop</a>"#,
+                ),
+            ),
+            ("/desktop", html("<p>Fixed layout</p>")),
+        ]);
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
+
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            spider.crawl(test_config(&temp_dir)).await.unwrap()
+        });
+
+        let responsive = &result.documents[0];
+        assert_eq!(
+            responsive.get_metadata("viewport"),
+            Some("width=device-width, initial-scale=1")
+        );
+        assert!(responsive.is_mobile_friendly());
+
+        let desktop = &result.documents[1];
+        assert_eq!(desktop.get_metadata("viewport"), None);
+        assert!(!desktop.is_mobile_friendly());
+    }
+
+    #[test]
+    fn test_crawl_records_default_extraction() {
+        let server = TestServer::start(vec![("/", html("<nav>Menu</nav><p>Body</p>"))]);
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
+
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            spider.crawl(test_config(&temp_dir)).await.unwrap()
+        });
+
+        let doc = &result.documents[0];
+        assert!(!doc.extraction().stripped_boilerplate);
+        assert!(doc.content().contains("Menu"));
+    }
+
+    #[test]
+    fn test_crawl_decodes_windows_1252_title() {
+        let server = TestServer::start(vec![(
+            "/",
+            response_bytes(
+                200,
+                &[("Content-Type", "text/html; charset=windows-1252")],
+                b"<html><head><title>Caf\xe9 Menu</title></head><body>Cr\xe8me</body></html>",
+            ),
+        )]);
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
+
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            spider.crawl(test_config(&temp_dir)).await.unwrap()
+        });
+
+        let doc = &result.documents[0];
+        assert_eq!(doc.title(), "Café Menu");
+        assert!(doc.content().contains("Crème"));
+    }
+
+    #[test]
+    fn test_crawl_records_canonical_url() {
+        let server = TestServer::start(vec![
+            (
+                "/",
+                html(r#"<a href="/post">Post</a><a href="/plain">Plain</a>"#),
+            ),
+            (
+                "/post",
+                html(r#"<head><link rel="canonical" href="/articles/post"></head><p>Post</p>"#),
+            ),
+            ("/plain", html("<p>Plain</p>")),
+        ]);
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
+
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            spider.crawl(test_config(&temp_dir)).await.unwrap()
+        });
+
+        // The canonical is recorded, but the document keeps the fetched URL
+        let post = result
+            .documents
+            .iter()
+            .find(|d| d.url().ends_with("/post"))
+            .unwrap();
+        assert_eq!(post.url(), server.url("/post"));
+        let canonical = server.url("/articles/post");
+        assert_eq!(post.canonical_url(), Some(canonical.as_str()));
+        assert!(server.request_for("/articles/post").is_none());
+
+        let plain = result
+            .documents
+            .iter()
+            .find(|d| d.url().ends_with("/plain"))
+            .unwrap();
+        assert_eq!(plain.canonical_url(), None);
+    }
+
+    #[test]
+    fn test_resolve_canonical() {
+        let graph: HashMap<String, String> = [("a", "b"), ("b", "c"), ("x", "y"), ("y", "x")]
+            .into_iter()
+            .map(|(from, to)| (from.to_string(), to.to_string()))
+            .collect();
+
+        assert_eq!(resolve_canonical(&graph, "a").as_deref(), Some("c"));
+        assert_eq!(resolve_canonical(&graph, "c").as_deref(), Some("c"));
+        assert_eq!(resolve_canonical(&graph, "x"), None);
+        assert_eq!(resolve_canonical(&graph, "y"), None);
+    }
+
+    #[test]
+    fn test_crawl_canonical_dedup_skips_duplicates() {
+        let temp_dir = TempDir::new().unwrap();
+        let server = TestServer::start(vec![
+            (
+                "/",
+                html(r#"<a href="/article">Article</a><a href="/article-print">Print</a>"#),
+            ),
+            ("/article", html("<p>Article</p>")),
+            (
+                "/article-print",
+                html(r#"<head><link rel="canonical" href="/article"></head><p>Article</p>"#),
+            ),
+        ]);
+        let seed = server.url("/");
+
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            let config = test_config(&temp_dir).with_canonical_dedup(true);
+            spider.crawl(config).await.unwrap()
+        });
+
+        assert_eq!(result.pages_crawled, 3);
+        assert!(result
+            .documents
+            .iter()
+            .any(|d| d.url().ends_with("/article")));
+        assert!(!result
+            .documents
+            .iter()
+            .any(|d| d.url().ends_with("/article-print")));
+    }
+
+    #[test]
+    fn test_crawl_canonical_cycle_keeps_both_pages() {
+        let temp_dir = TempDir::new().unwrap();
+        let server = TestServer::start(vec![
+            ("/", html(r#"<a href="/a">A</a><a href="/b">B</a>"#)),
+            (
+                "/a",
+                html(r#"<head><link rel="canonical" href="/b"></head><p>A</p>"#),
+            ),
+            (
+                "/b",
+                html(r#"<head><link rel="canonical" href="/a"></head><p>B</p>"#),
+            ),
+        ]);
+        let seed = server.url("/");
+
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            let config = test_config(&temp_dir).with_canonical_dedup(true);
+            spider.crawl(config).await.unwrap()
+        });
+
+        // Neither page collapses onto the other
+        for path in ["/a", "/b"] {
+            let url = server.url(path);
+            assert!(
+                result.documents.iter().any(|d| d.url() == url),
+                "{} was not stored",
+                url
+            );
+        }
+    }
+
+    #[test]
+    fn test_crawl_allowed_content_types_wildcards() {
+        let server = TestServer::start(vec![
+            (
+                "/",
+                html(r#"<a href="/notes.txt">Notes</a><a href="/report.pdf">Report</a>"#),
+            ),
+            (
+                "/notes.txt",
+                response(200, &[("Content-Type", "text/plain")], "Notes"),
+            ),
+            (
+                "/report.pdf",
+                response(200, &[("Content-Type", "application/pdf")], "%PDF"),
+            ),
+        ]);
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
+
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            let config =
+                test_config(&temp_dir).with_allowed_content_types(vec!["text/*".to_string()]);
+            spider.crawl(config).await.unwrap()
+        });
+
+        // The PDF is fetched but neither stored nor counted as a failure
+        assert!(server.request_for("/report.pdf").is_some());
+        assert_eq!(result.pages_failed, 0);
+        assert_eq!(result.documents.len(), 2);
+        assert!(result.documents.iter().all(|d| !d.url().ends_with(".pdf")));
+    }
+
+    #[test]
+    fn test_crawl_records_discovery_anchor() {
+        let server = TestServer::start(vec![
+            (
+                "/",
+                html(
+                    r#"<a href="/story">Read <b>more</b></a><a href="/about"><img src="/a.png"></a>"#,
+                ),
+            ),
+            ("/story", html(r#"<p>Story</p><a href="/">Home page</a>"#)),
+            ("/about", html("<p>About</p>")),
+        ]);
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
+
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            spider.crawl(test_config(&temp_dir)).await.unwrap()
+        });
+
+        let anchor_of = |path: &str| {
+            result
+                .documents
+                .iter()
+                .find(|d| d.url() == server.url(path))
+                .unwrap()
+                .discovery_anchor()
+                .map(str::to_string)
+        };
+        assert_eq!(anchor_of("/story"), Some("Read more".to_string()));
+        // An image-only link has no text; the seed wasn't discovered by a link
+        assert_eq!(anchor_of("/about"), None);
+        assert_eq!(anchor_of(""), None);
+    }
+
+    #[test]
+    fn test_crawl_writes_http_log() {
+        let server = fan_out_server(6);
+        server.route("/page0", response(301, &[("Location", "/page1")], ""));
+        let temp_dir = TempDir::new().unwrap();
+        let log_path = temp_dir.path().join("http.log");
+        let seed = server.url("/");
+
+        async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            let config = test_config(&temp_dir)
+                .with_concurrency(4)
+                .with_http_log(log_path.to_str().unwrap());
+            spider.crawl(config).await.unwrap()
+        });
+
+        let log = std::fs::read_to_string(&log_path).unwrap();
+        assert!(log.contains("> GET / HTTP/1.1\n"));
+        assert!(log.contains("> Connection: close\n"));
+        assert!(log.contains("< HTTP/1.1 301 Moved Permanently\n< Location: /page1\n"));
+        assert!(log.contains("< Content-Type: text/html\n"));
+        assert!(!log.contains("<a href"));
+
+        // One intact entry per request, even with concurrent fetches
+        let entries: Vec<&str> = log.trim_end().split("\n\n").collect();
+        assert_eq!(entries.len(), server.requests().len());
+        for entry in entries {
+            assert!(entry.starts_with("> GET "));
+            assert!(entry.lines().last().unwrap().starts_with("< "));
+            assert_eq!(entry.matches("> GET ").count(), 1);
         }
+    }
 
-        // Final statistics
-        let (total_urls, _, _) = manager.stats();
+    #[test]
+    fn test_crawl_empty_titles_fall_back_to_url() {
+        let server = TestServer::start(vec![
+            (
+                "/",
+                html(r#"<a href="/blank">Blank</a><a href="/comment">Comment</a>"#),
+            ),
+            ("/blank", html("<title>   </title><p>Blank</p>")),
+            (
+                "/comment",
+                html("<title><!-- todo --></title><p>Comment</p>"),
+            ),
+        ]);
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
 
-        if config.verbose {
-            println!("✅ Crawl Complete!");
-            println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-            println!("📈 Statistics:");
-            println!("   • Pages crawled: {}", pages_crawled);
-            println!("   • Pages failed: {}", pages_failed);
-            println!("   • URLs discovered: {}", total_urls);
-            println!("   • Output: {}/{}", config.output_dir, config.output_file);
-            println!();
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            spider.crawl(test_config(&temp_dir)).await.unwrap()
+        });
+
+        for name in ["blank", "comment"] {
+            let doc = result
+                .documents
+                .iter()
+                .find(|d| d.url().ends_with(name))
+                .unwrap();
+            assert_eq!(doc.title(), name);
         }
+    }
 
-        Ok(CrawlResult {
-            pages_crawled,
-            pages_failed,
-            urls_discovered: total_urls,
-            documents,
-        })
+    // ===== Redirect Tests =====
+
+    #[test]
+    fn test_crawl_follows_seed_redirect_to_new_host() {
+        // `localhost` plays the bare domain, `127.0.0.1` the `www` host
+        let server = TestServer::start(vec![]);
+        server.route(
+            "/",
+            response(301, &[("Location", &server.url("/home"))], ""),
+        );
+        server.route(
+            "/home",
+            html(&format!(r#"<a href="{}">About</a>"#, server.url("/about"))),
+        );
+        server.route("/about", html("<p>About</p>"));
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/").replace("127.0.0.1", "localhost");
+
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            let config = test_config(&temp_dir).with_allowed_domains(vec!["localhost".to_string()]);
+            spider.crawl(config).await.unwrap()
+        });
+
+        assert_eq!(result.pages_crawled, 2);
+        assert!(server.request_for("/about").is_some());
     }
 
-    /// Fetches HTML from a URL
-    ///
-    /// Internal helper method that directly fetches HTML without modifying self.url.
-    async fn fetch_url(&mut self, url: &str) -> Result<String, Box<dyn std::error::Error>> {
-        use async_std::io::{BufReader, ReadExt, WriteExt};
-        use async_std::net::TcpStream;
+    #[test]
+    fn test_crawl_redirects_to_same_target_produce_one_document() {
+        let server = TestServer::start(vec![(
+            "/",
+            html(r#"<a href="/r1">One</a><a href="/r2">Two</a><a href="/target">Direct</a>"#),
+        )]);
+        server.route("/r1", response(301, &[("Location", "/target")], ""));
+        server.route("/r2", response(302, &[("Location", "/target")], ""));
+        server.route("/target", html("<p>Target</p>"));
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
+
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            spider.crawl(test_config(&temp_dir)).await.unwrap()
+        });
+
+        let urls: Vec<&str> = result.documents.iter().map(|d| d.url()).collect();
+        assert_eq!(urls, vec![server.url(""), server.url("/target")]);
+        assert_eq!(result.pages_crawled, 2);
+
+        // The target is only reached through the two redirects, never
+        // fetched again from its own queue entry
+        let target_fetches = server
+            .requests()
+            .iter()
+            .filter(|r| r.starts_with("GET /target "))
+            .count();
+        assert_eq!(target_fetches, 2);
+        assert!(server.request_for("/r1").is_some());
+        assert!(server.request_for("/r2").is_some());
+    }
 
-        // Parse URL
-        let (host, path) = parse_url(url)?;
+    #[test]
+    fn test_crawl_marks_redirect_hops_visited() {
+        let server = TestServer::start(vec![(
+            "/",
+            html(r#"<a href="/r1">One</a><a href="/r2">Two</a>"#),
+        )]);
+        server.route("/r1", response(301, &[("Location", "/hop")], ""));
+        server.route(
+            "/hop",
+            html(r#"<a href="/r1">Back</a><a href="/r2">Again</a>"#),
+        );
+        server.route("/r2", response(302, &[("Location", "/hop")], ""));
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
 
-        // Connect to host
-        let address = format!("{}:80", host);
-        let mut stream = TcpStream::connect(&address).await?;
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            spider.crawl(test_config(&temp_dir)).await.unwrap()
+        });
 
-        // Build HTTP request
-        let request = format!(
-            "GET {} HTTP/1.1\r\n\
-             Host: {}\r\n\
-             User-Agent: Spiderman/0.1.0 (Rust Web Crawler)\r\n\
-             Accept: text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8\r\n\
-             Connection: close\r\n\
-             \r\n",
-            path, host
+        assert_eq!(result.documents.len(), 2);
+        assert_eq!(
+            server
+                .requests()
+                .iter()
+                .filter(|r| r.starts_with("GET /r1 "))
+                .count(),
+            1
         );
+        assert_eq!(
+            server
+                .requests()
+                .iter()
+                .filter(|r| r.starts_with("GET /r2 "))
+                .count(),
+            1
+        );
+    }
 
-        // Send request
-        stream.write_all(request.as_bytes()).await?;
-        stream.flush().await?;
+    #[test]
+    fn test_crawl_resolves_links_against_redirect_target() {
+        let server = TestServer::start(vec![
+            ("/", html(r#"<a href="/old">Old</a>"#)),
+            ("/old", response(301, &[("Location", "/docs/intro")], "")),
+            ("/docs/intro", html(r#"<a href="next">Next</a>"#)),
+            ("/docs/next", html("<p>Next</p>")),
+        ]);
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
+
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            spider.crawl(test_config(&temp_dir)).await.unwrap()
+        });
 
-        // Read response
-        let mut reader = BufReader::new(stream);
-        let mut response = String::new();
-        reader.read_to_string(&mut response).await?;
+        let intro = result
+            .documents
+            .iter()
+            .find(|doc| doc.url().ends_with("/docs/intro"))
+            .unwrap();
+        assert_eq!(intro.links(), &[server.url("/docs/next")][..]);
 
-        // Extract body
-        extract_body(&response)
+        // Fetched relative to the final URL, never relative to `/old`
+        assert!(server.request_for("/docs/next").is_some());
+        assert!(server.request_for("/next").is_none());
     }
-}
 
-/// Parses URL to extract host and path
-fn parse_url(url: &str) -> Result<(String, String), Box<dyn std::error::Error>> {
-    let url = url
-        .trim_start_matches("http://")
-        .trim_start_matches("https://");
+    #[test]
+    fn test_crawl_too_many_redirects_fails_page() {
+        let server = TestServer::start(vec![("/", html(r#"<a href="/loop">Loop</a>"#))]);
+        server.route("/loop", response(302, &[("Location", "/loop2")], ""));
+        server.route("/loop2", response(302, &[("Location", "/loop")], ""));
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
 
-    let parts: Vec<&str> = url.splitn(2, '/').collect();
-    let host = parts[0].to_string();
-    let path = if parts.len() > 1 {
-        format!("/{}", parts[1])
-    } else {
-        "/".to_string()
-    };
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            spider
+                .crawl(test_config(&temp_dir).with_max_redirects(2))
+                .await
+                .unwrap()
+        });
 
-    if host.is_empty() {
-        return Err("Invalid URL: empty host".into());
+        assert_eq!(result.pages_failed, 1);
+        assert_eq!(result.pages_crawled, 1);
     }
 
-    Ok((host, path))
-}
+    #[test]
+    fn test_crawl_redirect_after_seed_does_not_widen_scope() {
+        let server = TestServer::start(vec![("/", html(r#"<a href="/jump">Jump</a>"#))]);
+        server.route(
+            "/jump",
+            response(302, &[("Location", &server.url("/landing"))], ""),
+        );
+        server.route(
+            "/landing",
+            html(&format!(r#"<a href="{}">Next</a>"#, server.url("/next"))),
+        );
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/").replace("127.0.0.1", "localhost");
 
-/// Extracts body from HTTP response
-fn extract_body(response: &str) -> Result<String, Box<dyn std::error::Error>> {
-    if let Some(pos) = response.find("\r\n\r\n") {
-        Ok(response[pos + 4..].to_string())
-    } else if let Some(pos) = response.find("\n\n") {
-        Ok(response[pos + 2..].to_string())
-    } else {
-        Err("Invalid HTTP response: no body separator found".into())
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            let config = test_config(&temp_dir).with_allowed_domains(vec!["localhost".to_string()]);
+            spider.crawl(config).await.unwrap()
+        });
+
+        assert_eq!(result.pages_crawled, 2);
+        assert!(server.request_for("/next").is_none());
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn test_truncate_at_word() {
+        assert_eq!(truncate_at_word("The quick brown fox", 12), "The quick…");
+        assert_eq!(
+            truncate_at_word("The quick brown fox", 19),
+            "The quick brown fox"
+        );
+        assert_eq!(truncate_at_word("Supercalifragilistic", 6), "Super…");
+        assert_eq!(truncate_at_word("anything", 0), "");
+    }
 
     #[test]
-    fn test_crawl_config_default() {
-        let config = CrawlConfig::default();
-        assert_eq!(config.max_pages, Some(50));
-        assert_eq!(config.output_dir, "output");
-        assert_eq!(config.output_file, "crawl.jsonl");
-        assert_eq!(config.verbose, true);
+    fn test_truncate_long_title_at_word_boundary() {
+        let title = "word ".repeat(100);
+        let title = title.trim_end();
+        assert_eq!(title.len(), 499);
+
+        let truncated = truncate_at_word(title, 80);
+
+        assert!(truncated.chars().count() <= 80);
+        assert!(truncated.ends_with("word…"));
+        assert!(title.starts_with(truncated.trim_end_matches('…')));
     }
 
     #[test]
-    fn test_crawl_config_builder() {
-        let config = CrawlConfig::new()
-            .with_max_pages(100)
-            .with_output_dir("custom_output")
-            .with_output_file("results.jsonl")
-            .with_verbose(false);
+    fn test_crawl_truncates_title_and_description() {
+        let long_title = "Breaking ".repeat(55);
+        let server = TestServer::start(vec![
+            (
+                "/",
+                html(&format!(
+                    r#"<head><title>{}</title>
+                    <meta name="description" content="A fairly long description of the page">
+                    </head><a href="/short">Short</a>"#,
+                    long_title
+                )),
+            ),
+            ("/short", html("<head><title>Short title</title></head>")),
+        ]);
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
 
-        assert_eq!(config.max_pages, Some(100));
-        assert_eq!(config.output_dir, "custom_output");
-        assert_eq!(config.output_file, "results.jsonl");
-        assert_eq!(config.verbose, false);
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            let config = test_config(&temp_dir)
+                .with_max_title_len(40)
+                .with_max_description_len(20);
+            spider.crawl(config).await.unwrap()
+        });
+
+        let seed_doc = &result.documents[0];
+        assert_eq!(seed_doc.title(), "Breaking Breaking Breaking Breaking…");
+        assert_eq!(seed_doc.description(), Some("A fairly long…"));
+
+        let short_doc = &result.documents[1];
+        assert_eq!(short_doc.title(), "Short title");
+    }
+
+    /// Builds a crawl result from `(url, links)` pairs
+    fn result_from_graph(graph: &[(&str, &[&str])]) -> CrawlResult {
+        let documents: Vec<Document> = graph
+            .iter()
+            .map(|(url, links)| {
+                let links = links.iter().map(|l| l.to_string()).collect();
+                Document::new(url, String::new(), links)
+            })
+            .collect();
+
+        CrawlResult {
+            pages_crawled: documents.len(),
+            pages_failed: 0,
+            urls_discovered: documents.len(),
+            documents,
+            changes: HashMap::new(),
+            fetch_durations: Vec::new(),
+        }
     }
 
     #[test]
-    fn test_crawl_config_with_domains() {
-        let domains = vec!["example.com".to_string(), "test.com".to_string()];
-        let config = CrawlConfig::new().with_allowed_domains(domains.clone());
+    fn test_top_pages_by_inlinks_hub_ranks_first() {
+        let result = result_from_graph(&[
+            (
+                "http://example.com/a",
+                &["http://example.com/hub", "http://example.com/b"],
+            ),
+            ("http://example.com/b", &["http://example.com/hub/"]),
+            (
+                "http://example.com/c",
+                &["http://example.com/hub", "http://example.com/b"],
+            ),
+            (
+                "http://example.com/hub",
+                &["http://example.com/hub", "http://example.com/a"],
+            ),
+        ]);
 
-        assert_eq!(config.allowed_domains, Some(domains));
+        let top = result.top_pages_by_inlinks(10);
+
+        assert_eq!(top[0], ("http://example.com/hub".to_string(), 3));
+        assert_eq!(top[1], ("http://example.com/b".to_string(), 2));
+        assert_eq!(top[2], ("http://example.com/a".to_string(), 1));
+        assert_eq!(top.len(), 3);
     }
 
     #[test]
-    fn test_crawl_config_raw_html() {
-        let config = CrawlConfig::new().with_raw_html(true);
-        assert_eq!(config.store_raw_html, true);
+    fn test_top_pages_by_inlinks_limits_results() {
+        let result = result_from_graph(&[
+            (
+                "http://example.com/a",
+                &["http://example.com/x", "http://example.com/y"],
+            ),
+            ("http://example.com/b", &["http://example.com/x"]),
+        ]);
+
+        assert_eq!(
+            result.top_pages_by_inlinks(1),
+            vec![("http://example.com/x".to_string(), 2)]
+        );
+        assert!(result.top_pages_by_inlinks(0).is_empty());
+    }
+
+    #[test]
+    fn test_merge_results() {
+        let mut first = result_from_graph(&[
+            ("http://example.com/a", &[]),
+            ("http://example.com/shared", &[]),
+        ]);
+        first.pages_failed = 1;
+        first.urls_discovered = 5;
+        first.fetch_durations = vec![Duration::from_millis(10); 3];
+
+        let mut second = result_from_graph(&[
+            ("http://example.com/shared/", &[]),
+            ("http://example.com/b", &[]),
+            ("http://example.com/c", &[]),
+        ]);
+        second.urls_discovered = 4;
+        second.fetch_durations = vec![Duration::from_millis(20); 3];
+
+        let mut merged = first.merge(second);
+
+        assert_eq!(merged.pages_crawled, 5);
+        assert_eq!(merged.pages_failed, 1);
+        // The shared page was discovered by both shards
+        assert_eq!(merged.urls_discovered, 8);
+        assert_eq!(merged.fetch_durations.len(), 6);
+        assert_eq!(merged.documents.len(), 5);
+
+        assert_eq!(merged.dedup_documents(), 1);
+        let urls: Vec<&str> = merged.documents.iter().map(|doc| doc.url()).collect();
+        assert_eq!(
+            urls,
+            [
+                "http://example.com/a",
+                "http://example.com/shared",
+                "http://example.com/b",
+                "http://example.com/c",
+            ]
+        );
+        assert_eq!(merged.dedup_documents(), 0);
+    }
+
+    #[test]
+    fn test_documents_by_domain() {
+        let result = result_from_graph(&[
+            ("http://example.com/a", &[]),
+            ("http://blog.example.com/post", &[]),
+            ("http://example.com/b", &[]),
+            ("https://other.org:8443/", &[]),
+            ("http://example.com/c", &[]),
+        ]);
+
+        let grouped = result.documents_by_domain();
+
+        assert_eq!(grouped.len(), 3);
+        let urls: Vec<&str> = grouped["example.com"].iter().map(|d| d.url()).collect();
+        assert_eq!(
+            urls,
+            vec![
+                "http://example.com/a",
+                "http://example.com/b",
+                "http://example.com/c"
+            ]
+        );
+        assert_eq!(grouped["blog.example.com"].len(), 1);
+        assert_eq!(grouped["other.org"].len(), 1);
+    }
+
+    #[test]
+    fn test_latency_percentiles() {
+        let mut result = result_from_graph(&[]);
+        assert_eq!(result.latency_percentiles(), Default::default());
+
+        // 1..=100 ms, shuffled
+        result.fetch_durations = (1..=100)
+            .map(|i| Duration::from_millis((i * 37) % 100 + 1))
+            .collect();
+        assert_eq!(
+            result.latency_percentiles(),
+            (
+                Duration::from_millis(50),
+                Duration::from_millis(90),
+                Duration::from_millis(99)
+            )
+        );
+
+        result.fetch_durations = vec![Duration::from_millis(7)];
+        let single = Duration::from_millis(7);
+        assert_eq!(result.latency_percentiles(), (single, single, single));
+    }
+
+    #[test]
+    fn test_crawl_records_fetch_durations() {
+        let server = fan_out_server(9);
+        server.set_path_latency("/page0", Duration::from_millis(300));
+        let temp_dir = TempDir::new().unwrap();
+        let seed = server.url("/");
+
+        let result = async_std::task::block_on(async {
+            let mut spider = Spiderman::new(&seed);
+            spider.crawl(test_config(&temp_dir)).await.unwrap()
+        });
+
+        // One slow fetch out of ten only shows up in the tail
+        assert_eq!(result.fetch_durations.len(), 10);
+        let (p50, p90, p99) = result.latency_percentiles();
+        assert!(p50 < Duration::from_millis(300));
+        assert!(p90 < Duration::from_millis(300));
+        assert!(p99 >= Duration::from_millis(300));
     }
 }
@@ -26,6 +26,17 @@
 //! ├── raw_html: Option         (Original HTML, optional)
 //! ├── links: Vec<String>       (Outbound links found)
 //! ├── crawled_at: DateTime     (When it was crawled)
+//! ├── status: Option           (HTTP status code of the response)
+//! ├── extraction: ExtractionInfo (How the content was produced)
+//! ├── asset_path: Option       (Local copy of a non-HTML resource)
+//! ├── text_density: f32        (Markdown size relative to the HTML)
+//! ├── published_at: Option     (Publication date, if the page states one)
+//! ├── truncated: bool          (HTML was cut before conversion)
+//! ├── favicon: Option          (Absolute URL of the site icon)
+//! ├── canonical_url: Option    (Absolute canonical URL the page declares)
+//! ├── discovery_anchor: Option (Text of the link the page was found by)
+//! ├── is_gated: bool           (Page looks like a login wall or paywall)
+//! ├── assets: Option           (Scripts, stylesheets and images referenced)
 //! └── metadata: HashMap        (Additional metadata)
 //! ```
 //!
@@ -74,6 +85,12 @@
 //! println!("{}", json);
 //! ```
 
+use super::feed::FEED_TYPES;
+use super::html_to_md::CONVERTER;
+use super::link_extractor::{is_valid_url, normalize_url, parse_base_url, AssetSet};
+use super::url_manager::{
+    extract_domain, normalize_url_for_storage, normalize_url_with_options, NormalizeOptions,
+};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -92,6 +109,20 @@ use std::collections::HashMap;
 /// * `raw_html` - Optional original HTML (for storage/debugging)
 /// * `links` - List of outbound links found on the page
 /// * `crawled_at` - UTC timestamp of when the page was crawled
+/// * `status` - Optional HTTP status code the page was served with
+/// * `extraction` - How the content was extracted (converter, options)
+/// * `asset_path` - Optional local path of a saved non-HTML resource
+/// * `text_density` - Ratio of Markdown bytes to HTML bytes (0.0 if unknown)
+/// * `published_at` - Optional publication date found in the page
+/// * `truncated` - Whether the HTML was cut short before conversion
+/// * `favicon` - Optional absolute URL of the page's icon
+/// * `canonical_url` - Optional absolute URL from `<link rel="canonical">`
+/// * `discovery_anchor` - Optional anchor text of the link that led to the
+///   page
+/// * `is_gated` - Whether the page matched a login/paywall signal
+/// * `assets` - Optional inventory of the page's subresources
+/// * `etag` - Optional `ETag` header the page was served with
+/// * `content_hash` - Optional hash of the response body, for change detection
 /// * `metadata` - Additional key-value metadata
 ///
 /// # Examples
@@ -133,6 +164,62 @@ pub struct Document {
     /// When the page was crawled (UTC)
     crawled_at: DateTime<Utc>,
 
+    /// HTTP status code of the response (optional)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    status: Option<u16>,
+
+    /// How the content was produced
+    #[serde(default)]
+    extraction: ExtractionInfo,
+
+    /// Local path of the saved raw resource (non-HTML assets only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    asset_path: Option<String>,
+
+    /// Markdown size relative to the HTML it came from (low values mean
+    /// mostly markup, e.g. inlined scripts)
+    #[serde(default)]
+    text_density: f32,
+
+    /// When the page says it was published
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    published_at: Option<DateTime<Utc>>,
+
+    /// Whether the HTML was truncated before conversion (see
+    /// `CrawlConfig::max_html_bytes_for_conversion`)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    truncated: bool,
+
+    /// Absolute URL of the page's icon
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    favicon: Option<String>,
+
+    /// Canonical URL declared by the page (informational; `url` is always
+    /// the fetched URL)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    canonical_url: Option<String>,
+
+    /// Anchor text of the link the page was discovered through
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    discovery_anchor: Option<String>,
+
+    /// Whether the page looks like a login wall or paywall (see
+    /// `CrawlConfig::paywall_signals`)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    is_gated: bool,
+
+    /// Subresources the page references (see `CrawlConfig::inventory_assets`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    assets: Option<AssetSet>,
+
+    /// `ETag` response header, for conditional re-crawls
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    etag: Option<String>,
+
+    /// Hash of the response body (see `hash_content`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    content_hash: Option<String>,
+
     /// Additional metadata (keywords, author, etc.)
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     metadata: HashMap<String, String>,
@@ -171,6 +258,19 @@ impl Document {
             raw_html: None,
             links,
             crawled_at: Utc::now(),
+            status: None,
+            extraction: ExtractionInfo::default(),
+            asset_path: None,
+            text_density: 0.0,
+            published_at: None,
+            truncated: false,
+            favicon: None,
+            canonical_url: None,
+            discovery_anchor: None,
+            is_gated: false,
+            assets: None,
+            etag: None,
+            content_hash: None,
             metadata: HashMap::new(),
         }
     }
@@ -220,6 +320,32 @@ impl Document {
         self
     }
 
+    /// Replaces the links and returns self (builder pattern)
+    ///
+    /// Entries that can't be links are dropped: empty strings, relative
+    /// URLs and non-crawlable ones such as `mailto:`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spiderman::core::document::Document;
+    ///
+    /// let doc = Document::new("http://example.com", "content".to_string(), vec![])
+    ///     .with_links(vec![
+    ///         "http://example.com/about".to_string(),
+    ///         "".to_string(),
+    ///         "/relative".to_string(),
+    ///     ]);
+    /// assert_eq!(doc.links(), ["http://example.com/about"]);
+    /// ```
+    pub fn with_links(mut self, links: Vec<String>) -> Self {
+        self.links = links
+            .into_iter()
+            .filter(|link| is_absolute_link(link))
+            .collect();
+        self
+    }
+
     /// Adds a metadata key-value pair and returns self (builder pattern)
     ///
     /// # Examples
@@ -236,6 +362,30 @@ impl Document {
         self
     }
 
+    /// Merges a map of metadata and returns self (builder pattern)
+    ///
+    /// Keys already present are overwritten.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spiderman::core::document::Document;
+    /// use std::collections::HashMap;
+    ///
+    /// let extra = HashMap::from([
+    ///     ("author".to_string(), "John Doe".to_string()),
+    ///     ("lang".to_string(), "en".to_string()),
+    /// ]);
+    ///
+    /// let doc = Document::new("http://example.com", "content".to_string(), vec![])
+    ///     .with_metadata_map(extra);
+    /// assert_eq!(doc.get_metadata("lang"), Some("en"));
+    /// ```
+    pub fn with_metadata_map(mut self, map: HashMap<String, String>) -> Self {
+        self.metadata.extend(map);
+        self
+    }
+
     /// Sets the crawled_at timestamp and returns self (builder pattern)
     ///
     /// Useful for testing or when restoring from storage
@@ -255,8 +405,271 @@ impl Document {
         self
     }
 
+    /// Sets the HTTP status code and returns self (builder pattern)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spiderman::core::document::Document;
+    ///
+    /// let doc = Document::new("http://example.com/missing", "content".to_string(), vec![])
+    ///     .with_status(404);
+    /// ```
+    pub fn with_status(mut self, status: u16) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    /// Sets how the content was extracted and returns self (builder pattern)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spiderman::core::document::{Document, ExtractionInfo};
+    ///
+    /// let info = ExtractionInfo {
+    ///     stripped_boilerplate: true,
+    ///     ..ExtractionInfo::default()
+    /// };
+    /// let doc = Document::new("http://example.com", "content".to_string(), vec![])
+    ///     .with_extraction(info);
+    /// ```
+    pub fn with_extraction(mut self, extraction: ExtractionInfo) -> Self {
+        self.extraction = extraction;
+        self
+    }
+
+    /// Sets the local path of a saved non-HTML resource (builder pattern)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spiderman::core::document::Document;
+    ///
+    /// let doc = Document::new("http://example.com/doc.pdf", String::new(), vec![])
+    ///     .with_asset_path("assets/example.com-doc.pdf".to_string());
+    /// ```
+    pub fn with_asset_path(mut self, path: String) -> Self {
+        self.asset_path = Some(path);
+        self
+    }
+
+    /// Sets the text density and returns self (builder pattern)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spiderman::core::document::Document;
+    ///
+    /// let doc = Document::new("http://example.com", "# Hi".to_string(), vec![])
+    ///     .with_text_density(0.4);
+    /// assert_eq!(doc.text_density(), 0.4);
+    /// ```
+    pub fn with_text_density(mut self, density: f32) -> Self {
+        self.text_density = density;
+        self
+    }
+
+    /// Sets the publication date and returns self (builder pattern)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spiderman::core::document::Document;
+    /// use chrono::{TimeZone, Utc};
+    ///
+    /// let date = Utc.with_ymd_and_hms(2024, 3, 1, 9, 30, 0).unwrap();
+    /// let doc = Document::new("http://example.com/news", "Story".to_string(), vec![])
+    ///     .with_published_at(Some(date));
+    /// assert_eq!(doc.published_at(), Some(date));
+    /// ```
+    pub fn with_published_at(mut self, published_at: Option<DateTime<Utc>>) -> Self {
+        self.published_at = published_at;
+        self
+    }
+
+    /// Marks the content as converted from truncated HTML (builder pattern)
+    pub fn with_truncated(mut self, truncated: bool) -> Self {
+        self.truncated = truncated;
+        self
+    }
+
+    /// Marks the page as a login wall or paywall (builder pattern)
+    pub fn with_gated(mut self, gated: bool) -> Self {
+        self.is_gated = gated;
+        self
+    }
+
+    /// Sets the favicon URL and returns self (builder pattern)
+    pub fn with_favicon(mut self, favicon: Option<String>) -> Self {
+        self.favicon = favicon;
+        self
+    }
+
+    /// Sets the declared canonical URL and returns self (builder pattern)
+    pub fn with_canonical_url(mut self, canonical_url: Option<String>) -> Self {
+        self.canonical_url = canonical_url;
+        self
+    }
+
+    /// Sets the page's asset inventory and returns self (builder pattern)
+    pub fn with_assets(mut self, assets: AssetSet) -> Self {
+        self.assets = Some(assets);
+        self
+    }
+
+    /// Sets the discovery anchor text and returns self (builder pattern)
+    pub fn with_discovery_anchor(mut self, anchor: Option<String>) -> Self {
+        self.discovery_anchor = anchor;
+        self
+    }
+
+    /// Sets the `ETag` the page was served with and returns self (builder
+    /// pattern)
+    pub fn with_etag(mut self, etag: Option<String>) -> Self {
+        self.etag = etag;
+        self
+    }
+
+    /// Sets the response body hash and returns self (builder pattern)
+    pub fn with_content_hash(mut self, hash: String) -> Self {
+        self.content_hash = Some(hash);
+        self
+    }
+
     // Getters
 
+    /// Returns how the content was extracted
+    pub fn extraction(&self) -> &ExtractionInfo {
+        &self.extraction
+    }
+
+    /// Returns the ratio of Markdown bytes to HTML bytes
+    pub fn text_density(&self) -> f32 {
+        self.text_density
+    }
+
+    /// Returns the absolute URL of the page's icon, if known
+    pub fn favicon(&self) -> Option<&str> {
+        self.favicon.as_deref()
+    }
+
+    /// Returns the canonical URL the page declares, if any
+    pub fn canonical_url(&self) -> Option<&str> {
+        self.canonical_url.as_deref()
+    }
+
+    /// Returns true if the page looks built for mobile screens
+    ///
+    /// A heuristic: the page's viewport meta tag (stored as the `viewport`
+    /// metadata) sets `width=device-width`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spiderman::core::document::Document;
+    ///
+    /// let doc = Document::new("http://example.com", String::new(), vec![])
+    ///     .with_metadata("viewport", "width=device-width, initial-scale=1");
+    /// assert!(doc.is_mobile_friendly());
+    /// ```
+    pub fn is_mobile_friendly(&self) -> bool {
+        self.get_metadata("viewport").is_some_and(|viewport| {
+            viewport.split([',', ';']).any(|setting| {
+                setting.split_once('=').is_some_and(|(name, value)| {
+                    name.trim().eq_ignore_ascii_case("width")
+                        && value.trim().eq_ignore_ascii_case("device-width")
+                })
+            })
+        })
+    }
+
+    /// Returns true if the page declares a canonical URL other than its own
+    ///
+    /// Both URLs are compared in normalized form, so case, default ports
+    /// and trailing slashes don't count as a mismatch. Pages without a
+    /// canonical URL never mismatch.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spiderman::core::document::Document;
+    ///
+    /// let doc = Document::new("http://example.com/a?ref=nav", String::new(), vec![])
+    ///     .with_canonical_url(Some("http://example.com/a".to_string()));
+    /// assert!(doc.canonical_mismatch());
+    /// ```
+    pub fn canonical_mismatch(&self) -> bool {
+        self.canonical_url
+            .as_deref()
+            .is_some_and(|canonical| normalize_url_for_storage(canonical) != self.normalized_url())
+    }
+
+    /// Returns the page URL in the normalized form the crawler stores URLs in
+    ///
+    /// Equivalent spellings of a URL (host case, default port, trailing
+    /// slash, fragment) give the same result, so documents can be joined
+    /// with indices keyed by normalized URL.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spiderman::core::document::Document;
+    ///
+    /// let doc = Document::new("http://Example.com:80/a/#top", String::new(), vec![]);
+    /// assert_eq!(doc.normalized_url(), "http://example.com/a");
+    /// ```
+    pub fn normalized_url(&self) -> String {
+        normalize_url_for_storage(&self.url)
+    }
+
+    /// Returns the normalized page URL, with the query parameter handling of
+    /// a crawl that used `options` (see `CrawlConfig::normalize_options`)
+    pub fn normalized_url_with_options(&self, options: &NormalizeOptions) -> String {
+        normalize_url_with_options(&self.url, options)
+    }
+
+    /// Returns the scripts, stylesheets and images the page references, if
+    /// they were inventoried
+    pub fn assets(&self) -> Option<&AssetSet> {
+        self.assets.as_ref()
+    }
+
+    /// Returns the anchor text of the link the page was discovered through
+    pub fn discovery_anchor(&self) -> Option<&str> {
+        self.discovery_anchor.as_deref()
+    }
+
+    /// Returns the `ETag` the page was served with, if any
+    pub fn etag(&self) -> Option<&str> {
+        self.etag.as_deref()
+    }
+
+    /// Returns the hash of the response body, if recorded
+    pub fn content_hash(&self) -> Option<&str> {
+        self.content_hash.as_deref()
+    }
+
+    /// Returns true if the content was converted from truncated HTML
+    pub fn truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Returns true if the page looks like a login wall or paywall
+    pub fn is_gated(&self) -> bool {
+        self.is_gated
+    }
+
+    /// Returns the publication date, if the page stated one
+    pub fn published_at(&self) -> Option<DateTime<Utc>> {
+        self.published_at
+    }
+
+    /// Returns the local path of the saved resource, if any
+    pub fn asset_path(&self) -> Option<&str> {
+        self.asset_path.as_deref()
+    }
+
     /// Returns the URL of the document
     pub fn url(&self) -> &str {
         &self.url
@@ -292,6 +705,11 @@ impl Document {
         self.crawled_at
     }
 
+    /// Returns the HTTP status code if recorded
+    pub fn status(&self) -> Option<u16> {
+        self.status
+    }
+
     /// Returns the metadata map
     pub fn metadata(&self) -> &HashMap<String, String> {
         &self.metadata
@@ -302,6 +720,140 @@ impl Document {
         self.metadata.get(key).map(|s| s.as_str())
     }
 
+    /// Sets a metadata value, overwriting any previous value for the key
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spiderman::core::document::Document;
+    ///
+    /// let mut doc = Document::new("http://example.com", "content".to_string(), vec![])
+    ///     .with_metadata("author", "Jane");
+    /// doc.set_metadata("author", "John");
+    /// assert_eq!(doc.get_metadata("author"), Some("John"));
+    /// ```
+    pub fn set_metadata(&mut self, key: &str, value: &str) {
+        self.metadata.insert(key.to_string(), value.to_string());
+    }
+
+    /// Returns links pointing to a different domain than the document
+    ///
+    /// Domains are compared case-insensitively, ignoring ports. Links whose
+    /// domain can't be determined are treated as internal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spiderman::core::document::Document;
+    ///
+    /// let doc = Document::new(
+    ///     "http://example.com",
+    ///     "content".to_string(),
+    ///     vec![
+    ///         "http://example.com/about".to_string(),
+    ///         "https://other.org/".to_string(),
+    ///     ],
+    /// );
+    /// assert_eq!(doc.outbound_external_links(), vec!["https://other.org/"]);
+    /// ```
+    pub fn outbound_external_links(&self) -> Vec<&str> {
+        self.links
+            .iter()
+            .filter(|link| self.is_external(link))
+            .map(|link| link.as_str())
+            .collect()
+    }
+
+    /// Returns links pointing to the document's own domain
+    ///
+    /// The complement of `outbound_external_links()`.
+    pub fn internal_links(&self) -> Vec<&str> {
+        self.links
+            .iter()
+            .filter(|link| !self.is_external(link))
+            .map(|link| link.as_str())
+            .collect()
+    }
+
+    /// Returns true if a link's domain differs from the document's
+    fn is_external(&self, link: &str) -> bool {
+        match (extract_domain(&self.url), extract_domain(link)) {
+            (Some(own), Some(other)) => !own.eq_ignore_ascii_case(&other),
+            _ => false,
+        }
+    }
+
+    /// Adds a link unless the document already has it
+    ///
+    /// Links are compared in normalized form, so `http://example.com/a/`
+    /// and `http://EXAMPLE.com/a` count as the same link.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The absolute URL of the link
+    ///
+    /// # Returns
+    ///
+    /// * `true` if the link was added
+    /// * `false` if it was already present
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spiderman::core::document::Document;
+    ///
+    /// let mut doc = Document::new("http://example.com", "content".to_string(), vec![]);
+    /// assert!(doc.add_link("http://example.com/about".to_string()));
+    /// assert!(!doc.add_link("http://example.com/about/".to_string()));
+    /// assert_eq!(doc.link_count(), 1);
+    /// ```
+    pub fn add_link(&mut self, url: String) -> bool {
+        let key = normalize_url_for_storage(&url);
+        if self
+            .links
+            .iter()
+            .any(|link| normalize_url_for_storage(link) == key)
+        {
+            return false;
+        }
+
+        self.links.push(url);
+        true
+    }
+
+    /// Removes a link (compared in normalized form)
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL of the link to remove
+    ///
+    /// # Returns
+    ///
+    /// * `true` if a link was removed
+    /// * `false` if the document had no such link
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spiderman::core::document::Document;
+    ///
+    /// let mut doc = Document::new(
+    ///     "http://example.com",
+    ///     "content".to_string(),
+    ///     vec!["http://example.com/old".to_string()],
+    /// );
+    /// assert!(doc.remove_link("http://example.com/old"));
+    /// assert!(!doc.remove_link("http://example.com/old"));
+    /// ```
+    pub fn remove_link(&mut self, url: &str) -> bool {
+        let key = normalize_url_for_storage(url);
+        let before = self.links.len();
+        self.links
+            .retain(|link| normalize_url_for_storage(link) != key);
+
+        self.links.len() < before
+    }
+
     /// Returns the number of links in the document
     pub fn link_count(&self) -> usize {
         self.links.len()
@@ -354,26 +906,209 @@ impl Document {
     pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
         serde_json::from_str(json)
     }
-}
-
-/// Metadata extracted from HTML
-///
-/// This struct holds metadata extracted from HTML `<head>` tags.
-///
-/// # Fields
-///
-/// * `title` - Page title from `<title>` tag
-/// * `description` - Meta description
-/// * `keywords` - Meta keywords
-/// * `author` - Meta author
-/// * `other` - Other meta tags as key-value pairs
-#[derive(Debug, Clone, Default)]
-pub struct Metadata {
-    /// Page title from <title> tag
-    pub title: Option<String>,
-
-    /// Meta description
-    pub description: Option<String>,
+
+    /// Compares this document's content with another crawl of the page
+    ///
+    /// Lines are matched with a longest-common-subsequence diff, so moved
+    /// or edited lines count as one removal plus one addition.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The newer version of the document
+    ///
+    /// # Returns
+    ///
+    /// A `ContentDiff` with the added/removed line counts and a unified
+    /// diff from `self` to `other`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spiderman::core::document::Document;
+    ///
+    /// let old = Document::new("http://example.com", "a\nb".to_string(), vec![]);
+    /// let new = Document::new("http://example.com", "a\nc\nd".to_string(), vec![]);
+    ///
+    /// let diff = old.content_diff(&new);
+    /// assert_eq!((diff.added, diff.removed), (2, 1));
+    /// ```
+    pub fn content_diff(&self, other: &Document) -> ContentDiff {
+        let old: Vec<&str> = self.content.lines().collect();
+        let new: Vec<&str> = other.content.lines().collect();
+
+        // Unchanged head and tail lines don't need the quadratic table
+        let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+        let suffix = old[prefix..]
+            .iter()
+            .rev()
+            .zip(new[prefix..].iter().rev())
+            .take_while(|(a, b)| a == b)
+            .count();
+        let old_mid = &old[prefix..old.len() - suffix];
+        let new_mid = &new[prefix..new.len() - suffix];
+
+        // lcs[i][j] = length of the LCS of old_mid[i..] and new_mid[j..]
+        let mut lcs = vec![vec![0usize; new_mid.len() + 1]; old_mid.len() + 1];
+        for i in (0..old_mid.len()).rev() {
+            for j in (0..new_mid.len()).rev() {
+                lcs[i][j] = if old_mid[i] == new_mid[j] {
+                    lcs[i + 1][j + 1] + 1
+                } else {
+                    lcs[i + 1][j].max(lcs[i][j + 1])
+                };
+            }
+        }
+
+        let mut diff = ContentDiff {
+            unified: format!("--- {}\n+++ {}\n", self.url, other.url),
+            ..ContentDiff::default()
+        };
+        for line in &old[..prefix] {
+            diff.push_line(' ', line);
+        }
+
+        let (mut i, mut j) = (0, 0);
+        while i < old_mid.len() || j < new_mid.len() {
+            if i < old_mid.len() && j < new_mid.len() && old_mid[i] == new_mid[j] {
+                diff.push_line(' ', old_mid[i]);
+                i += 1;
+                j += 1;
+            } else if i < old_mid.len() && (j == new_mid.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+                diff.push_line('-', old_mid[i]);
+                diff.removed += 1;
+                i += 1;
+            } else {
+                diff.push_line('+', new_mid[j]);
+                diff.added += 1;
+                j += 1;
+            }
+        }
+
+        for line in &old[old.len() - suffix..] {
+            diff.push_line(' ', line);
+        }
+        diff
+    }
+}
+
+/// Line-level difference between two versions of a document's content
+///
+/// Produced by `Document::content_diff`.
+///
+/// # Fields
+///
+/// * `added` - Lines present only in the newer document
+/// * `removed` - Lines present only in the older document
+/// * `unified` - Full-context unified diff (`---`/`+++` header, then every
+///   line prefixed with `' '`, `'-'` or `'+'`)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ContentDiff {
+    /// Number of added lines
+    pub added: usize,
+
+    /// Number of removed lines
+    pub removed: usize,
+
+    /// Unified diff text
+    pub unified: String,
+}
+
+impl ContentDiff {
+    /// Returns true when the two contents are line-for-line identical
+    pub fn is_empty(&self) -> bool {
+        self.added == 0 && self.removed == 0
+    }
+
+    fn push_line(&mut self, marker: char, line: &str) {
+        self.unified.push(marker);
+        self.unified.push_str(line);
+        self.unified.push('\n');
+    }
+}
+
+/// Describes how a document's content was produced
+///
+/// Recorded on every `Document` so exported content can be reproduced.
+///
+/// # Fields
+///
+/// * `converter` - Name and version of the HTML converter (empty if the
+///   body was stored verbatim)
+/// * `stripped_boilerplate` - Whether navigation, headers, footers and
+///   asides were removed before conversion
+/// * `main_content` - Whether only the main article (found by
+///   `readability::extract_main`) was converted
+/// * `content_format` - Format of the `content` field (e.g. `"markdown"`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExtractionInfo {
+    /// Converter name and version (e.g. `"html2text/0.12"`)
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub converter: String,
+
+    /// Whether boilerplate was stripped before conversion
+    pub stripped_boilerplate: bool,
+
+    /// Whether only the main article was converted
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub main_content: bool,
+
+    /// Format of the content
+    pub content_format: String,
+}
+
+impl Default for ExtractionInfo {
+    fn default() -> Self {
+        Self {
+            converter: CONVERTER.to_string(),
+            stripped_boilerplate: false,
+            main_content: false,
+            content_format: "markdown".to_string(),
+        }
+    }
+}
+
+impl ExtractionInfo {
+    /// Describes a body stored as fetched, without any conversion
+    ///
+    /// # Arguments
+    ///
+    /// * `content_format` - Format of the body (e.g. `"json"`)
+    pub fn verbatim(content_format: &str) -> Self {
+        Self {
+            converter: String::new(),
+            stripped_boilerplate: false,
+            main_content: false,
+            content_format: content_format.to_string(),
+        }
+    }
+}
+
+/// Metadata extracted from HTML
+///
+/// This struct holds metadata extracted from HTML `<head>` tags.
+///
+/// # Fields
+///
+/// * `title` - Page title from `<title>` tag
+/// * `description` - Meta description
+/// * `keywords` - Meta keywords
+/// * `author` - Meta author
+/// * `next` - Pagination `rel="next"` link (as written in the page)
+/// * `prev` - Pagination `rel="prev"` link (as written in the page)
+/// * `published_at` - Publication date (see `extract_published_at`)
+/// * `favicon` - Icon `<link>` href (as written in the page)
+/// * `canonical` - `<link rel="canonical">` href (as written in the page)
+/// * `viewport` - `<meta name="viewport">` content
+/// * `feeds` - RSS/Atom feed `<link rel="alternate">` hrefs (as written in
+///   the page)
+/// * `other` - Other meta tags as key-value pairs
+#[derive(Debug, Clone, Default)]
+pub struct Metadata {
+    /// Page title from <title> tag
+    pub title: Option<String>,
+
+    /// Meta description
+    pub description: Option<String>,
 
     /// Meta keywords
     pub keywords: Option<String>,
@@ -381,10 +1116,212 @@ pub struct Metadata {
     /// Meta author
     pub author: Option<String>,
 
+    /// Next page in a paginated series (`rel="next"` href)
+    pub next: Option<String>,
+
+    /// Previous page in a paginated series (`rel="prev"` href)
+    pub prev: Option<String>,
+
+    /// Publication date
+    pub published_at: Option<DateTime<Utc>>,
+
+    /// Icon link (`rel="icon"`, `"shortcut icon"` or `"apple-touch-icon"`)
+    pub favicon: Option<String>,
+
+    /// Canonical link (`rel="canonical"` href)
+    pub canonical: Option<String>,
+
+    /// Viewport settings (e.g. `width=device-width, initial-scale=1`)
+    pub viewport: Option<String>,
+
+    /// Declared RSS/Atom feeds (`rel="alternate"` links with a feed type)
+    pub feeds: Vec<String>,
+
     /// Other meta tags
     pub other: HashMap<String, String>,
 }
 
+/// Page-level robots directives
+///
+/// Parsed from `<meta name="robots">` tags and `X-Robots-Tag` headers.
+///
+/// # Fields
+///
+/// * `noindex` - The page must not be stored/exported
+/// * `nofollow` - The page's links must not be followed
+/// * `noarchive` - The page's raw HTML must not be stored
+/// * `nosnippet` - The page's description must not be stored
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RobotsDirectives {
+    /// Do not store or export the page
+    pub noindex: bool,
+
+    /// Do not follow the page's links
+    pub nofollow: bool,
+
+    /// Do not keep a copy of the raw HTML
+    pub noarchive: bool,
+
+    /// Do not keep a description or snippet of the page
+    pub nosnippet: bool,
+}
+
+impl RobotsDirectives {
+    /// Parses a comma-separated directive list (e.g. `"noindex, nofollow"`)
+    ///
+    /// Directive names are case-insensitive; `none` means both `noindex`
+    /// and `nofollow`. Unknown directives are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spiderman::core::document::RobotsDirectives;
+    ///
+    /// let directives = RobotsDirectives::parse("NOINDEX, follow");
+    /// assert!(directives.noindex);
+    /// assert!(!directives.nofollow);
+    /// ```
+    pub fn parse(value: &str) -> Self {
+        let mut directives = Self::default();
+
+        for directive in value.split(',').map(|d| d.trim().to_lowercase()) {
+            match directive.as_str() {
+                "noindex" => directives.noindex = true,
+                "nofollow" => directives.nofollow = true,
+                "noarchive" => directives.noarchive = true,
+                "nosnippet" => directives.nosnippet = true,
+                "none" => {
+                    directives.noindex = true;
+                    directives.nofollow = true;
+                }
+                _ => {}
+            }
+        }
+
+        directives
+    }
+
+    /// Combines two sets of directives; a directive set in either applies
+    pub fn merge(self, other: Self) -> Self {
+        Self {
+            noindex: self.noindex || other.noindex,
+            nofollow: self.nofollow || other.nofollow,
+            noarchive: self.noarchive || other.noarchive,
+            nosnippet: self.nosnippet || other.nosnippet,
+        }
+    }
+}
+
+impl Metadata {
+    /// Returns the absolute favicon URL for a page at `base_url`
+    ///
+    /// Resolves the declared icon link against the page URL, falling back
+    /// to `/favicon.ico` when the page declares none.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spiderman::core::document::extract_metadata;
+    ///
+    /// let metadata = extract_metadata(r#"<link rel="icon" href="/img/icon.png">"#);
+    /// assert_eq!(
+    ///     metadata.favicon_url("http://example.com/blog/post"),
+    ///     Some("http://example.com/img/icon.png".to_string())
+    /// );
+    ///
+    /// let metadata = extract_metadata("<title>No icon</title>");
+    /// assert_eq!(
+    ///     metadata.favicon_url("http://example.com/blog/post"),
+    ///     Some("http://example.com/favicon.ico".to_string())
+    /// );
+    /// ```
+    pub fn favicon_url(&self, base_url: &str) -> Option<String> {
+        normalize_url(self.favicon.as_deref().unwrap_or("/favicon.ico"), base_url)
+    }
+
+    /// Returns the directives of the page's `<meta name="robots">` tag
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spiderman::core::document::extract_metadata;
+    ///
+    /// let metadata = extract_metadata(r#"<meta name="robots" content="nofollow">"#);
+    /// assert!(metadata.robots().nofollow);
+    /// ```
+    pub fn robots(&self) -> RobotsDirectives {
+        self.other
+            .iter()
+            .filter(|(name, _)| name.eq_ignore_ascii_case("robots"))
+            .fold(RobotsDirectives::default(), |directives, (_, content)| {
+                directives.merge(RobotsDirectives::parse(content))
+            })
+    }
+
+    /// Extracts metadata from the `<head>` section of an HTML document
+    ///
+    /// Only the `<head>...</head>` region is scanned, so `<meta>` or `<title>`
+    /// tags appearing in body content (e.g. inside a code example) are not
+    /// mistaken for page metadata. Documents without a `<head>` section are
+    /// scanned in full.
+    ///
+    /// Pagination links are the exception: `<link rel="next">`/`rel="prev"`
+    /// in the head take precedence, but `<a rel="next">` anchors anywhere in
+    /// the document are used as a fallback. The publication date is also
+    /// looked for in the whole document, since `<time>` elements and JSON-LD
+    /// usually live in the body.
+    ///
+    /// # Arguments
+    ///
+    /// * `html` - The HTML document
+    ///
+    /// # Returns
+    ///
+    /// A `Metadata` struct with extracted values
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spiderman::core::document::Metadata;
+    ///
+    /// let html = r#"
+    ///     <head><meta name="description" content="Real"></head>
+    ///     <body><meta name="description" content="Example code"></body>
+    /// "#;
+    ///
+    /// let metadata = Metadata::from_document_head(html);
+    /// assert_eq!(metadata.description, Some("Real".to_string()));
+    /// ```
+    pub fn from_document_head(html: &str) -> Self {
+        let head = head_section(html);
+
+        let mut metadata = Metadata {
+            title: extract_title(head),
+            ..Default::default()
+        };
+
+        // Extract meta tags
+        extract_meta_tags(head, &mut metadata);
+
+        // Extract pagination links (head <link> first, then any <a>)
+        metadata.next =
+            extract_rel_link(head, "link", "next").or_else(|| extract_rel_link(html, "a", "next"));
+        metadata.prev =
+            extract_rel_link(head, "link", "prev").or_else(|| extract_rel_link(html, "a", "prev"));
+
+        metadata.published_at = extract_published_at(html);
+
+        // `rel="icon"` also matches `rel="shortcut icon"`
+        metadata.favicon = extract_rel_link(head, "link", "icon")
+            .or_else(|| extract_rel_link(head, "link", "apple-touch-icon"));
+
+        metadata.canonical = extract_rel_link(head, "link", "canonical");
+        metadata.feeds = extract_feed_links(head);
+
+        metadata
+    }
+}
+
 /// Extracts metadata from HTML content
 ///
 /// This function parses HTML to extract common metadata from the `<head>` section:
@@ -392,8 +1329,11 @@ pub struct Metadata {
 /// - Meta description
 /// - Meta keywords
 /// - Meta author
+/// - Meta viewport
 /// - Other meta tags
 ///
+/// It is equivalent to `Metadata::from_document_head()`.
+///
 /// # Arguments
 ///
 /// * `html` - The HTML content to extract metadata from
@@ -422,15 +1362,158 @@ pub struct Metadata {
 /// assert_eq!(metadata.description, Some("This is an example".to_string()));
 /// ```
 pub fn extract_metadata(html: &str) -> Metadata {
-    let mut metadata = Metadata::default();
+    Metadata::from_document_head(html)
+}
 
-    // Extract title
-    metadata.title = extract_title(html);
+/// Extracts a best-effort publication date from HTML
+///
+/// Sources are tried in order:
+/// 1. `<meta property="article:published_time" content="...">`
+/// 2. `<time datetime="...">` (the first one with a parseable value)
+/// 3. JSON-LD `datePublished` (in any `application/ld+json` script)
+///
+/// Values are parsed as ISO-8601: full timestamps with an offset, local
+/// timestamps (taken as UTC) and plain dates (midnight UTC).
+///
+/// # Arguments
+///
+/// * `html` - The HTML document
+///
+/// # Returns
+///
+/// The publication date if one was found and parsed
+///
+/// # Examples
+///
+/// ```
+/// use spiderman::core::document::extract_published_at;
+///
+/// let html = r#"<time datetime="2024-05-01T08:00:00Z">May 1</time>"#;
+/// assert!(extract_published_at(html).is_some());
+/// assert!(extract_published_at("<p>Undated</p>").is_none());
+/// ```
+pub fn extract_published_at(html: &str) -> Option<DateTime<Utc>> {
+    let meta_re = regex::Regex::new(r#"(?i)<meta\s+([^>]+)>"#).unwrap();
+    let from_meta = meta_re.captures_iter(html).find_map(|cap| {
+        let attrs = cap.get(1)?.as_str();
+        let property = extract_attribute(attrs, "property")?;
+        if property.eq_ignore_ascii_case("article:published_time") {
+            parse_iso8601(&extract_attribute(attrs, "content")?)
+        } else {
+            None
+        }
+    });
+    if from_meta.is_some() {
+        return from_meta;
+    }
 
-    // Extract meta tags
-    extract_meta_tags(html, &mut metadata);
+    let time_re = regex::Regex::new(r#"(?i)<time\s+([^>]+)>"#).unwrap();
+    let from_time = time_re.captures_iter(html).find_map(|cap| {
+        let attrs = cap.get(1)?.as_str();
+        parse_iso8601(&extract_attribute(attrs, "datetime")?)
+    });
+    if from_time.is_some() {
+        return from_time;
+    }
+
+    let json_ld_re = regex::Regex::new(
+        r#"(?is)<script[^>]*type\s*=\s*["']application/ld\+json["'][^>]*>(.*?)</script>"#,
+    )
+    .unwrap();
+    let from_json_ld = json_ld_re.captures_iter(html).find_map(|cap| {
+        let json: serde_json::Value = serde_json::from_str(cap.get(1)?.as_str()).ok()?;
+        find_json_string(&json, "datePublished").and_then(parse_iso8601)
+    });
+    from_json_ld
+}
 
-    metadata
+/// Returns true if a link is a crawlable absolute URL with a host
+fn is_absolute_link(link: &str) -> bool {
+    is_valid_url(link) && parse_base_url(link.trim()).is_some_and(|(_, host, _)| !host.is_empty())
+}
+
+/// Hashes a response body for change detection between crawls
+///
+/// Uses 64-bit FNV-1a, which is stable across platforms and Rust versions,
+/// so hashes stored in exported documents stay comparable.
+///
+/// # Arguments
+///
+/// * `bytes` - The response body
+///
+/// # Returns
+///
+/// The hash as 16 lowercase hex digits
+///
+/// # Examples
+///
+/// ```
+/// use spiderman::core::document::hash_content;
+///
+/// assert_eq!(hash_content(b"<p>Hi</p>"), hash_content(b"<p>Hi</p>"));
+/// assert_ne!(hash_content(b"<p>Hi</p>"), hash_content(b"<p>Bye</p>"));
+/// ```
+pub fn hash_content(bytes: &[u8]) -> String {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+
+    let hash = bytes.iter().fold(OFFSET_BASIS, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+    });
+    format!("{:016x}", hash)
+}
+
+/// Parses an ISO-8601 date or timestamp into UTC
+fn parse_iso8601(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+
+    if let Ok(date) = DateTime::parse_from_rfc3339(value) {
+        return Some(date.with_timezone(&Utc));
+    }
+    for format in [
+        "%Y-%m-%dT%H:%M:%S%.f",
+        "%Y-%m-%dT%H:%M:%S",
+        "%Y-%m-%dT%H:%M",
+    ] {
+        if let Ok(naive) = chrono::NaiveDateTime::parse_from_str(value, format) {
+            return Some(naive.and_utc());
+        }
+    }
+    chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|naive| naive.and_utc())
+}
+
+/// Finds the first string value stored under `key`, at any depth
+fn find_json_string<'v>(value: &'v serde_json::Value, key: &str) -> Option<&'v str> {
+    match value {
+        serde_json::Value::Object(map) => map
+            .get(key)
+            .and_then(|v| v.as_str())
+            .or_else(|| map.values().find_map(|v| find_json_string(v, key))),
+        serde_json::Value::Array(items) => items.iter().find_map(|v| find_json_string(v, key)),
+        _ => None,
+    }
+}
+
+/// Returns the contents of the `<head>` section
+///
+/// Falls back to the whole document if there is no complete
+/// `<head>...</head>` section.
+///
+/// # Arguments
+///
+/// * `html` - The HTML document
+///
+/// # Returns
+///
+/// The head contents, or the whole document
+fn head_section(html: &str) -> &str {
+    let re = regex::Regex::new(r"(?is)<head(?:\s[^>]*)?>(.*?)</head>").unwrap();
+    re.captures(html)
+        .and_then(|cap| cap.get(1))
+        .map_or(html, |m| m.as_str())
 }
 
 /// Extracts the title from HTML
@@ -443,12 +1526,20 @@ pub fn extract_metadata(html: &str) -> Metadata {
 ///
 /// # Returns
 ///
-/// The title text if found, None otherwise
+/// The title text if found, None otherwise. A title that is empty once
+/// comments and surrounding whitespace are removed counts as missing, so
+/// callers fall back to another title source.
 fn extract_title(html: &str) -> Option<String> {
     let re = regex::Regex::new(r"<title[^>]*>(.*?)</title>").ok()?;
-    re.captures(html)
-        .and_then(|cap| cap.get(1))
-        .map(|m| decode_html_entities(m.as_str().trim()))
+    let comments = regex::Regex::new(r"(?s)<!--.*?-->").ok()?;
+
+    let raw = re.captures(html).and_then(|cap| cap.get(1))?.as_str();
+    let title = comments.replace_all(raw, "");
+    let title = title.trim();
+    if title.is_empty() {
+        return None;
+    }
+    Some(decode_html_entities(title))
 }
 
 /// Extracts meta tags from HTML
@@ -478,6 +1569,7 @@ fn extract_meta_tags(html: &str, metadata: &mut Metadata) {
                     "description" => metadata.description = Some(content_decoded),
                     "keywords" => metadata.keywords = Some(content_decoded),
                     "author" => metadata.author = Some(content_decoded),
+                    "viewport" => metadata.viewport = Some(content_decoded),
                     _ => {
                         metadata.other.insert(n, content_decoded);
                     }
@@ -487,6 +1579,69 @@ fn extract_meta_tags(html: &str, metadata: &mut Metadata) {
     }
 }
 
+/// Extracts the href of the first `tag` element with the given `rel`
+///
+/// `rel` may hold several space-separated values; `prev` also matches
+/// `previous`.
+///
+/// # Arguments
+///
+/// * `html` - The HTML content
+/// * `tag` - The element name (`link` or `a`)
+/// * `rel` - The relation to look for (`next` or `prev`)
+///
+/// # Returns
+///
+/// The (entity-decoded) href if found
+fn extract_rel_link(html: &str, tag: &str, rel: &str) -> Option<String> {
+    let pattern = format!(r"(?is)<{}\s+([^>]+)>", tag);
+    let re = regex::Regex::new(&pattern).ok()?;
+
+    let href = re.captures_iter(html).find_map(|cap| {
+        let attrs = cap.get(1)?.as_str();
+        let rels = extract_attribute(attrs, "rel")?.to_lowercase();
+        let matches = rels
+            .split_whitespace()
+            .any(|r| r == rel || (rel == "prev" && r == "previous"));
+
+        if matches {
+            extract_attribute(attrs, "href").map(|href| decode_html_entities(&href))
+        } else {
+            None
+        }
+    });
+    href
+}
+
+/// Extracts the hrefs of the RSS/Atom feeds declared by `<link>` tags
+///
+/// A feed link has `rel="alternate"` and one of the `FEED_TYPES` as its
+/// `type`; other alternates (translations, print versions) are skipped.
+fn extract_feed_links(html: &str) -> Vec<String> {
+    let re = regex::Regex::new(r"(?is)<link\s+([^>]+)>").unwrap();
+
+    let mut feeds: Vec<String> = Vec::new();
+    for cap in re.captures_iter(html) {
+        let attrs = &cap[1];
+        let is_alternate = extract_attribute(attrs, "rel").is_some_and(|rel| {
+            rel.split_whitespace()
+                .any(|r| r.eq_ignore_ascii_case("alternate"))
+        });
+        let is_feed = extract_attribute(attrs, "type")
+            .is_some_and(|t| FEED_TYPES.contains(&t.trim().to_lowercase().as_str()));
+
+        if let (true, true, Some(href)) = (is_alternate, is_feed, extract_attribute(attrs, "href"))
+        {
+            let href = decode_html_entities(&href);
+            if !feeds.contains(&href) {
+                feeds.push(href);
+            }
+        }
+    }
+
+    feeds
+}
+
 /// Extracts an attribute value from an HTML tag's attributes string
 ///
 /// # Arguments
@@ -516,7 +1671,7 @@ fn extract_attribute(attrs: &str, attr_name: &str) -> Option<String> {
 /// # Returns
 ///
 /// Text with entities decoded
-fn decode_html_entities(text: &str) -> String {
+pub(crate) fn decode_html_entities(text: &str) -> String {
     text.replace("&amp;", "&")
         .replace("&lt;", "<")
         .replace("&gt;", ">")
@@ -528,6 +1683,7 @@ fn decode_html_entities(text: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::TimeZone;
 
     // ===== Document Creation Tests =====
 
@@ -566,6 +1722,81 @@ mod tests {
         assert_eq!(doc.raw_html(), Some(html));
     }
 
+    #[test]
+    fn test_document_with_status() {
+        let doc = Document::new("http://example.com", "content".to_string(), vec![]);
+        assert_eq!(doc.status(), None);
+
+        let doc = doc.with_status(404);
+        assert_eq!(doc.status(), Some(404));
+
+        let restored = Document::from_json(&doc.to_json().unwrap()).unwrap();
+        assert_eq!(restored.status(), Some(404));
+    }
+
+    #[test]
+    fn test_document_default_extraction() {
+        let doc = Document::new("http://example.com", "content".to_string(), vec![]);
+
+        assert_eq!(doc.extraction().converter, CONVERTER);
+        assert!(!doc.extraction().stripped_boilerplate);
+        assert_eq!(doc.extraction().content_format, "markdown");
+    }
+
+    #[test]
+    fn test_document_extraction_serialization() {
+        let info = ExtractionInfo {
+            stripped_boilerplate: true,
+            ..ExtractionInfo::default()
+        };
+        let doc = Document::new("http://example.com", "content".to_string(), vec![])
+            .with_extraction(info.clone());
+
+        let json = doc.to_json().unwrap();
+        assert!(json.contains(r#""stripped_boilerplate":true"#));
+        assert_eq!(Document::from_json(&json).unwrap().extraction(), &info);
+    }
+
+    #[test]
+    fn test_document_from_json_without_extraction() {
+        let json = r#"{"url":"http://example.com","title":"","content":"test","links":[],"crawled_at":"2024-01-01T00:00:00Z"}"#;
+        let doc = Document::from_json(json).unwrap();
+        assert_eq!(doc.extraction(), &ExtractionInfo::default());
+    }
+
+    #[test]
+    fn test_document_with_asset_path() {
+        let doc = Document::new("http://example.com/doc.pdf", String::new(), vec![]);
+        assert_eq!(doc.asset_path(), None);
+        assert!(!doc.to_json().unwrap().contains("asset_path"));
+
+        let doc = doc.with_asset_path("assets/doc.pdf".to_string());
+        assert_eq!(doc.asset_path(), Some("assets/doc.pdf"));
+        assert!(doc
+            .to_json()
+            .unwrap()
+            .contains(r#""asset_path":"assets/doc.pdf""#));
+    }
+
+    #[test]
+    fn test_document_text_density_roundtrip() {
+        let doc =
+            Document::new("http://example.com", "text".to_string(), vec![]).with_text_density(0.25);
+
+        let parsed = Document::from_json(&doc.to_json().unwrap()).unwrap();
+        assert_eq!(parsed.text_density(), 0.25);
+    }
+
+    #[test]
+    fn test_document_truncated_flag() {
+        let doc = Document::new("http://example.com", String::new(), vec![]);
+        assert!(!doc.truncated());
+        assert!(!doc.to_json().unwrap().contains("truncated"));
+
+        let doc = doc.with_truncated(true);
+        assert!(doc.to_json().unwrap().contains(r#""truncated":true"#));
+    }
+
     // ===== Getter Tests =====
 
     #[test]
@@ -594,6 +1825,85 @@ mod tests {
         assert_eq!(doc.metadata().len(), 2);
     }
 
+    #[test]
+    fn test_document_with_links() {
+        let doc = Document::new(
+            "http://example.com",
+            "content".to_string(),
+            vec!["http://example.com/old".to_string()],
+        )
+        .with_links(vec![
+            "http://example.com/new".to_string(),
+            "".to_string(),
+            "   ".to_string(),
+            "/relative".to_string(),
+            "mailto:me@example.com".to_string(),
+            "http:///no-host".to_string(),
+            "https://other.com/page".to_string(),
+        ]);
+
+        assert_eq!(
+            doc.links(),
+            ["http://example.com/new", "https://other.com/page"]
+        );
+    }
+
+    #[test]
+    fn test_document_metadata_map() {
+        let map = HashMap::from([
+            ("author".to_string(), "John Doe".to_string()),
+            ("lang".to_string(), "en".to_string()),
+            ("section".to_string(), "news".to_string()),
+        ]);
+
+        let doc = Document::new("http://example.com", "content".to_string(), vec![])
+            .with_metadata("author", "Jane Doe")
+            .with_metadata("keywords", "rust")
+            .with_metadata_map(map);
+
+        assert_eq!(doc.metadata().len(), 4);
+        assert_eq!(doc.get_metadata("author"), Some("John Doe"));
+        assert_eq!(doc.get_metadata("lang"), Some("en"));
+        assert_eq!(doc.get_metadata("section"), Some("news"));
+        assert_eq!(doc.get_metadata("keywords"), Some("rust"));
+    }
+
+    #[test]
+    fn test_document_set_metadata() {
+        let mut doc = Document::new("http://example.com", "content".to_string(), vec![])
+            .with_metadata("author", "Jane Doe");
+
+        doc.set_metadata("author", "John Doe");
+        doc.set_metadata("lang", "en");
+
+        assert_eq!(doc.get_metadata("author"), Some("John Doe"));
+        assert_eq!(doc.get_metadata("lang"), Some("en"));
+        assert_eq!(doc.metadata().len(), 2);
+    }
+
+    #[test]
+    fn test_document_link_partition() {
+        let doc = Document::new(
+            "http://example.com/page",
+            "content".to_string(),
+            vec![
+                "http://example.com/about".to_string(),
+                "https://external.org/post".to_string(),
+                "http://EXAMPLE.com:8080/other".to_string(),
+                "http://blog.example.com/".to_string(),
+            ],
+        );
+
+        assert_eq!(
+            doc.internal_links(),
+            vec!["http://example.com/about", "http://EXAMPLE.com:8080/other"]
+        );
+        assert_eq!(
+            doc.outbound_external_links(),
+            vec!["https://external.org/post", "http://blog.example.com/"]
+        );
+    }
+
     // ===== Serialization Tests =====
 
     #[test]
@@ -645,6 +1955,23 @@ mod tests {
         assert_eq!(title, Some("Test Title".to_string()));
     }
 
+    #[test]
+    fn test_extract_title_whitespace_only() {
+        let html = "<html><head><title>   </title></head></html>";
+
+        assert_eq!(extract_title(html), None);
+        assert_eq!(extract_metadata(html).title, None);
+    }
+
+    #[test]
+    fn test_extract_title_comment_only() {
+        let html = "<html><head><title><!-- todo --></title></head></html>";
+        assert_eq!(extract_title(html), None);
+
+        let html = "<html><head><title> <!-- a --> Real <!-- b --> </title></head></html>";
+        assert_eq!(extract_title(html), Some("Real".to_string()));
+    }
+
     #[test]
     fn test_extract_title_not_found() {
         let html = "<html><head></head></html>";
@@ -705,6 +2032,394 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_extract_metadata_viewport() {
+        let html = r#"<meta name="viewport" content="width=device-width, initial-scale=1">"#;
+
+        let metadata = extract_metadata(html);
+
+        assert_eq!(
+            metadata.viewport.as_deref(),
+            Some("width=device-width, initial-scale=1")
+        );
+        assert!(metadata.other.is_empty());
+    }
+
+    #[test]
+    fn test_document_is_mobile_friendly() {
+        let page = |viewport: Option<&str>| {
+            let doc = Document::new("http://example.com", String::new(), vec![]);
+            match viewport {
+                Some(viewport) => doc.with_metadata("viewport", viewport),
+                None => doc,
+            }
+        };
+
+        assert!(page(Some("width=device-width, initial-scale=1")).is_mobile_friendly());
+        assert!(page(Some("initial-scale=1; Width = Device-Width")).is_mobile_friendly());
+        assert!(!page(Some("width=1024")).is_mobile_friendly());
+        assert!(!page(None).is_mobile_friendly());
+    }
+
+    #[test]
+    fn test_metadata_ignores_body_meta_tags() {
+        let html = r#"
+            <html>
+                <head>
+                    <title>Real Title</title>
+                    <meta name="description" content="Head description">
+                </head>
+                <body>
+                    <pre>&lt;meta name="description" content="Example"&gt;</pre>
+                    <meta name="description" content="Body description">
+                    <meta name="author" content="Body Author">
+                </body>
+            </html>
+        "#;
+
+        let metadata = Metadata::from_document_head(html);
+
+        assert_eq!(metadata.title, Some("Real Title".to_string()));
+        assert_eq!(metadata.description, Some("Head description".to_string()));
+        assert_eq!(metadata.author, None);
+    }
+
+    #[test]
+    fn test_metadata_head_with_attributes() {
+        let html = r#"<HEAD lang="en"><meta name="author" content="Jane"></HEAD>
+            <header><meta name="keywords" content="ignored"></header>"#;
+
+        let metadata = Metadata::from_document_head(html);
+
+        assert_eq!(metadata.author, Some("Jane".to_string()));
+        assert_eq!(metadata.keywords, None);
+    }
+
+    #[test]
+    fn test_metadata_without_head_scans_whole_document() {
+        let html = r#"<meta name="description" content="No head here">"#;
+
+        let metadata = Metadata::from_document_head(html);
+
+        assert_eq!(metadata.description, Some("No head here".to_string()));
+    }
+
+    // ===== Publication Date Tests =====
+
+    #[test]
+    fn test_published_at_from_meta() {
+        let html = r#"<head>
+            <meta property="article:published_time" content="2024-03-01T09:30:00+02:00">
+        </head><body><time datetime="2020-01-01">Old</time></body>"#;
+
+        assert_eq!(
+            extract_metadata(html).published_at,
+            Some(Utc.with_ymd_and_hms(2024, 3, 1, 7, 30, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_published_at_from_time_element() {
+        let html = r#"<body>
+            <time>Yesterday</time>
+            <time datetime="not a date">Soon</time>
+            <time class="pub" datetime="2023-11-05">Nov 5</time>
+        </body>"#;
+
+        assert_eq!(
+            extract_published_at(html),
+            Some(Utc.with_ymd_and_hms(2023, 11, 5, 0, 0, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_published_at_from_json_ld() {
+        let html = r#"<script type="application/ld+json">
+            {"@context": "https://schema.org", "@graph": [
+                {"@type": "WebSite", "name": "News"},
+                {"@type": "NewsArticle", "datePublished": "2022-07-14T18:05:00"}
+            ]}
+        </script>"#;
+
+        assert_eq!(
+            extract_published_at(html),
+            Some(Utc.with_ymd_and_hms(2022, 7, 14, 18, 5, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_published_at_missing() {
+        let html = r#"<head><meta name="description" content="No date"></head>
+            <script type="application/ld+json">{"@type": "WebPage"}</script>"#;
+
+        assert_eq!(extract_metadata(html).published_at, None);
+    }
+
+    #[test]
+    fn test_document_published_at_serialization() {
+        let date = Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap();
+        let doc = Document::new("http://example.com", String::new(), vec![]);
+        assert!(!doc.to_json().unwrap().contains("published_at"));
+
+        let doc = doc.with_published_at(Some(date));
+        let parsed = Document::from_json(&doc.to_json().unwrap()).unwrap();
+        assert_eq!(parsed.published_at(), Some(date));
+    }
+
+    // ===== Change Detection Tests =====
+
+    #[test]
+    fn test_hash_content() {
+        // Reference FNV-1a values
+        assert_eq!(hash_content(b""), "cbf29ce484222325");
+        assert_eq!(hash_content(b"a"), "af63dc4c8601ec8c");
+        assert_ne!(hash_content(b"ab"), hash_content(b"ba"));
+    }
+
+    #[test]
+    fn test_document_etag_and_hash_roundtrip() {
+        let doc = Document::new("http://example.com", "content".to_string(), vec![])
+            .with_etag(Some("\"v1\"".to_string()))
+            .with_content_hash(hash_content(b"<p>content</p>"));
+
+        let json = serde_json::to_string(&doc).unwrap();
+        let parsed: Document = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.etag(), Some("\"v1\""));
+        assert_eq!(parsed.content_hash(), doc.content_hash());
+
+        let plain = Document::new("http://example.com", "content".to_string(), vec![]);
+        assert!(!serde_json::to_string(&plain).unwrap().contains("etag"));
+    }
+
+    #[test]
+    fn test_content_diff_counts_added_and_removed_lines() {
+        let old = Document::new(
+            "http://example.com",
+            "# Title\nintro\nold paragraph\nfooter".to_string(),
+            vec![],
+        );
+        let new = Document::new(
+            "http://example.com",
+            "# Title\nintro\nnew paragraph\nanother one\nfooter\nsignature".to_string(),
+            vec![],
+        );
+
+        let diff = old.content_diff(&new);
+        assert_eq!(diff.added, 3);
+        assert_eq!(diff.removed, 1);
+        assert!(!diff.is_empty());
+        assert_eq!(
+            diff.unified,
+            "--- http://example.com\n+++ http://example.com\n \
+             # Title\n intro\n-old paragraph\n+new paragraph\n+another one\n \
+             footer\n+signature\n"
+        );
+
+        let reverse = new.content_diff(&old);
+        assert_eq!((reverse.added, reverse.removed), (1, 3));
+    }
+
+    #[test]
+    fn test_content_diff_identical_and_empty() {
+        let doc = Document::new("http://example.com", "a\nb\nc".to_string(), vec![]);
+        assert!(doc.content_diff(&doc.clone()).is_empty());
+
+        let empty = Document::new("http://example.com", String::new(), vec![]);
+        let diff = empty.content_diff(&doc);
+        assert_eq!((diff.added, diff.removed), (3, 0));
+        assert_eq!(doc.content_diff(&empty).removed, 3);
+    }
+
+    // ===== Feed Link Tests =====
+
+    #[test]
+    fn test_metadata_feeds() {
+        let html = r#"<head>
+            <link rel="alternate" type="application/rss+xml" title="RSS" href="/feed.xml">
+            <link rel="alternate" type="application/atom+xml" href="/atom.xml?a=1&amp;b=2">
+            <link rel="alternate" hreflang="de" href="/de/">
+            <link rel="stylesheet" type="text/css" href="/style.css">
+        </head>"#;
+
+        let metadata = extract_metadata(html);
+        assert_eq!(metadata.feeds, vec!["/feed.xml", "/atom.xml?a=1&b=2"]);
+        assert!(extract_metadata("<head></head>").feeds.is_empty());
+    }
+
+    // ===== Canonical URL Tests =====
+
+    #[test]
+    fn test_metadata_canonical() {
+        let html = r#"<head><link rel="canonical" href="/articles/1?ref=a&amp;b=2"></head>"#;
+        let metadata = extract_metadata(html);
+        assert_eq!(
+            metadata.canonical,
+            Some("/articles/1?ref=a&b=2".to_string())
+        );
+
+        let metadata = extract_metadata("<head><title>None</title></head>");
+        assert_eq!(metadata.canonical, None);
+    }
+
+    #[test]
+    fn test_document_canonical_url_roundtrip() {
+        let doc = Document::new("http://example.com/a?x=1", "content".to_string(), vec![])
+            .with_canonical_url(Some("http://example.com/a".to_string()));
+
+        let json = serde_json::to_string(&doc).unwrap();
+        let parsed: Document = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.canonical_url(), Some("http://example.com/a"));
+        assert_eq!(parsed.url(), "http://example.com/a?x=1");
+    }
+
+    #[test]
+    fn test_canonical_mismatch() {
+        let same = Document::new("http://Example.com:80/a/", String::new(), vec![])
+            .with_canonical_url(Some("http://example.com/a".to_string()));
+        assert!(!same.canonical_mismatch());
+
+        let other = Document::new("http://example.com/a?x=1", String::new(), vec![])
+            .with_canonical_url(Some("http://example.com/a".to_string()));
+        assert!(other.canonical_mismatch());
+
+        let none = Document::new("http://example.com/a", String::new(), vec![]);
+        assert!(!none.canonical_mismatch());
+    }
+
+    #[test]
+    fn test_normalized_url() {
+        let lower = Document::new("http://example.com/a", String::new(), vec![]);
+        let mixed = Document::new("HTTP://Example.COM:80/a/#intro", String::new(), vec![]);
+        assert_eq!(lower.normalized_url(), mixed.normalized_url());
+        assert_eq!(mixed.normalized_url(), "http://example.com/a");
+        // The stored URL is left as crawled
+        assert_eq!(mixed.url(), "HTTP://Example.COM:80/a/#intro");
+
+        let tracked = Document::new("http://example.com/a?utm_source=x", String::new(), vec![]);
+        let options = NormalizeOptions::new().with_strip_params(vec!["utm_source".to_string()]);
+        assert_eq!(
+            tracked.normalized_url_with_options(&options),
+            lower.normalized_url()
+        );
+    }
+
+    // ===== Favicon Tests =====
+
+    #[test]
+    fn test_favicon_explicit_link() {
+        let html = r#"<head>
+            <link rel="stylesheet" href="/style.css">
+            <link rel="shortcut icon" href="icons/fav.png">
+        </head>"#;
+        let metadata = extract_metadata(html);
+
+        assert_eq!(metadata.favicon, Some("icons/fav.png".to_string()));
+        assert_eq!(
+            metadata.favicon_url("http://example.com/docs/page"),
+            Some("http://example.com/docs/icons/fav.png".to_string())
+        );
+    }
+
+    #[test]
+    fn test_favicon_apple_touch_icon() {
+        let html = r#"<head><link rel="apple-touch-icon" href="https://cdn.example.com/touch.png"></head>"#;
+
+        assert_eq!(
+            extract_metadata(html).favicon_url("http://example.com/"),
+            Some("https://cdn.example.com/touch.png".to_string())
+        );
+    }
+
+    #[test]
+    fn test_favicon_default_fallback() {
+        let metadata = extract_metadata("<head><title>Plain</title></head>");
+
+        assert_eq!(metadata.favicon, None);
+        assert_eq!(
+            metadata.favicon_url("http://example.com:8080/a/b"),
+            Some("http://example.com:8080/favicon.ico".to_string())
+        );
+    }
+
+    // ===== Pagination Tests =====
+
+    #[test]
+    fn test_extract_rel_next_and_prev_links() {
+        let html = r#"<html><head>
+            <link rel="prev" href="/articles?page=1">
+            <link rel="next" href="/articles?page=3&amp;sort=new">
+        </head><body></body></html>"#;
+
+        let metadata = extract_metadata(html);
+
+        assert_eq!(metadata.next, Some("/articles?page=3&sort=new".to_string()));
+        assert_eq!(metadata.prev, Some("/articles?page=1".to_string()));
+    }
+
+    #[test]
+    fn test_extract_rel_next_from_anchor() {
+        let html = r#"<html><head><title>List</title></head><body>
+            <a href="/page/1" rel="previous">Back</a>
+            <a class="pager" rel="nofollow next" href="/page/3">More</a>
+        </body></html>"#;
+
+        let metadata = extract_metadata(html);
+
+        assert_eq!(metadata.next, Some("/page/3".to_string()));
+        assert_eq!(metadata.prev, Some("/page/1".to_string()));
+    }
+
+    #[test]
+    fn test_extract_rel_link_absent() {
+        let html = r#"<head><link rel="stylesheet" href="/style.css"></head>"#;
+        let metadata = extract_metadata(html);
+        assert_eq!(metadata.next, None);
+        assert_eq!(metadata.prev, None);
+    }
+
+    // ===== Robots Directive Tests =====
+
+    #[test]
+    fn test_robots_directives_parse() {
+        assert_eq!(
+            RobotsDirectives::parse("noindex, nofollow"),
+            RobotsDirectives {
+                noindex: true,
+                nofollow: true,
+                ..RobotsDirectives::default()
+            }
+        );
+        assert_eq!(
+            RobotsDirectives::parse("NoArchive, nosnippet"),
+            RobotsDirectives {
+                noarchive: true,
+                nosnippet: true,
+                ..RobotsDirectives::default()
+            }
+        );
+        assert_eq!(
+            RobotsDirectives::parse("None"),
+            RobotsDirectives::parse("noindex,nofollow")
+        );
+        assert_eq!(
+            RobotsDirectives::parse("index, follow, max-snippet:50"),
+            RobotsDirectives::default()
+        );
+    }
+
+    #[test]
+    fn test_metadata_robots() {
+        let html = r#"<head><meta name="ROBOTS" content="noindex"></head>"#;
+        let robots = extract_metadata(html).robots();
+
+        assert!(robots.noindex);
+        assert!(!robots.nofollow);
+        assert_eq!(
+            extract_metadata("<head></head>").robots(),
+            RobotsDirectives::default()
+        );
+    }
+
     #[test]
     fn test_decode_html_entities() {
         let text = "Test &amp; Example &lt;tag&gt; &quot;quoted&quot; &#39;apostrophe&#39;";
@@ -742,6 +2457,45 @@ mod tests {
         assert_eq!(doc.links().len(), 0);
     }
 
+    #[test]
+    fn test_document_add_link() {
+        let mut doc = Document::new(
+            "http://example.com",
+            "content".to_string(),
+            vec!["http://example.com/a".to_string()],
+        );
+
+        assert!(doc.add_link("http://example.com/b".to_string()));
+        // Duplicates (in any spelling) are a no-op
+        assert!(!doc.add_link("http://example.com/a".to_string()));
+        assert!(!doc.add_link("HTTP://Example.com/b/#top".to_string()));
+
+        assert_eq!(
+            doc.links(),
+            &[
+                "http://example.com/a".to_string(),
+                "http://example.com/b".to_string()
+            ][..]
+        );
+    }
+
+    #[test]
+    fn test_document_remove_link() {
+        let mut doc = Document::new(
+            "http://example.com",
+            "content".to_string(),
+            vec![
+                "http://example.com/a".to_string(),
+                "http://example.com/b".to_string(),
+            ],
+        );
+
+        assert!(doc.remove_link("http://example.com/a/"));
+        assert!(!doc.remove_link("http://example.com/missing"));
+        assert_eq!(doc.links(), &["http://example.com/b".to_string()][..]);
+        assert_eq!(doc.link_count(), 1);
+    }
+
     #[test]
     fn test_document_many_links() {
         let links: Vec<String> = (0..100)
@@ -0,0 +1,209 @@
+//! Error Module
+//!
+//! This module defines `CrawlError`, the error type returned by the
+//! crawler's high-level operations.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use spiderman::core::{CrawlConfig, CrawlError, Spiderman};
+//!
+//! async_std::task::block_on(async {
+//!     let spider = Spiderman::new("http://example.com");
+//!     match spider.list_sitemap_urls(&CrawlConfig::default()).await {
+//!         Ok(urls) => println!("Found {} URLs", urls.len()),
+//!         Err(CrawlError::Fetch(msg)) => eprintln!("Fetch failed: {}", msg),
+//!         Err(e) => eprintln!("Error: {}", e),
+//!     }
+//! });
+//! ```
+
+use super::crawl::CrawlResult;
+use std::fmt;
+use std::io;
+
+/// Errors that can occur while crawling
+#[derive(Debug)]
+pub enum CrawlError {
+    /// A page could not be fetched (connection, protocol or parse failure)
+    Fetch(String),
+
+    /// A file system or network I/O error
+    Io(io::Error),
+
+    /// The host name could not be resolved
+    DnsFailure {
+        /// Host that failed to resolve
+        host: String,
+
+        /// The underlying lookup error
+        source: io::Error,
+    },
+
+    /// The host actively refused the connection
+    ConnectionRefused {
+        /// Host that refused the connection
+        host: String,
+
+        /// The underlying connection error
+        source: io::Error,
+    },
+
+    /// The connection was reset or aborted while talking to the host
+    ConnectionReset {
+        /// Host whose connection was reset
+        host: String,
+
+        /// The underlying I/O error
+        source: io::Error,
+    },
+
+    /// The seed URL is malformed (no host, or a scheme other than `http`
+    /// or `https`); reported before anything is fetched
+    InvalidSeed {
+        /// The seed URL as given
+        url: String,
+
+        /// What is wrong with it
+        reason: String,
+    },
+
+    /// A URL redirected to itself (its resolved `Location` is the requested
+    /// URL exactly; a redirect that only adds a trailing slash is not a
+    /// loop)
+    RedirectLoop {
+        /// URL that redirected to itself
+        url: String,
+    },
+
+    /// The crawl was aborted after reaching `CrawlConfig::max_failures`
+    TooManyFailures {
+        /// Number of failures that triggered the abort
+        failures: usize,
+
+        /// Statistics and documents collected before aborting
+        result: Box<CrawlResult>,
+    },
+}
+
+impl fmt::Display for CrawlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CrawlError::Fetch(msg) => write!(f, "Fetch failed: {}", msg),
+            CrawlError::Io(e) => write!(f, "I/O error: {}", e),
+            CrawlError::DnsFailure { host, source } => {
+                write!(f, "DNS lookup failed for {}: {}", host, source)
+            }
+            CrawlError::ConnectionRefused { host, source } => {
+                write!(f, "Connection refused by {}: {}", host, source)
+            }
+            CrawlError::ConnectionReset { host, source } => {
+                write!(f, "Connection reset by {}: {}", host, source)
+            }
+            CrawlError::InvalidSeed { url, reason } => {
+                write!(f, "Invalid seed URL {:?}: {}", url, reason)
+            }
+            CrawlError::RedirectLoop { url } => {
+                write!(f, "Redirect loop: {} redirects to itself", url)
+            }
+            CrawlError::TooManyFailures { failures, result } => write!(
+                f,
+                "Crawl aborted after {} failures ({} pages crawled)",
+                failures, result.pages_crawled
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CrawlError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CrawlError::Io(e) => Some(e),
+            CrawlError::DnsFailure { source, .. }
+            | CrawlError::ConnectionRefused { source, .. }
+            | CrawlError::ConnectionReset { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for CrawlError {
+    fn from(e: io::Error) -> Self {
+        CrawlError::Io(e)
+    }
+}
+
+impl From<Box<dyn std::error::Error>> for CrawlError {
+    /// Unwraps a boxed `CrawlError` (e.g. a categorized connection error
+    /// from the fetcher); anything else becomes `CrawlError::Fetch`
+    fn from(e: Box<dyn std::error::Error>) -> Self {
+        match e.downcast::<CrawlError>() {
+            Ok(err) => *err,
+            Err(e) => CrawlError::Fetch(e.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crawl_error_display() {
+        let err = CrawlError::Fetch("connection refused".to_string());
+        assert_eq!(err.to_string(), "Fetch failed: connection refused");
+    }
+
+    #[test]
+    fn test_crawl_error_redirect_loop_display() {
+        let err = CrawlError::RedirectLoop {
+            url: "http://example.com/page".to_string(),
+        };
+        assert_eq!(
+            err.to_string(),
+            "Redirect loop: http://example.com/page redirects to itself"
+        );
+    }
+
+    #[test]
+    fn test_crawl_error_invalid_seed_display() {
+        let err = CrawlError::InvalidSeed {
+            url: "htp://example".to_string(),
+            reason: "unsupported scheme `htp`".to_string(),
+        };
+        assert_eq!(
+            err.to_string(),
+            "Invalid seed URL \"htp://example\": unsupported scheme `htp`"
+        );
+    }
+
+    #[test]
+    fn test_crawl_error_from_io() {
+        let err: CrawlError = io::Error::new(io::ErrorKind::NotFound, "missing").into();
+        assert!(matches!(err, CrawlError::Io(_)));
+        assert!(std::error::Error::source(&err).is_some());
+    }
+
+    #[test]
+    fn test_crawl_error_from_boxed_error() {
+        let boxed: Box<dyn std::error::Error> = "Invalid URL: empty host".into();
+        let err: CrawlError = boxed.into();
+        assert!(matches!(err, CrawlError::Fetch(ref msg) if msg == "Invalid URL: empty host"));
+    }
+
+    #[test]
+    fn test_crawl_error_from_boxed_crawl_error() {
+        let boxed: Box<dyn std::error::Error> = Box::new(CrawlError::ConnectionRefused {
+            host: "localhost:1".to_string(),
+            source: io::Error::from(io::ErrorKind::ConnectionRefused),
+        });
+        let err: CrawlError = boxed.into();
+
+        assert!(
+            matches!(err, CrawlError::ConnectionRefused { ref host, .. } if host == "localhost:1")
+        );
+        assert!(err
+            .to_string()
+            .starts_with("Connection refused by localhost:1"));
+    }
+}
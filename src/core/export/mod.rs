@@ -8,8 +8,12 @@
 //! The Export module supports:
 //! 1. **JSONL Export** - One JSON document per line (recommended for large datasets)
 //! 2. **JSON Export** - Single JSON array (good for small datasets)
-//! 3. **Batch Operations** - Efficient bulk export
-//! 4. **Error Handling** - Robust error reporting
+//! 3. **CSV / XML Export** - Tabular and XML output (see `OutputFormat`)
+//! 4. **Batch Operations** - Efficient bulk export
+//! 5. **Merging** - Combine JSONL files from sharded crawls
+//! 6. **Assets** - Save raw bytes of non-HTML resources
+//! 7. **Sinks** - Hand documents to custom destinations (`DocumentSink`)
+//! 8. **Error Handling** - Robust error reporting
 //!
 //! # JSONL Format
 //!
@@ -49,10 +53,211 @@
 //! exporter.export_batch(&documents, "crawl.jsonl").unwrap();
 //! ```
 
-use crate::core::document::Document;
+use crate::core::document::{hash_content, Document};
+use crate::core::url_manager::normalize_url_for_storage;
+use std::collections::HashSet;
+use std::fmt;
 use std::fs::{self, OpenOptions};
 use std::io::{self, Write};
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// Default maximum length of file names derived from URLs, safely below
+/// the 255-byte limit of common file systems
+pub const DEFAULT_MAX_FILENAME_LEN: usize = 200;
+
+/// How `Exporter::merge_jsonl_with` handles records sharing a URL
+///
+/// URLs are compared in normalized form.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DuplicateUrls {
+    /// Keep every record
+    #[default]
+    KeepAll,
+
+    /// Keep the first record seen for each URL
+    KeepFirst,
+
+    /// Keep the last record seen for each URL (e.g. from a later recrawl)
+    KeepLatest,
+}
+
+/// File format used for crawl output
+///
+/// JSONL is written one document at a time as pages are crawled; the other
+/// formats are written once, from all documents, when the crawl finishes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    /// One JSON document per line (streamed)
+    #[default]
+    Jsonl,
+
+    /// A single pretty-printed JSON array
+    JsonArray,
+
+    /// Comma-separated values with a header row
+    Csv,
+
+    /// An XML `<documents>` tree
+    Xml,
+}
+
+impl OutputFormat {
+    /// Returns true if documents are appended as they are crawled
+    pub fn is_streaming(&self) -> bool {
+        matches!(self, OutputFormat::Jsonl)
+    }
+}
+
+/// A `Document` field, for exporting a projection of documents (see
+/// `Exporter::export_projection`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DocField {
+    /// The page URL
+    Url,
+
+    /// The page title
+    Title,
+
+    /// The meta description
+    Description,
+
+    /// The Markdown content
+    Content,
+
+    /// The stored HTML
+    RawHtml,
+
+    /// The outbound links
+    Links,
+
+    /// When the page was crawled
+    CrawledAt,
+
+    /// The HTTP status code
+    Status,
+
+    /// How the content was extracted
+    Extraction,
+
+    /// Where a non-HTML resource was saved
+    AssetPath,
+
+    /// Markdown size relative to the HTML
+    TextDensity,
+
+    /// The publication date
+    PublishedAt,
+
+    /// Whether the HTML was truncated
+    Truncated,
+
+    /// The icon URL
+    Favicon,
+
+    /// The declared canonical URL
+    CanonicalUrl,
+
+    /// The anchor text the page was discovered through
+    DiscoveryAnchor,
+
+    /// Whether the page looks like a login wall or paywall
+    IsGated,
+
+    /// The scripts, stylesheets and images the page references
+    Assets,
+
+    /// The `ETag` header
+    Etag,
+
+    /// The response body hash
+    ContentHash,
+
+    /// Additional metadata (keywords, author, ...)
+    Metadata,
+}
+
+impl DocField {
+    /// Returns the field's key in exported JSON
+    pub fn key(&self) -> &'static str {
+        match self {
+            DocField::Url => "url",
+            DocField::Title => "title",
+            DocField::Description => "description",
+            DocField::Content => "content",
+            DocField::RawHtml => "raw_html",
+            DocField::Links => "links",
+            DocField::CrawledAt => "crawled_at",
+            DocField::Status => "status",
+            DocField::Extraction => "extraction",
+            DocField::AssetPath => "asset_path",
+            DocField::TextDensity => "text_density",
+            DocField::PublishedAt => "published_at",
+            DocField::Truncated => "truncated",
+            DocField::Favicon => "favicon",
+            DocField::CanonicalUrl => "canonical_url",
+            DocField::DiscoveryAnchor => "discovery_anchor",
+            DocField::IsGated => "is_gated",
+            DocField::Assets => "assets",
+            DocField::Etag => "etag",
+            DocField::ContentHash => "content_hash",
+            DocField::Metadata => "metadata",
+        }
+    }
+}
+
+/// A custom destination for crawled documents (database, network service...)
+///
+/// During a crawl, sinks are written from a background thread, one document
+/// at a time (see `CrawlConfig::with_sink`).
+///
+/// # Examples
+///
+/// ```
+/// use spiderman::core::document::Document;
+/// use spiderman::core::export::DocumentSink;
+/// use std::io;
+///
+/// struct Printer;
+///
+/// impl DocumentSink for Printer {
+///     fn write_document(&mut self, document: &Document) -> io::Result<()> {
+///         println!("{}", document.url());
+///         Ok(())
+///     }
+/// }
+/// ```
+pub trait DocumentSink: Send {
+    /// Writes one document
+    fn write_document(&mut self, document: &Document) -> io::Result<()>;
+}
+
+/// A `DocumentSink` that can be stored in (and cloned along with)
+/// `CrawlConfig`
+#[derive(Clone)]
+pub struct SharedSink(Arc<Mutex<dyn DocumentSink>>);
+
+impl SharedSink {
+    /// Wraps a sink for sharing
+    pub fn new<S: DocumentSink + 'static>(sink: S) -> Self {
+        Self(Arc::new(Mutex::new(sink)))
+    }
+
+    /// Writes one document to the wrapped sink
+    pub fn write_document(&self, document: &Document) -> io::Result<()> {
+        let mut sink = self
+            .0
+            .lock()
+            .map_err(|_| io::Error::other("document sink poisoned"))?;
+        sink.write_document(document)
+    }
+}
+
+impl fmt::Debug for SharedSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SharedSink(..)")
+    }
+}
 
 /// Exporter for saving crawled documents to files
 ///
@@ -76,6 +281,9 @@ use std::path::{Path, PathBuf};
 pub struct Exporter {
     /// Output directory path
     output_dir: PathBuf,
+
+    /// Maximum length of file names derived from URLs
+    max_filename_len: usize,
 }
 
 impl Exporter {
@@ -101,9 +309,19 @@ impl Exporter {
     pub fn new<P: AsRef<Path>>(output_dir: P) -> Self {
         Self {
             output_dir: output_dir.as_ref().to_path_buf(),
+            max_filename_len: DEFAULT_MAX_FILENAME_LEN,
         }
     }
 
+    /// Sets the maximum length of file names derived from URLs and returns
+    /// self (builder pattern)
+    ///
+    /// See `slugify_url_with_max_len`.
+    pub fn with_max_filename_len(mut self, max: usize) -> Self {
+        self.max_filename_len = max;
+        self
+    }
+
     /// Ensures the output directory exists, creating it if necessary
     ///
     /// # Returns
@@ -130,6 +348,37 @@ impl Exporter {
         self.output_dir.join(filename)
     }
 
+    /// Replaces an output file with new contents, atomically
+    ///
+    /// The contents are written to `<filename>.tmp` in the output directory
+    /// and renamed over the target once complete, so readers see either
+    /// the old file or the whole new one, never a partial write. The
+    /// temporary file is removed if writing fails.
+    ///
+    /// # Arguments
+    ///
+    /// * `filename` - The name of the output file
+    /// * `contents` - The complete file contents
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if the file was replaced
+    /// `Err` if the temporary file can't be written or renamed
+    fn write_atomically(&self, filename: &str, contents: &[u8]) -> io::Result<()> {
+        let path = self.get_output_path(filename);
+        let tmp_path = self.get_output_path(&format!("{}.tmp", filename));
+
+        let written = fs::File::create(&tmp_path).and_then(|mut file| {
+            file.write_all(contents)?;
+            file.sync_all()
+        });
+        let result = written.and_then(|()| fs::rename(&tmp_path, &path));
+        if result.is_err() {
+            let _ = fs::remove_file(&tmp_path);
+        }
+        result
+    }
+
     /// Exports a single document to a JSONL file
     ///
     /// Appends the document as a new line to the specified file.
@@ -221,6 +470,7 @@ impl Exporter {
     ///
     /// Creates a JSON file with all documents in an array.
     /// Use this for small datasets only - large datasets should use JSONL.
+    /// The file is replaced atomically (see `write_atomically`).
     ///
     /// # Arguments
     ///
@@ -245,14 +495,340 @@ impl Exporter {
     pub fn export_json_array(&self, documents: &[Document], filename: &str) -> io::Result<()> {
         self.ensure_output_dir()?;
 
-        let file_path = self.get_output_path(filename);
         let json = serde_json::to_string_pretty(documents)
             .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
 
-        fs::write(file_path, json)?;
+        self.write_atomically(filename, json.as_bytes())
+    }
+
+    /// Exports documents to a CSV file
+    ///
+    /// Writes a header row followed by one row per document with the
+    /// columns `url,title,description,status,crawled_at,link_count,content`.
+    /// Fields containing commas, quotes or line breaks are quoted. The file is
+    /// overwritten if it exists, atomically (see `write_atomically`).
+    ///
+    /// # Arguments
+    ///
+    /// * `documents` - Slice of documents to export
+    /// * `filename` - Name of the output file
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if export succeeds
+    /// `Err` if the file can't be written
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use spiderman::core::export::Exporter;
+    ///
+    /// let exporter = Exporter::new("output");
+    /// let documents = vec![/* ... */];
+    ///
+    /// exporter.export_csv(&documents, "crawl.csv").unwrap();
+    /// ```
+    pub fn export_csv(&self, documents: &[Document], filename: &str) -> io::Result<()> {
+        self.ensure_output_dir()?;
+
+        let mut csv = String::from("url,title,description,status,crawled_at,link_count,content\n");
+        for doc in documents {
+            let fields = [
+                doc.url().to_string(),
+                doc.title().to_string(),
+                doc.description().unwrap_or_default().to_string(),
+                doc.status().map(|s| s.to_string()).unwrap_or_default(),
+                doc.crawled_at().to_rfc3339(),
+                doc.link_count().to_string(),
+                doc.content().to_string(),
+            ];
+            let row: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+            csv.push_str(&row.join(","));
+            csv.push('\n');
+        }
+
+        self.write_atomically(filename, csv.as_bytes())
+    }
+
+    /// Exports documents to an XML file
+    ///
+    /// Writes a `<documents>` root with one `<document>` element per
+    /// document. Text is escaped, and characters not allowed in XML are
+    /// dropped. The file is overwritten if it exists, atomically (see
+    /// `write_atomically`).
+    ///
+    /// # Arguments
+    ///
+    /// * `documents` - Slice of documents to export
+    /// * `filename` - Name of the output file
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if export succeeds
+    /// `Err` if the file can't be written
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use spiderman::core::export::Exporter;
+    ///
+    /// let exporter = Exporter::new("output");
+    /// let documents = vec![/* ... */];
+    ///
+    /// exporter.export_xml(&documents, "crawl.xml").unwrap();
+    /// ```
+    pub fn export_xml(&self, documents: &[Document], filename: &str) -> io::Result<()> {
+        self.ensure_output_dir()?;
+
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<documents>\n");
+        for doc in documents {
+            xml.push_str("  <document>\n");
+            xml.push_str(&format!("    <url>{}</url>\n", xml_escape(doc.url())));
+            xml.push_str(&format!("    <title>{}</title>\n", xml_escape(doc.title())));
+            if let Some(description) = doc.description() {
+                xml.push_str(&format!(
+                    "    <description>{}</description>\n",
+                    xml_escape(description)
+                ));
+            }
+            if let Some(status) = doc.status() {
+                xml.push_str(&format!("    <status>{}</status>\n", status));
+            }
+            xml.push_str(&format!(
+                "    <crawled_at>{}</crawled_at>\n",
+                doc.crawled_at().to_rfc3339()
+            ));
+            xml.push_str("    <links>\n");
+            for link in doc.links() {
+                xml.push_str(&format!("      <link>{}</link>\n", xml_escape(link)));
+            }
+            xml.push_str("    </links>\n");
+            xml.push_str(&format!(
+                "    <content>{}</content>\n",
+                xml_escape(doc.content())
+            ));
+            xml.push_str("  </document>\n");
+        }
+        xml.push_str("</documents>\n");
+
+        self.write_atomically(filename, xml.as_bytes())
+    }
+
+    /// Exports selected fields of documents to a JSONL file
+    ///
+    /// Each line holds only the chosen fields, under the same keys as a
+    /// full export. Optional fields a document doesn't have are left out,
+    /// as in a full export. Like `export_batch`, the file is appended to.
+    ///
+    /// # Arguments
+    ///
+    /// * `documents` - Slice of documents to export
+    /// * `fields` - The fields to keep
+    /// * `filename` - Name of the output file
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if all documents exported successfully
+    /// `Err` on first error encountered
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use spiderman::core::export::{DocField, Exporter};
+    ///
+    /// let exporter = Exporter::new("output");
+    /// let documents = vec![/* ... */];
+    ///
+    /// exporter
+    ///     .export_projection(&documents, &[DocField::Url, DocField::Title], "titles.jsonl")
+    ///     .unwrap();
+    /// ```
+    pub fn export_projection(
+        &self,
+        documents: &[Document],
+        fields: &[DocField],
+        filename: &str,
+    ) -> io::Result<()> {
+        self.ensure_output_dir()?;
+
+        let file_path = self.get_output_path(filename);
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(file_path)?;
+
+        for doc in documents {
+            let value = serde_json::to_value(doc)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            let mut full = match value {
+                serde_json::Value::Object(map) => map,
+                _ => serde_json::Map::new(),
+            };
+
+            let projection: serde_json::Map<String, serde_json::Value> = fields
+                .iter()
+                .filter_map(|field| {
+                    let key = field.key();
+                    full.remove(key).map(|value| (key.to_string(), value))
+                })
+                .collect();
+            writeln!(file, "{}", serde_json::Value::Object(projection))?;
+        }
+
         Ok(())
     }
 
+    /// Exports all documents at once in the given format
+    ///
+    /// JSONL output is appended (like `export_batch`); the other formats
+    /// overwrite the file.
+    ///
+    /// # Arguments
+    ///
+    /// * `documents` - Slice of documents to export
+    /// * `filename` - Name of the output file
+    /// * `format` - The file format to write
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if export succeeds
+    /// `Err` if serialization or file write fails
+    pub fn export_as(
+        &self,
+        documents: &[Document],
+        filename: &str,
+        format: OutputFormat,
+    ) -> io::Result<()> {
+        match format {
+            OutputFormat::Jsonl => self.export_batch(documents, filename),
+            OutputFormat::JsonArray => self.export_json_array(documents, filename),
+            OutputFormat::Csv => self.export_csv(documents, filename),
+            OutputFormat::Xml => self.export_xml(documents, filename),
+        }
+    }
+
+    /// Merges several JSONL files into one
+    ///
+    /// Concatenates the input files line by line, in order, keeping every
+    /// record. Use `merge_jsonl_with` to deduplicate by URL.
+    ///
+    /// # Arguments
+    ///
+    /// * `inputs` - Names of the JSONL files to merge (in the output directory)
+    /// * `output` - Name of the merged file; overwritten if it exists
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if merging succeeds
+    /// `Err` if an input can't be read or the output can't be written
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use spiderman::core::export::Exporter;
+    ///
+    /// let exporter = Exporter::new("output");
+    /// exporter.merge_jsonl(&["shard1.jsonl", "shard2.jsonl"], "crawl.jsonl").unwrap();
+    /// ```
+    pub fn merge_jsonl(&self, inputs: &[&str], output: &str) -> io::Result<()> {
+        self.merge_jsonl_with(inputs, output, DuplicateUrls::KeepAll)
+    }
+
+    /// Merges several JSONL files into one, deduplicating by URL
+    ///
+    /// Blank lines are skipped. Records are kept in input order; with
+    /// `KeepLatest` a record takes the place of its last occurrence.
+    ///
+    /// # Arguments
+    ///
+    /// * `inputs` - Names of the JSONL files to merge (in the output directory)
+    /// * `output` - Name of the merged file; overwritten if it exists
+    /// * `duplicates` - Which record to keep when URLs repeat
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if merging succeeds
+    /// `Err` if an input can't be read, a line isn't a JSON object with a
+    /// `url` (when deduplicating) or the output can't be written
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use spiderman::core::export::{DuplicateUrls, Exporter};
+    ///
+    /// let exporter = Exporter::new("output");
+    /// exporter
+    ///     .merge_jsonl_with(&["old.jsonl", "new.jsonl"], "crawl.jsonl", DuplicateUrls::KeepLatest)
+    ///     .unwrap();
+    /// ```
+    pub fn merge_jsonl_with(
+        &self,
+        inputs: &[&str],
+        output: &str,
+        duplicates: DuplicateUrls,
+    ) -> io::Result<()> {
+        let mut lines = Vec::new();
+        for input in inputs {
+            let content = fs::read_to_string(self.get_output_path(input))?;
+            lines.extend(
+                content
+                    .lines()
+                    .filter(|line| !line.trim().is_empty())
+                    .map(|line| line.to_string()),
+            );
+        }
+
+        let lines = match duplicates {
+            DuplicateUrls::KeepAll => lines,
+            DuplicateUrls::KeepFirst => dedup_by_url(lines)?,
+            DuplicateUrls::KeepLatest => {
+                lines.reverse();
+                let mut kept = dedup_by_url(lines)?;
+                kept.reverse();
+                kept
+            }
+        };
+
+        self.ensure_output_dir()?;
+        let mut merged = String::new();
+        for line in lines {
+            merged.push_str(&line);
+            merged.push('\n');
+        }
+        fs::write(self.get_output_path(output), merged)
+    }
+
+    /// Saves the raw bytes of a fetched resource (PDF, image, ...)
+    ///
+    /// The file is named after the slugified URL (see
+    /// `slugify_url_with_max_len`) and overwritten if it exists.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL the resource was fetched from
+    /// * `bytes` - The resource content
+    ///
+    /// # Returns
+    ///
+    /// `Ok(path)` with the path of the written file
+    /// `Err` if the directory or file can't be written
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use spiderman::core::export::Exporter;
+    ///
+    /// let exporter = Exporter::new("assets");
+    /// let path = exporter.save_asset("http://example.com/report.pdf", b"%PDF-1.7").unwrap();
+    /// ```
+    pub fn save_asset(&self, url: &str, bytes: &[u8]) -> io::Result<PathBuf> {
+        self.ensure_output_dir()?;
+
+        let path = self.get_output_path(&slugify_url_with_max_len(url, self.max_filename_len));
+        fs::write(&path, bytes)?;
+        Ok(path)
+    }
+
     /// Returns the output directory path
     ///
     /// # Examples
@@ -307,6 +883,138 @@ impl Exporter {
     }
 }
 
+/// Quotes a CSV field if it contains a separator, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Escapes text for use in XML, dropping characters XML can't represent
+fn xml_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if c < ' ' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Turns a URL into a file name
+///
+/// Drops the scheme and replaces every character other than ASCII letters,
+/// digits, `.` and `_` with `-`, collapsing runs of `-`. Names longer than
+/// `DEFAULT_MAX_FILENAME_LEN` are shortened (see
+/// `slugify_url_with_max_len`).
+///
+/// # Examples
+///
+/// ```
+/// use spiderman::core::export::slugify_url;
+///
+/// assert_eq!(
+///     slugify_url("https://example.com/files/Report 2024.pdf"),
+///     "example.com-files-Report-2024.pdf"
+/// );
+/// ```
+pub fn slugify_url(url: &str) -> String {
+    slugify_url_with_max_len(url, DEFAULT_MAX_FILENAME_LEN)
+}
+
+/// Turns a URL into a file name of at most `max_len` bytes
+///
+/// Works like `slugify_url`. A slug longer than `max_len` is cut short and
+/// followed by a hash of the full URL (and its short file extension, if
+/// any), so long URLs sharing a prefix still get distinct names.
+///
+/// # Arguments
+///
+/// * `url` - The URL to turn into a file name
+/// * `max_len` - Maximum length in bytes (at least 32 is always allowed)
+///
+/// # Examples
+///
+/// ```
+/// use spiderman::core::export::slugify_url_with_max_len;
+///
+/// let url = format!("http://example.com/{}.pdf", "a".repeat(300));
+/// let name = slugify_url_with_max_len(&url, 64);
+///
+/// assert_eq!(name.len(), 64);
+/// assert!(name.ends_with(".pdf"));
+/// ```
+pub fn slugify_url_with_max_len(url: &str, max_len: usize) -> String {
+    let slug = full_slug(url);
+    let max_len = max_len.max(32);
+    if slug.len() <= max_len {
+        return slug;
+    }
+
+    // Keep a short extension (e.g. `.pdf`) so the file type survives
+    let extension = slug
+        .rsplit_once('.')
+        .map(|(_, ext)| ext)
+        .filter(|ext| ext.len() <= 8 && ext.chars().all(|c| c.is_ascii_alphanumeric()))
+        .map(|ext| format!(".{}", ext))
+        .unwrap_or_default();
+
+    let suffix = format!("-{}{}", hash_content(url.as_bytes()), extension);
+    // The slug is ASCII, so any byte index is a char boundary
+    let prefix = slug[..max_len - suffix.len()].trim_end_matches('-');
+    format!("{}{}", prefix, suffix)
+}
+
+/// Turns a URL into an unshortened file name
+fn full_slug(url: &str) -> String {
+    let without_scheme = url.split_once("://").map_or(url, |(_, rest)| rest);
+
+    let mut slug = String::with_capacity(without_scheme.len());
+    for c in without_scheme.chars() {
+        if c.is_ascii_alphanumeric() || c == '.' || c == '_' {
+            slug.push(c);
+        } else if !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+
+    let slug = slug.trim_matches('-');
+    if slug.is_empty() {
+        "index".to_string()
+    } else {
+        slug.to_string()
+    }
+}
+
+/// Keeps the first JSONL line for each (normalized) URL
+fn dedup_by_url(lines: Vec<String>) -> io::Result<Vec<String>> {
+    let mut seen = HashSet::new();
+    let mut kept = Vec::new();
+
+    for line in lines {
+        let record: serde_json::Value = serde_json::from_str(&line)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        let url = record["url"].as_str().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidData, "JSONL record without url")
+        })?;
+
+        if seen.insert(normalize_url_for_storage(url)) {
+            kept.push(line);
+        }
+    }
+
+    Ok(kept)
+}
+
 /// Default exporter instance using "output" directory
 impl Default for Exporter {
     fn default() -> Self {
@@ -503,4 +1211,397 @@ mod tests {
 
         assert_eq!(lines.len(), 2);
     }
+
+    // ===== CSV / XML Tests =====
+
+    #[test]
+    fn test_export_csv() {
+        let temp_dir = TempDir::new().unwrap();
+        let exporter = Exporter::new(temp_dir.path());
+        let documents = vec![
+            create_test_document("http://example.com/1"),
+            Document::new(
+                "http://example.com/2",
+                "Line one, with \"quotes\"\nLine two".to_string(),
+                vec![],
+            )
+            .with_title("Plain".to_string()),
+        ];
+
+        exporter.export_csv(&documents, "crawl.csv").unwrap();
+
+        let content = fs::read_to_string(temp_dir.path().join("crawl.csv")).unwrap();
+        assert!(content.starts_with("url,title,description,status,crawled_at,link_count,content\n"));
+        assert!(content.contains(",\"Line one, with \"\"quotes\"\"\nLine two\"\n"));
+        assert!(content.contains("http://example.com/2,Plain,,"));
+    }
+
+    #[test]
+    fn test_export_xml_escapes_text() {
+        let temp_dir = TempDir::new().unwrap();
+        let exporter = Exporter::new(temp_dir.path());
+        let documents = vec![Document::new(
+            "http://example.com/?a=1&b=2",
+            "<b>bold</b> \u{1}".to_string(),
+            vec!["http://example.com/x".to_string()],
+        )];
+
+        exporter.export_xml(&documents, "crawl.xml").unwrap();
+
+        let content = fs::read_to_string(temp_dir.path().join("crawl.xml")).unwrap();
+        assert!(content.starts_with("<?xml"));
+        assert!(content.contains("<url>http://example.com/?a=1&amp;b=2</url>"));
+        assert!(content.contains("<content>&lt;b&gt;bold&lt;/b&gt; </content>"));
+        assert!(content.contains("<link>http://example.com/x</link>"));
+        assert_eq!(content.matches("<document>").count(), 1);
+        assert!(content.trim_end().ends_with("</documents>"));
+    }
+
+    // ===== Atomic Write Tests =====
+
+    /// Returns the names of the files in a directory
+    fn file_names(dir: &Path) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_write_once_exports_leave_no_temp_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let exporter = Exporter::new(temp_dir.path());
+        let documents = vec![
+            create_test_document("http://example.com/1"),
+            create_test_document("http://example.com/2"),
+        ];
+
+        exporter
+            .export_json_array(&documents, "crawl.json")
+            .unwrap();
+        exporter.export_csv(&documents, "crawl.csv").unwrap();
+        exporter.export_xml(&documents, "crawl.xml").unwrap();
+
+        assert_eq!(
+            file_names(temp_dir.path()),
+            ["crawl.csv", "crawl.json", "crawl.xml"]
+        );
+
+        let json = fs::read_to_string(temp_dir.path().join("crawl.json")).unwrap();
+        let parsed: Vec<Document> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.len(), 2);
+
+        let csv = fs::read_to_string(temp_dir.path().join("crawl.csv")).unwrap();
+        assert_eq!(csv.lines().count(), 3);
+
+        let xml = fs::read_to_string(temp_dir.path().join("crawl.xml")).unwrap();
+        assert_eq!(xml.matches("<document>").count(), 2);
+        assert!(xml.ends_with("</documents>\n"));
+    }
+
+    #[test]
+    fn test_write_once_export_replaces_existing_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let exporter = Exporter::new(temp_dir.path());
+        fs::write(
+            temp_dir.path().join("crawl.json"),
+            "stale contents, much longer than []",
+        )
+        .unwrap();
+
+        exporter.export_json_array(&[], "crawl.json").unwrap();
+
+        let json = fs::read_to_string(temp_dir.path().join("crawl.json")).unwrap();
+        assert_eq!(json, "[]");
+        assert_eq!(file_names(temp_dir.path()), ["crawl.json"]);
+    }
+
+    #[test]
+    fn test_write_atomically_failure_removes_temp_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let exporter = Exporter::new(temp_dir.path());
+        // A non-empty directory in the way of the rename makes it fail
+        fs::create_dir(temp_dir.path().join("blocked.csv")).unwrap();
+        fs::write(temp_dir.path().join("blocked.csv").join("inner"), "x").unwrap();
+
+        assert!(exporter.write_atomically("blocked.csv", b"new").is_err());
+        assert_eq!(file_names(temp_dir.path()), ["blocked.csv"]);
+    }
+
+    #[test]
+    fn test_output_format_streaming() {
+        assert!(OutputFormat::default().is_streaming());
+        assert!(!OutputFormat::JsonArray.is_streaming());
+        assert!(!OutputFormat::Csv.is_streaming());
+        assert!(!OutputFormat::Xml.is_streaming());
+    }
+
+    // ===== Projection Tests =====
+
+    #[test]
+    fn test_export_projection_url_and_title() {
+        let temp_dir = TempDir::new().unwrap();
+        let exporter = Exporter::new(temp_dir.path());
+        let documents = vec![
+            create_test_document("http://example.com/1")
+                .with_metadata("author", "Jane")
+                .with_description(Some("Private summary".to_string())),
+            create_test_document("http://example.com/2"),
+        ];
+
+        exporter
+            .export_projection(
+                &documents,
+                &[DocField::Url, DocField::Title],
+                "titles.jsonl",
+            )
+            .unwrap();
+
+        let content = fs::read_to_string(temp_dir.path().join("titles.jsonl")).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines.len(), 2);
+        for (line, doc) in lines.iter().zip(&documents) {
+            let value: serde_json::Value = serde_json::from_str(line).unwrap();
+            let object = value.as_object().unwrap();
+
+            let mut keys: Vec<&str> = object.keys().map(|k| k.as_str()).collect();
+            keys.sort_unstable();
+            assert_eq!(keys, ["title", "url"]);
+            assert_eq!(object["url"], doc.url());
+            assert_eq!(object["title"], doc.title());
+        }
+    }
+
+    #[test]
+    fn test_export_projection_skips_absent_fields() {
+        let temp_dir = TempDir::new().unwrap();
+        let exporter = Exporter::new(temp_dir.path());
+        let documents = vec![create_test_document("http://example.com/1")];
+
+        exporter
+            .export_projection(
+                &documents,
+                &[DocField::Url, DocField::Description, DocField::Links],
+                "out.jsonl",
+            )
+            .unwrap();
+
+        let content = fs::read_to_string(temp_dir.path().join("out.jsonl")).unwrap();
+        let value: serde_json::Value = serde_json::from_str(content.trim()).unwrap();
+        let object = value.as_object().unwrap();
+        assert!(object.contains_key("url"));
+        assert!(object.contains_key("links"));
+        assert!(!object.contains_key("description"));
+        assert_eq!(object.len(), 2);
+    }
+
+    #[test]
+    fn test_export_projection_is_gated() {
+        let temp_dir = TempDir::new().unwrap();
+        let exporter = Exporter::new(temp_dir.path());
+        let documents = vec![create_test_document("http://example.com/1").with_gated(true)];
+
+        exporter
+            .export_projection(
+                &documents,
+                &[DocField::Url, DocField::IsGated],
+                "gated.jsonl",
+            )
+            .unwrap();
+
+        let content = fs::read_to_string(temp_dir.path().join("gated.jsonl")).unwrap();
+        let value: serde_json::Value = serde_json::from_str(content.trim()).unwrap();
+        assert_eq!(value["is_gated"], true);
+    }
+
+    #[test]
+    fn test_export_projection_assets() {
+        let temp_dir = TempDir::new().unwrap();
+        let exporter = Exporter::new(temp_dir.path());
+        let assets = crate::core::link_extractor::AssetSet {
+            scripts: vec!["http://example.com/app.js".to_string()],
+            ..Default::default()
+        };
+        let documents = vec![create_test_document("http://example.com/1").with_assets(assets)];
+
+        exporter
+            .export_projection(&documents, &[DocField::Assets], "assets.jsonl")
+            .unwrap();
+
+        let content = fs::read_to_string(temp_dir.path().join("assets.jsonl")).unwrap();
+        let value: serde_json::Value = serde_json::from_str(content.trim()).unwrap();
+        assert_eq!(value["assets"]["scripts"][0], "http://example.com/app.js");
+    }
+
+    // ===== Asset Tests =====
+
+    #[test]
+    fn test_slugify_url() {
+        assert_eq!(
+            slugify_url("http://example.com/a/b?x=1&y=2"),
+            "example.com-a-b-x-1-y-2"
+        );
+        assert_eq!(slugify_url("http://example.com/"), "example.com");
+        assert_eq!(
+            slugify_url("http://127.0.0.1:8080/doc.pdf"),
+            "127.0.0.1-8080-doc.pdf"
+        );
+        assert_eq!(slugify_url("://"), "index");
+    }
+
+    #[test]
+    fn test_slugify_long_url_is_capped() {
+        let url = format!("http://example.com/{}", "segment/".repeat(100));
+        let name = slugify_url(&url);
+
+        assert!(name.len() <= DEFAULT_MAX_FILENAME_LEN);
+        assert!(name.starts_with("example.com-segment-"));
+        assert!(name.ends_with(&hash_content(url.as_bytes())));
+
+        // Short URLs are unchanged
+        assert_eq!(
+            slugify_url_with_max_len("http://example.com/a", 32),
+            "example.com-a"
+        );
+    }
+
+    #[test]
+    fn test_slugify_long_urls_sharing_prefix_are_distinct() {
+        let prefix = format!("http://example.com/{}", "x".repeat(300));
+        let first = slugify_url_with_max_len(&format!("{}/one.pdf", prefix), 100);
+        let second = slugify_url_with_max_len(&format!("{}/two.pdf", prefix), 100);
+
+        assert_ne!(first, second);
+        assert!(first.len() <= 100 && second.len() <= 100);
+        assert!(first.ends_with(".pdf") && second.ends_with(".pdf"));
+    }
+
+    #[test]
+    fn test_save_asset_caps_filename() {
+        let temp_dir = TempDir::new().unwrap();
+        let exporter = Exporter::new(temp_dir.path()).with_max_filename_len(50);
+        let url = format!("http://example.com/{}.png", "long-name-".repeat(40));
+
+        let path = exporter.save_asset(&url, b"png").unwrap();
+
+        let name = path.file_name().unwrap().to_str().unwrap();
+        assert!(name.len() <= 50);
+        assert!(name.ends_with(".png"));
+        assert_eq!(fs::read(&path).unwrap(), b"png");
+    }
+
+    #[test]
+    fn test_save_asset() {
+        let temp_dir = TempDir::new().unwrap();
+        let exporter = Exporter::new(temp_dir.path().join("assets"));
+        let bytes = [0x25, 0x50, 0x44, 0x46, 0x00, 0xff, 0x10];
+
+        let path = exporter
+            .save_asset("http://example.com/files/doc.pdf", &bytes)
+            .unwrap();
+
+        assert_eq!(
+            path,
+            temp_dir.path().join("assets/example.com-files-doc.pdf")
+        );
+        assert_eq!(fs::read(path).unwrap(), bytes);
+    }
+
+    // ===== Merge Tests =====
+
+    /// Writes two shards sharing `http://example.com/shared`
+    fn write_shards(exporter: &Exporter) {
+        let shared_old =
+            create_test_document("http://example.com/shared").with_title("Old".to_string());
+        let shared_new =
+            create_test_document("http://example.com/shared/").with_title("New".to_string());
+
+        exporter
+            .export_batch(
+                &[create_test_document("http://example.com/a"), shared_old],
+                "shard1.jsonl",
+            )
+            .unwrap();
+        exporter
+            .export_batch(
+                &[shared_new, create_test_document("http://example.com/b")],
+                "shard2.jsonl",
+            )
+            .unwrap();
+    }
+
+    fn read_merged(temp_dir: &TempDir) -> Vec<Document> {
+        let content = fs::read_to_string(temp_dir.path().join("merged.jsonl")).unwrap();
+        content
+            .lines()
+            .map(|line| Document::from_json(line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_merge_jsonl_keeps_all() {
+        let temp_dir = TempDir::new().unwrap();
+        let exporter = Exporter::new(temp_dir.path());
+        write_shards(&exporter);
+
+        exporter
+            .merge_jsonl(&["shard1.jsonl", "shard2.jsonl"], "merged.jsonl")
+            .unwrap();
+
+        assert_eq!(read_merged(&temp_dir).len(), 4);
+    }
+
+    #[test]
+    fn test_merge_jsonl_keep_first() {
+        let temp_dir = TempDir::new().unwrap();
+        let exporter = Exporter::new(temp_dir.path());
+        write_shards(&exporter);
+
+        exporter
+            .merge_jsonl_with(
+                &["shard1.jsonl", "shard2.jsonl"],
+                "merged.jsonl",
+                DuplicateUrls::KeepFirst,
+            )
+            .unwrap();
+
+        let merged = read_merged(&temp_dir);
+        let titles: Vec<&str> = merged.iter().map(|d| d.title()).collect();
+        assert_eq!(titles, vec!["Test Title", "Old", "Test Title"]);
+    }
+
+    #[test]
+    fn test_merge_jsonl_keep_latest() {
+        let temp_dir = TempDir::new().unwrap();
+        let exporter = Exporter::new(temp_dir.path());
+        write_shards(&exporter);
+
+        exporter
+            .merge_jsonl_with(
+                &["shard1.jsonl", "shard2.jsonl"],
+                "merged.jsonl",
+                DuplicateUrls::KeepLatest,
+            )
+            .unwrap();
+
+        let merged = read_merged(&temp_dir);
+        let shared: Vec<&Document> = merged
+            .iter()
+            .filter(|d| d.url().contains("shared"))
+            .collect();
+        assert_eq!(merged.len(), 3);
+        assert_eq!(shared.len(), 1);
+        assert_eq!(shared[0].title(), "New");
+    }
+
+    #[test]
+    fn test_merge_jsonl_missing_input() {
+        let temp_dir = TempDir::new().unwrap();
+        let exporter = Exporter::new(temp_dir.path());
+
+        let result = exporter.merge_jsonl(&["missing.jsonl"], "merged.jsonl");
+        assert!(result.is_err());
+    }
 }
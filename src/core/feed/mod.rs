@@ -0,0 +1,208 @@
+//! Feed Module
+//!
+//! This module parses RSS and Atom feeds into the article URLs they list,
+//! so blogs can be discovered through the feeds their pages declare with
+//! `<link rel="alternate" type="application/rss+xml">`.
+//!
+//! # Feed Formats
+//!
+//! ```text
+//! RSS:                                Atom:
+//! <rss><channel>                      <feed>
+//!   <item>                              <entry>
+//!     <link>http://.../post</link>        <link href="http://.../post"/>
+//!   </item>                             </entry>
+//! </channel></rss>                    </feed>
+//! ```
+//!
+//! Channel- and feed-level links (the blog's home page) are not articles
+//! and are left out.
+//!
+//! # Examples
+//!
+//! ```
+//! use spiderman::core::feed::parse_feed;
+//!
+//! let rss = r#"<rss><channel><item><link>http://example.com/post</link></item></channel></rss>"#;
+//! assert_eq!(parse_feed(rss), vec!["http://example.com/post".to_string()]);
+//! ```
+
+use super::document::decode_html_entities;
+
+/// Media types of the feeds a page can declare
+pub const FEED_TYPES: [&str; 2] = ["application/rss+xml", "application/atom+xml"];
+
+/// Parses an RSS or Atom feed into its article URLs
+///
+/// RSS items use their `<link>`, or a permalink `<guid>` when the link is
+/// missing. Atom entries use their `rel="alternate"` link (or the first
+/// link without a `rel`).
+///
+/// # Arguments
+///
+/// * `xml` - The feed document
+///
+/// # Returns
+///
+/// The article URLs in feed order, deduplicated (possibly relative, as
+/// written in the feed)
+///
+/// # Examples
+///
+/// ```
+/// use spiderman::core::feed::parse_feed;
+///
+/// let atom = r#"<feed><entry><link rel="alternate" href="/posts/1"/></entry></feed>"#;
+/// assert_eq!(parse_feed(atom), vec!["/posts/1".to_string()]);
+/// ```
+pub fn parse_feed(xml: &str) -> Vec<String> {
+    let item_re =
+        regex::Regex::new(r"(?is)<(item|entry)(?:\s[^>]*)?>(.*?)</(?:item|entry)>").unwrap();
+
+    let mut urls: Vec<String> = Vec::new();
+    for cap in item_re.captures_iter(xml) {
+        let body = &cap[2];
+        let url = if cap[1].eq_ignore_ascii_case("entry") {
+            atom_entry_link(body)
+        } else {
+            rss_item_link(body)
+        };
+
+        if let Some(url) = url.filter(|url| !url.is_empty()) {
+            if !urls.contains(&url) {
+                urls.push(url);
+            }
+        }
+    }
+
+    urls
+}
+
+/// Returns the link of an RSS `<item>`, falling back to a permalink `<guid>`
+fn rss_item_link(item: &str) -> Option<String> {
+    let link_re = regex::Regex::new(r"(?is)<link>(.*?)</link>").unwrap();
+    let guid_re = regex::Regex::new(r"(?is)<guid(\s[^>]*)?>(.*?)</guid>").unwrap();
+
+    if let Some(cap) = link_re.captures(item) {
+        return Some(element_text(&cap[1]));
+    }
+
+    let cap = guid_re.captures(item)?;
+    let not_permalink = cap.get(1).is_some_and(|attrs| {
+        attrs
+            .as_str()
+            .to_lowercase()
+            .contains("ispermalink=\"false\"")
+    });
+    if not_permalink {
+        return None;
+    }
+    Some(element_text(&cap[2]))
+}
+
+/// Returns the alternate link of an Atom `<entry>`
+fn atom_entry_link(entry: &str) -> Option<String> {
+    let link_re = regex::Regex::new(r"(?is)<link\s([^>]*)>").unwrap();
+    let rel_re = regex::Regex::new(r#"(?i)\brel\s*=\s*["']([^"']*)["']"#).unwrap();
+    let href_re = regex::Regex::new(r#"(?i)\bhref\s*=\s*["']([^"']*)["']"#).unwrap();
+
+    let link = link_re.captures_iter(entry).find_map(|cap| {
+        let attrs = cap.get(1)?.as_str();
+        // A link without `rel` is the alternate one
+        let alternate = rel_re
+            .captures(attrs)
+            .is_none_or(|rel| rel[1].eq_ignore_ascii_case("alternate"));
+        if alternate {
+            href_re
+                .captures(attrs)
+                .map(|href| decode_html_entities(href[1].trim()))
+        } else {
+            None
+        }
+    });
+    link
+}
+
+/// Returns the trimmed text of an element, unwrapping CDATA and entities
+fn element_text(text: &str) -> String {
+    let text = text.trim();
+    match text
+        .strip_prefix("<![CDATA[")
+        .and_then(|rest| rest.strip_suffix("]]>"))
+    {
+        Some(cdata) => cdata.trim().to_string(),
+        None => decode_html_entities(text),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ===== RSS Tests =====
+
+    #[test]
+    fn test_parse_rss_feed() {
+        let rss = r#"<?xml version="1.0"?>
+            <rss version="2.0">
+              <channel>
+                <title>Blog</title>
+                <link>http://example.com/</link>
+                <item>
+                  <title>First</title>
+                  <link>http://example.com/posts/1?a=1&amp;b=2</link>
+                </item>
+                <item>
+                  <link><![CDATA[ http://example.com/posts/2 ]]></link>
+                </item>
+                <item>
+                  <guid isPermaLink="true">http://example.com/posts/3</guid>
+                </item>
+                <item>
+                  <guid isPermaLink="false">tag:example.com,2024:4</guid>
+                </item>
+                <item><link>http://example.com/posts/1?a=1&amp;b=2</link></item>
+              </channel>
+            </rss>"#;
+
+        assert_eq!(
+            parse_feed(rss),
+            vec![
+                "http://example.com/posts/1?a=1&b=2".to_string(),
+                "http://example.com/posts/2".to_string(),
+                "http://example.com/posts/3".to_string(),
+            ]
+        );
+    }
+
+    // ===== Atom Tests =====
+
+    #[test]
+    fn test_parse_atom_feed() {
+        let atom = r#"<feed xmlns="http://www.w3.org/2005/Atom">
+              <link href="http://example.com/"/>
+              <link rel="self" href="http://example.com/feed.atom"/>
+              <entry>
+                <link rel="edit" href="http://example.com/edit/1"/>
+                <link rel="alternate" type="text/html" href="http://example.com/posts/1"/>
+              </entry>
+              <entry>
+                <link href="/posts/2"/>
+              </entry>
+            </feed>"#;
+
+        assert_eq!(
+            parse_feed(atom),
+            vec![
+                "http://example.com/posts/1".to_string(),
+                "/posts/2".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_feed_not_a_feed() {
+        assert!(parse_feed("<html><body><p>Hello</p></body></html>").is_empty());
+        assert!(parse_feed("").is_empty());
+    }
+}
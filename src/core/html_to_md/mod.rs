@@ -1,3 +1,13 @@
+use super::document::decode_html_entities;
+use regex::Regex;
+use std::borrow::Cow;
+
+/// Name and version of the HTML to Markdown converter
+pub const CONVERTER: &str = "html2text/0.12";
+
+/// Elements treated as page boilerplate by `strip_boilerplate`
+const BOILERPLATE_TAGS: [&str; 4] = ["nav", "header", "footer", "aside"];
+
 /// Converts HTML content to Markdown format
 ///
 /// This function takes raw HTML content and converts it to clean Markdown text,
@@ -30,6 +40,203 @@ pub(crate) fn parser(html: String) -> String {
     clean_markdown(markdown)
 }
 
+/// Removes page boilerplate from HTML before conversion
+///
+/// Drops `<nav>`, `<header>`, `<footer>` and `<aside>` elements along with
+/// their content, leaving the main page content.
+///
+/// # Arguments
+///
+/// * `html` - The HTML content
+///
+/// # Returns
+///
+/// * The HTML without boilerplate elements
+///
+/// # Example
+///
+/// ```
+/// let html = "<nav>Menu</nav><p>Article</p>";
+/// assert_eq!(strip_boilerplate(html), "<p>Article</p>");
+/// ```
+pub fn strip_boilerplate(html: &str) -> String {
+    let mut stripped = html.to_string();
+
+    for tag in BOILERPLATE_TAGS {
+        let pattern = format!(r"(?is)<{tag}(?:\s[^>]*)?>.*?</{tag}\s*>", tag = tag);
+        let re = Regex::new(&pattern).unwrap();
+        stripped = re.replace_all(&stripped, "").to_string();
+    }
+
+    stripped
+}
+
+/// Handles `<noscript>` elements before conversion
+///
+/// The converter treats `<noscript>` content as raw text, which leaks its
+/// markup into the Markdown. With `include` the tags are unwrapped so their
+/// content converts like the rest of the page (pages often put the real
+/// content there for clients without JavaScript); otherwise the elements
+/// are dropped along with their content.
+///
+/// # Arguments
+///
+/// * `html` - The HTML content
+/// * `include` - Whether to keep the content of `<noscript>` elements
+///
+/// # Returns
+///
+/// * The HTML without `<noscript>` tags (borrowed if it had none)
+///
+/// # Example
+///
+/// ```
+/// let html = "<p>Intro</p><noscript><p>Body</p></noscript>";
+/// assert_eq!(resolve_noscript(html, true), "<p>Intro</p><p>Body</p>");
+/// assert_eq!(resolve_noscript(html, false), "<p>Intro</p>");
+/// ```
+pub fn resolve_noscript(html: &str, include: bool) -> Cow<'_, str> {
+    // Most pages have no <noscript>; skip compiling the patterns for them
+    let has_noscript = html
+        .as_bytes()
+        .windows(b"<noscript".len())
+        .any(|window| window.eq_ignore_ascii_case(b"<noscript"));
+    if !has_noscript {
+        return Cow::Borrowed(html);
+    }
+
+    if include {
+        let tag = Regex::new(r"(?i)</?noscript\b[^>]*>").unwrap();
+        tag.replace_all(html, "")
+    } else {
+        let element = Regex::new(r"(?is)<noscript\b[^>]*>.*?</noscript\s*>").unwrap();
+        element.replace_all(html, "")
+    }
+}
+
+/// Removes active content from HTML
+///
+/// Strips `<script>` elements, inline event handler attributes (`onclick`,
+/// `onload`, ...) and `javascript:` URLs in `href`, `src`, `action` and
+/// `formaction` attributes. Everything else is left as-is.
+///
+/// # Arguments
+///
+/// * `html` - The HTML content
+///
+/// # Returns
+///
+/// * The sanitized HTML
+///
+/// # Example
+///
+/// ```
+/// let html = r#"<p onclick="steal()">Hi</p><script>alert(1)</script>"#;
+/// assert_eq!(sanitize_html(html), "<p>Hi</p>");
+/// ```
+pub fn sanitize_html(html: &str) -> String {
+    let script = Regex::new(r"(?is)<script\b[^>]*>.*?</script\s*>|<script\b[^>]*/>").unwrap();
+    let tag = Regex::new(r"<[a-zA-Z][^>]*>").unwrap();
+    let attribute =
+        Regex::new(r#"(?i)\s+([a-z][a-z0-9_:-]*)\s*=\s*("[^"]*"|'[^']*'|[^\s>"']+)"#).unwrap();
+
+    let without_scripts = script.replace_all(html, "");
+
+    tag.replace_all(&without_scripts, |tag_caps: &regex::Captures| {
+        attribute
+            .replace_all(&tag_caps[0], |attr: &regex::Captures| {
+                let name = attr[1].to_lowercase();
+                let value = attr[2].trim_matches(|c| c == '"' || c == '\'');
+
+                let is_handler = name.starts_with("on");
+                let is_script_url =
+                    matches!(name.as_str(), "href" | "src" | "action" | "formaction")
+                        && value.trim_start().to_lowercase().starts_with("javascript:");
+
+                if is_handler || is_script_url {
+                    String::new()
+                } else {
+                    attr[0].to_string()
+                }
+            })
+            .to_string()
+    })
+    .to_string()
+}
+
+/// Truncates HTML to at most `max_bytes`, cutting at a tag boundary
+///
+/// If the cut would split a tag, it is moved to just before that tag's
+/// `<`; otherwise the HTML is cut at the last character boundary that fits.
+///
+/// # Arguments
+///
+/// * `html` - The HTML content
+/// * `max_bytes` - Maximum length of the result in bytes
+///
+/// # Returns
+///
+/// * The (possibly shortened) HTML
+///
+/// # Example
+///
+/// ```
+/// let html = "<p>One</p><p>Two</p>";
+/// assert_eq!(truncate_html(html, 12), "<p>One</p>");
+/// ```
+pub fn truncate_html(html: &str, max_bytes: usize) -> &str {
+    if html.len() <= max_bytes {
+        return html;
+    }
+
+    let mut end = max_bytes;
+    while !html.is_char_boundary(end) {
+        end -= 1;
+    }
+
+    // Drop a tag left open by the cut
+    let prefix = &html[..end];
+    match (prefix.rfind('<'), prefix.rfind('>')) {
+        (Some(open), close) if close.is_none_or(|close| close < open) => &html[..open],
+        _ => prefix,
+    }
+}
+
+/// Returns the text a reader sees on an HTML page
+///
+/// Drops comments, `<script>` and `<style>` elements and all tags, decodes
+/// entities and collapses whitespace (including `&nbsp;`) to single
+/// spaces. Cheaper than a full
+/// Markdown conversion, for matching phrases against a page.
+///
+/// # Arguments
+///
+/// * `html` - The HTML content
+///
+/// # Returns
+///
+/// * The page text on a single line
+///
+/// # Example
+///
+/// ```
+/// let html = "<p>Sign in\n  to <b>continue</b></p><script>var x;</script>";
+/// assert_eq!(visible_text(html), "Sign in to continue");
+/// ```
+pub fn visible_text(html: &str) -> String {
+    let hidden =
+        Regex::new(r"(?is)<!--.*?-->|<(script|style)\b[^>]*>.*?</(script|style)\s*>").unwrap();
+    let tag = Regex::new(r"<[^>]*>").unwrap();
+
+    let without_hidden = hidden.replace_all(html, " ");
+    let text = tag
+        .replace_all(&without_hidden, " ")
+        .replace("&nbsp;", " ")
+        .replace("&#160;", " ");
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    decode_html_entities(&text)
+}
+
 /// Cleans up the generated markdown by removing excessive whitespace
 /// and normalizing formatting
 fn clean_markdown(markdown: String) -> String {
@@ -91,6 +298,114 @@ mod tests {
         assert_eq!(result, "");
     }
 
+    // ===== Boilerplate Tests =====
+
+    #[test]
+    fn test_strip_boilerplate() {
+        let html = r#"<header class="top">Site</header><nav>Home | About</nav>
+            <main><p>Article body</p></main>
+            <aside>Related</aside><FOOTER>Copyright</FOOTER>"#;
+        let result = parser(strip_boilerplate(html));
+
+        assert!(result.contains("Article body"));
+        assert!(!result.contains("Site"));
+        assert!(!result.contains("Home | About"));
+        assert!(!result.contains("Related"));
+        assert!(!result.contains("Copyright"));
+    }
+
+    #[test]
+    fn test_strip_boilerplate_keeps_similar_tags() {
+        let html = "<headline>Kept</headline><p>Body</p>";
+        assert_eq!(strip_boilerplate(html), html);
+    }
+
+    // ===== Noscript Tests =====
+
+    #[test]
+    fn test_resolve_noscript() {
+        let html =
+            r#"<p>Intro</p><NOSCRIPT class="fallback"><p>Real <b>content</b></p></noscript>"#;
+
+        let included = parser(resolve_noscript(html, true).into_owned());
+        assert!(included.contains("Real"));
+        assert!(included.contains("content"));
+        assert!(!included.contains("<p>"));
+
+        let excluded = parser(resolve_noscript(html, false).into_owned());
+        assert!(excluded.contains("Intro"));
+        assert!(!excluded.contains("Real"));
+    }
+
+    #[test]
+    fn test_resolve_noscript_borrows_without_noscript() {
+        let html = "<p>No fallback here</p>";
+
+        assert!(matches!(resolve_noscript(html, true), Cow::Borrowed(_)));
+        assert!(matches!(resolve_noscript(html, false), Cow::Borrowed(_)));
+    }
+
+    // ===== Truncation Tests =====
+
+    #[test]
+    fn test_truncate_html_at_tag_boundary() {
+        let html = "<p>One</p><p>Two</p><p>Three</p>";
+
+        assert_eq!(truncate_html(html, 100), html);
+        assert_eq!(truncate_html(html, 12), "<p>One</p>");
+        assert_eq!(truncate_html(html, 20), "<p>One</p><p>Two</p>");
+    }
+
+    #[test]
+    fn test_truncate_html_without_tags() {
+        assert_eq!(truncate_html("héllo world", 3), "hé");
+        assert_eq!(truncate_html("<p>héllo world", 6), "<p>hé");
+    }
+
+    // ===== Visible Text Tests =====
+
+    #[test]
+    fn test_visible_text() {
+        let html = r#"<html><head><style>p { color: red }</style></head>
+            <body><!-- Sign in to continue -->
+            <p>Please <a href="/login">sign&nbsp;in</a>
+            to continue</p><script>alert("hidden")</script></body></html>"#;
+
+        assert_eq!(visible_text(html), "Please sign in to continue");
+        assert_eq!(visible_text("plain"), "plain");
+    }
+
+    // ===== Sanitization Tests =====
+
+    #[test]
+    fn test_sanitize_html_removes_scripts() {
+        let html = r#"<p>Keep</p><SCRIPT type="text/javascript">
+            document.cookie
+        </script><script src="/app.js"/><p>Also kept</p>"#;
+        assert_eq!(sanitize_html(html), "<p>Keep</p><p>Also kept</p>");
+    }
+
+    #[test]
+    fn test_sanitize_html_removes_event_handlers() {
+        let html = r#"<button class="btn" onclick="steal()" ONMOUSEOVER='x()' id=b1>Go</button>"#;
+        assert_eq!(
+            sanitize_html(html),
+            r#"<button class="btn" id=b1>Go</button>"#
+        );
+    }
+
+    #[test]
+    fn test_sanitize_html_removes_javascript_urls() {
+        let html = r#"<a href=" JavaScript:alert(1)">Bad</a><a href="/ok">Good</a>"#;
+        assert_eq!(sanitize_html(html), r#"<a>Bad</a><a href="/ok">Good</a>"#);
+    }
+
+    #[test]
+    fn test_sanitize_html_leaves_text_alone() {
+        let html = "<p>Turn onclick = on and read javascript: the good parts</p>";
+        assert_eq!(sanitize_html(html), html);
+    }
+
     #[test]
     fn test_clean_markdown_removes_excessive_blank_lines() {
         let markdown = String::from("Line 1\n\n\n\n\nLine 2");
@@ -9,7 +9,15 @@
 //! 1. **Extraction**: Finds all `<a href="...">` tags in HTML
 //! 2. **Normalization**: Converts relative URLs to absolute URLs
 //! 3. **Filtering**: Removes invalid URLs (anchors, javascript:, mailto:, etc.)
-//! 4. **Deduplication**: Returns unique URLs only
+//! 4. **Deduplication**: Returns unique URLs only, in document order. Two
+//!    spellings of a URL count as the same link when they share a storage
+//!    key (`normalize_url_for_storage`), just as in the `UrlManager` queue.
+//!
+//! With `LinkOptions::with_forms`, the `action` targets of GET forms
+//! (`<form method="get" action="...">`) are collected as well.
+//!
+//! `extract_assets` lists a page's subresources (scripts, stylesheets and
+//! images) instead, for an asset inventory; they are never crawled.
 //!
 //! # How It Works
 //!
@@ -63,7 +71,10 @@
 //! // Result: ["http://example.com/about", "https://external.com"]
 //! ```
 
-use std::collections::HashSet;
+use super::document::decode_html_entities;
+use super::url_manager::normalize_url_for_storage;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 
 /// Extracts all valid links from HTML content and normalizes them to absolute URLs
 ///
@@ -77,7 +88,8 @@ use std::collections::HashSet;
 ///
 /// # Returns
 ///
-/// A `Vec<String>` containing unique, normalized absolute URLs
+/// A `Vec<String>` containing unique, normalized absolute URLs in the order
+/// they first appear in the HTML
 ///
 /// # Process Flow
 ///
@@ -85,7 +97,7 @@ use std::collections::HashSet;
 /// 2. Extract the href attribute value from each tag
 /// 3. Filter out invalid URLs (anchors, javascript:, mailto:, etc.)
 /// 4. Normalize relative URLs to absolute URLs using the base URL
-/// 5. Deduplicate URLs using a HashSet
+/// 5. Deduplicate URLs by their storage key, keeping the first occurrence
 /// 6. Return the final list of unique URLs
 ///
 /// # Examples
@@ -100,30 +112,278 @@ use std::collections::HashSet;
 /// assert!(links.contains(&"http://example.com/page1".to_string()));
 /// ```
 pub fn extract_links(html: &str, base_url: &str) -> Vec<String> {
-    let mut unique_links = HashSet::new();
+    extract_links_with_options(html, base_url, &LinkOptions::default())
+}
+
+/// Options controlling which elements `extract_links_with_options` collects
+/// links from
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LinkOptions {
+    /// Also collect `action` targets of GET forms
+    pub include_forms: bool,
+}
+
+impl LinkOptions {
+    /// Creates options that only collect `<a href>` links
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether GET form `action` targets are collected (builder pattern)
+    ///
+    /// Forms without a `method` attribute count as GET; POST forms are
+    /// always skipped.
+    pub fn with_forms(mut self, include: bool) -> Self {
+        self.include_forms = include;
+        self
+    }
+}
+
+/// Extracts links from HTML content using the given options
+///
+/// Works like `extract_links`, optionally also collecting the `action` of
+/// `<form method="get">` elements. Links are returned in document order,
+/// whichever element they come from.
+///
+/// # Arguments
+///
+/// * `html` - The HTML content to extract links from
+/// * `base_url` - The base URL used to resolve relative links
+/// * `options` - Which elements to collect links from
+///
+/// # Returns
+///
+/// A `Vec<String>` containing unique, normalized absolute URLs
+///
+/// # Examples
+///
+/// ```
+/// use spiderman::core::link_extractor::{extract_links_with_options, LinkOptions};
+///
+/// let html = r#"<form method="get" action="/search"><input name="q"></form>"#;
+/// let options = LinkOptions::new().with_forms(true);
+///
+/// assert_eq!(
+///     extract_links_with_options(html, "http://example.com", &options),
+///     vec!["http://example.com/search".to_string()]
+/// );
+/// ```
+pub fn extract_links_with_options(
+    html: &str,
+    base_url: &str,
+    options: &LinkOptions,
+) -> Vec<String> {
+    let mut seen = HashSet::new();
+    let mut links = Vec::new();
 
     // Find all <a> tags with href attributes using regex
     // Pattern matches: <a ...href="..." ...> or <a ...href='...' ...>
     let re = regex::Regex::new(r#"<a\s+[^>]*href\s*=\s*["']([^"']+)["'][^>]*>"#).unwrap();
 
-    for cap in re.captures_iter(html) {
-        if let Some(href) = cap.get(1) {
-            let url = href.as_str();
+    // Candidate (position, url) pairs, so anchors and forms stay in
+    // document order
+    let mut candidates: Vec<(usize, &str)> = re
+        .captures_iter(html)
+        .filter_map(|cap| cap.get(1))
+        .map(|href| (href.start(), href.as_str()))
+        .collect();
+
+    if options.include_forms {
+        candidates.extend(form_actions(html));
+        candidates.sort_by_key(|(pos, _)| *pos);
+    }
+
+    for (_, url) in candidates {
+        // Filter out invalid URLs
+        if !is_valid_url(url) {
+            continue;
+        }
+
+        // Normalize the URL to absolute, deduplicating with the key the
+        // URL manager uses so no link is queued twice
+        if let Some(absolute_url) = normalize_url(url, base_url) {
+            if seen.insert(normalize_url_for_storage(&absolute_url)) {
+                links.push(absolute_url);
+            }
+        }
+    }
+
+    links
+}
+
+/// Extracts the anchor text of every link
+///
+/// The text is taken from between `<a>` and `</a>`, with nested tags
+/// removed, entities decoded and whitespace collapsed. When a URL is linked
+/// more than once, the first non-empty text wins.
+///
+/// # Arguments
+///
+/// * `html` - The HTML content to extract anchors from
+/// * `base_url` - The base URL used to resolve relative links
+///
+/// # Returns
+///
+/// A map from each link's storage key (see `normalize_url_for_storage`) to
+/// its anchor text
+///
+/// # Examples
+///
+/// ```
+/// use spiderman::core::link_extractor::extract_anchor_texts;
+///
+/// let html = r#"<a href="/post"><b>Read</b> more</a>"#;
+/// let anchors = extract_anchor_texts(html, "http://example.com");
+///
+/// assert_eq!(anchors["http://example.com/post"], "Read more");
+/// ```
+pub fn extract_anchor_texts(html: &str, base_url: &str) -> HashMap<String, String> {
+    let anchor_re =
+        regex::Regex::new(r#"(?is)<a\s+[^>]*href\s*=\s*["']([^"']+)["'][^>]*>(.*?)</a>"#).unwrap();
+    let tag_re = regex::Regex::new(r"<[^>]*>").unwrap();
+
+    let mut anchors = HashMap::new();
+    for cap in anchor_re.captures_iter(html) {
+        let (Some(href), Some(inner)) = (cap.get(1), cap.get(2)) else {
+            continue;
+        };
+        if !is_valid_url(href.as_str()) {
+            continue;
+        }
+
+        let text = tag_re.replace_all(inner.as_str(), " ");
+        let text = decode_html_entities(&text.split_whitespace().collect::<Vec<_>>().join(" "));
+        if text.is_empty() {
+            continue;
+        }
 
-            // Filter out invalid URLs
-            if !is_valid_url(url) {
-                continue;
+        if let Some(url) = normalize_url(href.as_str(), base_url) {
+            anchors
+                .entry(normalize_url_for_storage(&url))
+                .or_insert(text);
+        }
+    }
+
+    anchors
+}
+
+/// Subresource URLs referenced by a page
+///
+/// # Fields
+///
+/// * `scripts` - `<script src>` URLs
+/// * `styles` - `<link rel="stylesheet" href>` URLs
+/// * `images` - `<img src>` URLs
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AssetSet {
+    /// External scripts
+    pub scripts: Vec<String>,
+
+    /// Stylesheets
+    pub styles: Vec<String>,
+
+    /// Images
+    pub images: Vec<String>,
+}
+
+impl AssetSet {
+    /// Returns true if the page references no assets
+    pub fn is_empty(&self) -> bool {
+        self.scripts.is_empty() && self.styles.is_empty() && self.images.is_empty()
+    }
+}
+
+/// Extracts the scripts, stylesheets and images a page references
+///
+/// Only URLs are collected; nothing is fetched. Each list is resolved
+/// against `base_url`, deduplicated by storage key and kept in document
+/// order. Inline scripts and `data:` images have no URL and are skipped.
+///
+/// # Arguments
+///
+/// * `html` - The HTML content to extract assets from
+/// * `base_url` - The base URL used to resolve relative URLs
+///
+/// # Returns
+///
+/// The page's `AssetSet`
+///
+/// # Examples
+///
+/// ```
+/// use spiderman::core::link_extractor::extract_assets;
+///
+/// let html = r#"<script src="/app.js"></script><img src="logo.png">"#;
+/// let assets = extract_assets(html, "http://example.com/");
+///
+/// assert_eq!(assets.scripts, vec!["http://example.com/app.js".to_string()]);
+/// assert_eq!(assets.images, vec!["http://example.com/logo.png".to_string()]);
+/// assert!(assets.styles.is_empty());
+/// ```
+pub fn extract_assets(html: &str, base_url: &str) -> AssetSet {
+    let tag_re = regex::Regex::new(r"(?is)<(script|link|img)\b([^>]*)>").unwrap();
+    let attr = |attrs: &str, name: &str| {
+        regex::Regex::new(&format!(r#"(?i)\b{}\s*=\s*["']([^"']+)["']"#, name))
+            .unwrap()
+            .captures(attrs)
+            .map(|cap| decode_html_entities(cap[1].trim()))
+    };
+
+    let mut assets = AssetSet::default();
+    let mut seen = HashSet::new();
+    for cap in tag_re.captures_iter(html) {
+        let attrs = &cap[2];
+        let (bucket, url) = match cap[1].to_lowercase().as_str() {
+            "script" => (&mut assets.scripts, attr(attrs, "src")),
+            "img" => (&mut assets.images, attr(attrs, "src")),
+            _ => {
+                let is_stylesheet = attr(attrs, "rel").is_some_and(|rel| {
+                    rel.split_whitespace()
+                        .any(|r| r.eq_ignore_ascii_case("stylesheet"))
+                });
+                if !is_stylesheet {
+                    continue;
+                }
+                (&mut assets.styles, attr(attrs, "href"))
             }
+        };
 
-            // Normalize the URL to absolute
-            if let Some(absolute_url) = normalize_url(url, base_url) {
-                unique_links.insert(absolute_url);
+        let absolute = url
+            .filter(|url| is_valid_url(url))
+            .and_then(|url| normalize_url(&url, base_url));
+        if let Some(absolute) = absolute {
+            // Deduplicated within each kind of asset
+            if seen.insert((cap[1].to_lowercase(), normalize_url_for_storage(&absolute))) {
+                bucket.push(absolute);
             }
         }
     }
 
-    // Convert HashSet to Vec and return
-    unique_links.into_iter().collect()
+    assets
+}
+
+/// Finds the `action` of every GET form, with its position in the HTML
+///
+/// A form without a `method` attribute submits with GET. Attributes are
+/// matched after whitespace, so `data-method` and `data-action` are ignored.
+fn form_actions(html: &str) -> Vec<(usize, &str)> {
+    let form_re = regex::Regex::new(r#"(?i)<form\b[^>]*>"#).unwrap();
+    let method_re = regex::Regex::new(r#"(?i)(?:^|\s)method\s*=\s*["']?([a-z]+)"#).unwrap();
+    let action_re = regex::Regex::new(r#"(?i)(?:^|\s)action\s*=\s*["']([^"']+)["']"#).unwrap();
+
+    form_re
+        .find_iter(html)
+        .filter(|tag| {
+            method_re
+                .captures(tag.as_str())
+                .and_then(|cap| cap.get(1))
+                .is_none_or(|method| method.as_str().eq_ignore_ascii_case("get"))
+        })
+        .filter_map(|tag| {
+            let action = action_re.captures(tag.as_str())?.get(1)?;
+            Some((tag.start() + action.start(), action.as_str()))
+        })
+        .collect()
 }
 
 /// Checks if a URL is valid for crawling
@@ -134,8 +394,11 @@ pub fn extract_links(html: &str, base_url: &str) -> Vec<String> {
 /// - Mailto links (e.g., `mailto:email@example.com`)
 /// - Telephone links (e.g., `tel:+1234567890`)
 /// - Data URLs (e.g., `data:image/png;base64,...`)
+/// - Any other non-HTTP scheme (e.g., `ftp://`, `ws://`, `chrome://`)
 /// - Empty URLs
 ///
+/// Relative URLs (including protocol-relative `//host/path`) are allowed.
+///
 /// # Arguments
 ///
 /// * `url` - The URL string to validate
@@ -156,6 +419,7 @@ pub fn extract_links(html: &str, base_url: &str) -> Vec<String> {
 /// assert!(!is_valid_url("#section"));
 /// assert!(!is_valid_url("javascript:void(0)"));
 /// assert!(!is_valid_url("mailto:test@example.com"));
+/// assert!(!is_valid_url("ftp://example.com/file"));
 /// ```
 pub fn is_valid_url(url: &str) -> bool {
     let url = url.trim();
@@ -170,27 +434,26 @@ pub fn is_valid_url(url: &str) -> bool {
         return false;
     }
 
-    // Filter out javascript: URLs
-    if url.starts_with("javascript:") {
-        return false;
-    }
-
-    // Filter out mailto: URLs
-    if url.starts_with("mailto:") {
-        return false;
-    }
-
-    // Filter out tel: URLs
-    if url.starts_with("tel:") {
-        return false;
-    }
-
-    // Filter out data: URLs
-    if url.starts_with("data:") {
-        return false;
+    // Filter out every scheme other than http(s): javascript:, mailto:,
+    // tel:, data:, ftp://, ws:// and the like can't be crawled. Relative
+    // URLs have no scheme and pass.
+    match url_scheme(url) {
+        Some(scheme) => scheme.eq_ignore_ascii_case("http") || scheme.eq_ignore_ascii_case("https"),
+        None => true,
     }
+}
 
-    true
+/// Returns the scheme of an absolute URL (`http` in `http://...`)
+///
+/// A scheme is a letter followed by letters, digits, `+`, `-` or `.`, and
+/// ends at the first `:`. Relative URLs have none.
+fn url_scheme(url: &str) -> Option<&str> {
+    let (scheme, _) = url.split_once(':')?;
+    let mut chars = scheme.chars();
+
+    let valid = chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+    valid.then_some(scheme)
 }
 
 /// Normalizes a URL to an absolute URL using a base URL
@@ -285,6 +548,46 @@ pub fn normalize_url(url: &str, base_url: &str) -> Option<String> {
     Some(clean_url(&resolve_path(&combined)))
 }
 
+/// Prefixes a schemeless URL with a default scheme
+///
+/// URLs that already name a scheme are returned unchanged. Protocol-relative
+/// URLs (`//host/path`) get only the scheme.
+///
+/// # Arguments
+///
+/// * `url` - The URL, with or without a scheme (e.g. `example.com/page`)
+/// * `scheme` - The scheme to use when the URL has none (e.g. `https`)
+///
+/// # Returns
+///
+/// The URL with a scheme
+///
+/// # Examples
+///
+/// ```
+/// use spiderman::core::link_extractor::apply_default_scheme;
+///
+/// assert_eq!(apply_default_scheme("example.com", "https"), "https://example.com");
+/// assert_eq!(apply_default_scheme("//cdn.com/a.js", "https"), "https://cdn.com/a.js");
+/// assert_eq!(apply_default_scheme("http://example.com", "https"), "http://example.com");
+/// ```
+pub fn apply_default_scheme(url: &str, scheme: &str) -> String {
+    let url = url.trim();
+
+    // A `://` after the first `/`, `?` or `#` is part of the path or query
+    let has_scheme = url
+        .find("://")
+        .is_some_and(|pos| !url[..pos].contains(['/', '?', '#']));
+
+    if has_scheme {
+        url.to_string()
+    } else if let Some(rest) = url.strip_prefix("//") {
+        format!("{}://{}", scheme, rest)
+    } else {
+        format!("{}://{}", scheme, url)
+    }
+}
+
 /// Parses a base URL into its components
 ///
 /// Extracts protocol, host, and path from a URL.
@@ -465,6 +768,23 @@ mod tests {
         assert!(!is_valid_url("   "));
     }
 
+    #[test]
+    fn test_is_valid_url_rejects_non_http_schemes() {
+        assert!(!is_valid_url("ftp://x"));
+        assert!(!is_valid_url("ws://x"));
+        assert!(!is_valid_url("wss://x/socket"));
+        assert!(!is_valid_url("chrome://x"));
+        assert!(!is_valid_url("JavaScript:alert(1)"));
+        assert!(!is_valid_url("git+ssh://host/repo"));
+
+        assert!(is_valid_url("http://x"));
+        assert!(is_valid_url("HTTPS://x"));
+        assert!(is_valid_url("/relative"));
+        assert!(is_valid_url("//cdn.example.com/lib.js"));
+        assert!(is_valid_url("page.html?time=10:30"));
+        assert!(is_valid_url("./a:b"));
+    }
+
     // ===== URL Normalization Tests =====
 
     #[test]
@@ -568,6 +888,26 @@ mod tests {
         assert_eq!(path, "/page");
     }
 
+    #[test]
+    fn test_apply_default_scheme() {
+        assert_eq!(
+            apply_default_scheme("example.com/page", "https"),
+            "https://example.com/page"
+        );
+        assert_eq!(
+            apply_default_scheme("//cdn.example.com/a.js", "http"),
+            "http://cdn.example.com/a.js"
+        );
+        assert_eq!(
+            apply_default_scheme("http://example.com", "https"),
+            "http://example.com"
+        );
+        assert_eq!(
+            apply_default_scheme("example.com/go?to=http://other.com", "https"),
+            "https://example.com/go?to=http://other.com"
+        );
+    }
+
     // ===== Path Resolution Tests =====
 
     #[test]
@@ -688,6 +1028,27 @@ mod tests {
         assert!(links.contains(&"http://example.com/contact.html".to_string()));
     }
 
+    #[test]
+    fn test_extract_links_preserves_document_order() {
+        let html = r#"
+            <a href="/c">C</a>
+            <a href="/a">A</a>
+            <a href="/c">C again</a>
+            <a href="/b">B</a>
+        "#;
+
+        let links = extract_links(html, "http://example.com");
+
+        assert_eq!(
+            links,
+            vec![
+                "http://example.com/c".to_string(),
+                "http://example.com/a".to_string(),
+                "http://example.com/b".to_string()
+            ]
+        );
+    }
+
     #[test]
     fn test_extract_links_single_quotes() {
         let html = r##"<a href='/page1'>Page 1</a><a href='/page2'>Page 2</a>"##;
@@ -709,4 +1070,118 @@ mod tests {
         let links = extract_links(html, "http://example.com");
         assert_eq!(links.len(), 0);
     }
+
+    // ===== Anchor Text Tests =====
+
+    #[test]
+    fn test_extract_anchor_texts() {
+        let html = r#"
+            <a href="/post">
+                <img src="/thumb.png"> Read   <em>more</em>
+            </a>
+            <a href="/post/">Second link</a>
+            <a href="/empty"><img src="/icon.png"></a>
+            <a href="/empty">Fish &amp; Chips</a>
+            <a href="mailto:me@example.com">Mail</a>
+        "#;
+
+        let anchors = extract_anchor_texts(html, "http://example.com");
+
+        assert_eq!(anchors.len(), 2);
+        assert_eq!(anchors["http://example.com/post"], "Read more");
+        assert_eq!(anchors["http://example.com/empty"], "Fish & Chips");
+    }
+
+    // ===== Asset Inventory Tests =====
+
+    #[test]
+    fn test_extract_assets() {
+        let html = r#"
+            <head>
+                <link rel="stylesheet" href="/css/site.css">
+                <link rel="icon" href="/favicon.ico">
+                <link rel="alternate stylesheet" href="print.css">
+                <script src="https://cdn.example.net/lib.js"></script>
+                <script>console.log("inline");</script>
+            </head>
+            <body>
+                <img src="/img/a.png" alt="A">
+                <IMG SRC="/img/a.png">
+                <img src="data:image/png;base64,AAAA">
+                <a href="/page">Page</a>
+                <script type="module" src='/js/app.js?v=1&amp;x=2'></script>
+            </body>
+        "#;
+
+        let assets = extract_assets(html, "http://example.com/blog/");
+
+        assert_eq!(
+            assets.scripts,
+            vec![
+                "https://cdn.example.net/lib.js".to_string(),
+                "http://example.com/js/app.js?v=1&x=2".to_string(),
+            ]
+        );
+        assert_eq!(
+            assets.styles,
+            vec![
+                "http://example.com/css/site.css".to_string(),
+                "http://example.com/blog/print.css".to_string(),
+            ]
+        );
+        assert_eq!(
+            assets.images,
+            vec!["http://example.com/img/a.png".to_string()]
+        );
+        assert!(!assets.is_empty());
+        assert!(extract_assets("<p>Text</p>", "http://example.com").is_empty());
+    }
+
+    // ===== Form Action Tests =====
+
+    #[test]
+    fn test_extract_links_with_forms_discovers_get_actions() {
+        let html = r#"
+            <a href="/first">First</a>
+            <form method="GET" action="search"><input name="q"></form>
+            <form action="/filter"><select name="tag"></select></form>
+            <form method="post" action="/login"><input name="user"></form>
+            <a href="/last">Last</a>
+        "#;
+        let options = LinkOptions::new().with_forms(true);
+
+        let links = extract_links_with_options(html, "http://example.com/docs/", &options);
+
+        assert_eq!(
+            links,
+            vec![
+                "http://example.com/first".to_string(),
+                "http://example.com/docs/search".to_string(),
+                "http://example.com/filter".to_string(),
+                "http://example.com/last".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_links_with_forms_ignores_data_attributes() {
+        let html = r#"
+            <form data-method="get" method="post" action="/login"></form>
+            <form data-action="/fake" action="/search"></form>
+        "#;
+        let options = LinkOptions::new().with_forms(true);
+
+        let links = extract_links_with_options(html, "http://example.com", &options);
+
+        assert_eq!(links, vec!["http://example.com/search".to_string()]);
+    }
+
+    #[test]
+    fn test_extract_links_ignores_forms_by_default() {
+        let html = r#"<form method="get" action="/search"></form><a href="/a">A</a>"#;
+
+        let links = extract_links(html, "http://example.com");
+
+        assert_eq!(links, vec!["http://example.com/a".to_string()]);
+    }
 }
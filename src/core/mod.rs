@@ -1,25 +1,69 @@
 pub mod crawl;
 pub mod document;
+pub mod error;
 pub mod export;
+pub mod feed;
 pub mod html_to_md;
 pub mod link_extractor;
+pub mod readability;
+pub mod robots;
+pub mod sitemap;
 pub mod url_manager;
 pub mod webshooter;
 
+#[cfg(test)]
+mod test_server;
+
 // Re-export commonly used types
-pub use crawl::{CrawlConfig, CrawlResult};
-pub use document::Document;
-pub use export::Exporter;
+pub use crawl::{
+    CrawlConfig, CrawlControl, CrawlEvent, CrawlResult, CrawlState, FailureCount, FinishHook,
+    PageChange,
+};
+pub use document::{ContentDiff, Document, ExtractionInfo};
+pub use error::CrawlError;
+pub use export::{Exporter, OutputFormat};
+pub use robots::RobotsTxt;
+pub use url_manager::{CrawlStrategy, UrlRewriter};
 
 #[derive(Debug, Default)]
 pub struct Spiderman<'a> {
     url: &'a str,
     html: Option<String>,
+    config: Option<CrawlConfig>,
 }
 
 impl<'a> Spiderman<'a> {
     pub fn new(url: &'a str) -> Self {
-        Self { url, html: None }
+        Self {
+            url,
+            html: None,
+            config: None,
+        }
+    }
+
+    /// Creates a crawler for a seed URL with the config `run` uses
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The seed URL
+    /// * `config` - Crawl configuration options
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use spiderman::core::{CrawlConfig, Spiderman};
+    ///
+    /// async_std::task::block_on(async {
+    ///     let config = CrawlConfig::default().with_max_pages(10);
+    ///     let result = Spiderman::with_config("example.com", config).run().await.unwrap();
+    /// });
+    /// ```
+    pub fn with_config(url: &'a str, config: CrawlConfig) -> Self {
+        Self {
+            url,
+            html: None,
+            config: Some(config),
+        }
     }
 
     /// Get the fetched HTML content
@@ -0,0 +1,440 @@
+//! Readability Module
+//!
+//! This module finds the main article of an HTML page, Readability-style:
+//! paragraphs are scored by how much prose they hold, their scores are
+//! credited to the containers around them, and the container with the best
+//! score (discounted by how much of its text is links) is the article.
+//!
+//! # Scoring
+//!
+//! ```text
+//! <body>
+//!   <nav>..links..</nav>          link density ~1   -> score ~0
+//!   <div class="sidebar">         negative class    -> score - 25
+//!   <article>                     parent of the <p>s -> full paragraph scores
+//!     <p>Long text, with commas</p>
+//!     <p>More text...</p>
+//!   </article>
+//! </body>                         grandparent        -> half the scores
+//! ```
+//!
+//! Unlike `html_to_md::strip_boilerplate`, which drops a fixed set of tags,
+//! this also works on pages that lay out everything with `<div>`s.
+//!
+//! # Examples
+//!
+//! ```
+//! use spiderman::core::readability::extract_main;
+//!
+//! let html = r#"<div class="sidebar"><a href="/">Home</a></div>
+//!     <article><p>A paragraph that is long enough, with a comma, to count as prose.</p></article>"#;
+//! let main = extract_main(html).unwrap();
+//! assert!(main.starts_with("<article>"));
+//! ```
+
+use super::document::decode_html_entities;
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// Comments and elements whose text is never shown
+static HIDDEN_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?is)<!--.*?-->|<(script|style|template)\b[^>]*>.*?</(script|style|template)\s*>")
+        .unwrap()
+});
+
+/// An opening or closing tag, with its slash, name and attributes
+static ELEMENT_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"<(/?)([a-zA-Z][a-zA-Z0-9]*)\b([^>]*)>").unwrap());
+
+/// A `class` or `id` attribute value
+static HINT_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"(?i)\b(?:class|id)\s*=\s*["']([^"']*)["']"#).unwrap());
+
+/// Any tag, with its slash and (if it has one) its name
+static TAG_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"<(/?)([a-zA-Z][a-zA-Z0-9]*)?[^>]*>").unwrap());
+
+/// Elements that can hold the main article
+const CANDIDATE_TAGS: [&str; 7] = [
+    "article",
+    "main",
+    "section",
+    "div",
+    "td",
+    "blockquote",
+    "body",
+];
+
+/// Elements whose text is scored as prose
+const PARAGRAPH_TAGS: [&str; 2] = ["p", "pre"];
+
+/// Elements that never have content or a closing tag
+const VOID_TAGS: [&str; 14] = [
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+/// Paragraphs with less text than this are not scored
+const MIN_PARAGRAPH_LEN: usize = 25;
+
+/// Class or id words hinting at the main content
+const POSITIVE_HINTS: [&str; 7] = [
+    "article", "content", "main", "post", "entry", "story", "text",
+];
+
+/// Class or id words hinting at boilerplate
+const NEGATIVE_HINTS: [&str; 12] = [
+    "sidebar", "nav", "menu", "footer", "header", "comment", "related", "share", "social",
+    "widget", "banner", "promo",
+];
+
+/// An element located in the page
+#[derive(Debug)]
+struct Element {
+    /// Lowercase tag name
+    name: String,
+
+    /// Byte offset of the opening tag
+    start: usize,
+
+    /// Byte offset just past the closing tag (or where it was implicitly
+    /// closed)
+    end: usize,
+
+    /// Index of the enclosing element, if any
+    parent: Option<usize>,
+
+    /// Lowercase `class` and `id` attribute values
+    hints: String,
+}
+
+/// Finds the main article of an HTML page
+///
+/// Each `<p>`/`<pre>` with at least 25 characters of text scores one point,
+/// plus one per comma and one per 100 characters (up to three). The score
+/// goes to the paragraph's closest container (`<div>`, `<article>`, ...) and
+/// half of it to the next one up. Containers get a bonus for `<article>` and
+/// `<main>` and for class or id names like `content`, and a penalty for
+/// names like `sidebar`; the total is then scaled by the share of the
+/// container's text that is not link text.
+///
+/// # Arguments
+///
+/// * `html` - The HTML content
+///
+/// # Returns
+///
+/// * `Some(String)` - The HTML of the best-scoring container, tags included
+/// * `None` - If the page has no paragraph long enough to score
+///
+/// # Examples
+///
+/// ```
+/// use spiderman::core::readability::extract_main;
+///
+/// assert_eq!(extract_main("<nav><a href=\"/\">Home</a></nav>"), None);
+/// ```
+pub fn extract_main(html: &str) -> Option<String> {
+    let html = HIDDEN_RE.replace_all(html, "");
+    let elements = parse_elements(&html);
+
+    let mut scores = vec![0.0_f64; elements.len()];
+    let mut scored = vec![false; elements.len()];
+    for element in elements
+        .iter()
+        .filter(|e| PARAGRAPH_TAGS.contains(&e.name.as_str()))
+    {
+        let (text, _) = scan_text(&html[element.start..element.end]);
+        let len = text.chars().count();
+        if len < MIN_PARAGRAPH_LEN {
+            continue;
+        }
+        let score = 1.0 + text.matches(',').count() as f64 + (len / 100).min(3) as f64;
+
+        let mut containers = ancestors(&elements, element).filter(|&i| is_candidate(&elements[i]));
+        if let Some(parent) = containers.next() {
+            scores[parent] += score;
+            scored[parent] = true;
+        }
+        if let Some(grandparent) = containers.next() {
+            scores[grandparent] += score / 2.0;
+            scored[grandparent] = true;
+        }
+    }
+
+    let best = (0..elements.len())
+        .filter(|&i| scored[i])
+        .map(|i| {
+            let element = &elements[i];
+            let outer = &html[element.start..element.end];
+            let score = (scores[i] + tag_bonus(element)) * (1.0 - link_density(outer));
+            (i, score)
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1))?;
+
+    let element = &elements[best.0];
+    Some(html[element.start..element.end].to_string())
+}
+
+/// Locates every element of a page, with its span and parent
+///
+/// Tolerates sloppy markup the way browsers do for the common cases: a
+/// closing tag closes any elements still open inside it, a stray closing
+/// tag is ignored, and a `<p>` ends an open `<p>`.
+fn parse_elements(html: &str) -> Vec<Element> {
+    let mut elements: Vec<Element> = Vec::new();
+    let mut open: Vec<usize> = Vec::new();
+
+    for cap in ELEMENT_RE.captures_iter(html) {
+        let tag = cap.get(0).unwrap();
+        let name = cap[2].to_lowercase();
+
+        if &cap[1] == "/" {
+            if let Some(depth) = open.iter().rposition(|&i| elements[i].name == name) {
+                for i in open.drain(depth..) {
+                    elements[i].end = tag.end();
+                }
+            }
+            continue;
+        }
+
+        if name == "p" && open.last().is_some_and(|&i| elements[i].name == "p") {
+            let i = open.pop().unwrap();
+            elements[i].end = tag.start();
+        }
+
+        let attrs = &cap[3];
+        elements.push(Element {
+            name: name.clone(),
+            start: tag.start(),
+            end: tag.end(),
+            parent: open.last().copied(),
+            hints: HINT_RE
+                .captures_iter(attrs)
+                .map(|hint| hint[1].to_lowercase())
+                .collect::<Vec<_>>()
+                .join(" "),
+        });
+
+        if !VOID_TAGS.contains(&name.as_str()) && !attrs.trim_end().ends_with('/') {
+            open.push(elements.len() - 1);
+        }
+    }
+
+    // Elements left open run to the end of the page
+    for i in open {
+        elements[i].end = html.len();
+    }
+
+    elements
+}
+
+/// Iterates over the indices of an element's ancestors, closest first
+fn ancestors<'a>(elements: &'a [Element], element: &Element) -> impl Iterator<Item = usize> + 'a {
+    std::iter::successors(element.parent, move |&i| elements[i].parent)
+}
+
+/// Returns true if the element can hold the main article
+fn is_candidate(element: &Element) -> bool {
+    CANDIDATE_TAGS.contains(&element.name.as_str())
+}
+
+/// Returns the score adjustment for an element's tag and class/id names
+fn tag_bonus(element: &Element) -> f64 {
+    let tag = match element.name.as_str() {
+        "article" | "main" => 10.0,
+        "div" => 5.0,
+        "td" | "blockquote" => 3.0,
+        _ => 0.0,
+    };
+
+    let words: Vec<&str> = element
+        .hints
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .collect();
+    let has = |hints: &[&str]| hints.iter().any(|hint| words.contains(hint));
+
+    let mut bonus = tag;
+    if has(&POSITIVE_HINTS) {
+        bonus += 25.0;
+    }
+    if has(&NEGATIVE_HINTS) {
+        bonus -= 25.0;
+    }
+    bonus
+}
+
+/// Returns the share of an element's text that is inside links (0.0 - 1.0)
+fn link_density(html: &str) -> f64 {
+    let (text, link_text) = scan_text(html);
+
+    let text_len = text.chars().count();
+    if text_len == 0 {
+        return 0.0;
+    }
+    let link_len = link_text.chars().count();
+
+    (link_len as f64 / text_len as f64).min(1.0)
+}
+
+/// Returns the text of an HTML fragment and the part of it inside links,
+/// in one pass over its tags
+///
+/// Like `html_to_md::visible_text`, but expects comments, scripts and
+/// styles to be removed already.
+fn scan_text(html: &str) -> (String, String) {
+    let mut text = String::new();
+    let mut link_text = String::new();
+    let mut link_depth = 0_usize;
+    let mut last = 0;
+
+    for cap in TAG_RE.captures_iter(html) {
+        let tag = cap.get(0).unwrap();
+        let segment = &html[last..tag.start()];
+        text.push_str(segment);
+        text.push(' ');
+        if link_depth > 0 {
+            link_text.push_str(segment);
+            link_text.push(' ');
+        }
+        last = tag.end();
+
+        if cap
+            .get(2)
+            .is_some_and(|name| name.as_str().eq_ignore_ascii_case("a"))
+        {
+            if &cap[1] == "/" {
+                link_depth = link_depth.saturating_sub(1);
+            } else {
+                link_depth += 1;
+            }
+        }
+    }
+    text.push_str(&html[last..]);
+    if link_depth > 0 {
+        link_text.push_str(&html[last..]);
+    }
+
+    (collapse_text(&text), collapse_text(&link_text))
+}
+
+/// Collapses whitespace (including `&nbsp;`) and decodes entities
+fn collapse_text(raw: &str) -> String {
+    let text = raw.replace("&nbsp;", " ").replace("&#160;", " ");
+    decode_html_entities(&text.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A page with navigation, a link-heavy sidebar and an article
+    fn article_page() -> &'static str {
+        r#"<html><head><title>Post</title><script>var p = "<p>not prose, at all, really</p>";</script></head>
+        <body>
+          <div id="page">
+            <nav><a href="/">Home</a> | <a href="/blog">Blog</a> | <a href="/about">About</a></nav>
+            <div class="sidebar">
+              <p><a href="/popular/1">Popular: ten reasons to crawl, responsibly</a></p>
+              <p><a href="/popular/2">Popular: why robots.txt matters, explained</a></p>
+              <p>Subscribe to our newsletter for weekly updates</p>
+            </div>
+            <div class="post-body">
+              <h1>How crawlers work</h1>
+              <p>A crawler starts from a seed URL, fetches the page, and extracts its links.
+                 Each link is normalized, deduplicated, and queued for a later visit.</p>
+              <p>Politeness matters: crawlers honor robots.txt, wait between requests,
+                 and spread their load across hosts, so that no single server is overwhelmed.</p>
+              <p>The fetched HTML is converted to Markdown, which keeps the structure
+                 while dropping markup, scripts, and styling.</p>
+            </div>
+            <footer>Copyright, 2024, Example Inc. All rights reserved.</footer>
+          </div>
+        </body></html>"#
+    }
+
+    // ===== Extraction Tests =====
+
+    #[test]
+    fn test_extract_main_selects_article() {
+        let main = extract_main(article_page()).unwrap();
+
+        assert!(main.starts_with(r#"<div class="post-body">"#));
+        assert!(main.trim_end().ends_with("</div>"));
+        assert!(main.contains("How crawlers work"));
+        assert!(main.contains("Politeness matters"));
+        assert!(!main.contains("Popular"));
+        assert!(!main.contains("newsletter"));
+        assert!(!main.contains("Copyright"));
+        assert!(!main.contains("Home"));
+    }
+
+    #[test]
+    fn test_extract_main_prefers_article_over_wrapper() {
+        let html = r#"<div><article>
+            <p>First paragraph of the story, long enough to count, with a comma.</p>
+            <p>Second paragraph of the story, also long enough, with commas, too.</p>
+            </article><p>Short note below the article, not much to say here.</p></div>"#;
+
+        let main = extract_main(html).unwrap();
+        assert!(main.starts_with("<article>"));
+        assert!(!main.contains("Short note"));
+    }
+
+    #[test]
+    fn test_extract_main_unclosed_paragraphs() {
+        let html = r#"<div class="content"><p>An unclosed paragraph that has enough text, surely.
+            <p>Another unclosed paragraph, with some more text in it.</div>
+            <div class="menu"><a href="/a">A link</a></div>"#;
+
+        let main = extract_main(html).unwrap();
+        assert!(main.starts_with(r#"<div class="content">"#));
+        assert!(!main.contains("A link"));
+    }
+
+    #[test]
+    fn test_extract_main_without_prose() {
+        assert_eq!(extract_main("<nav><a href=\"/\">Home</a></nav>"), None);
+        assert_eq!(extract_main("<div><p>Too short</p></div>"), None);
+        assert_eq!(extract_main(""), None);
+    }
+
+    // ===== Scoring Tests =====
+
+    #[test]
+    fn test_link_density() {
+        assert_eq!(link_density("<p>no links</p>"), 0.0);
+        assert_eq!(link_density(r#"<a href="/">all link</a>"#), 1.0);
+        let mixed = link_density(r#"<p>abcd <a href="/">link</a> efgh</p>"#);
+        assert!((mixed - 4.0 / 14.0).abs() < 1e-9);
+        assert_eq!(link_density(""), 0.0);
+    }
+
+    #[test]
+    fn test_scan_text() {
+        let html = r#"<p>Read&nbsp;the <a href="/x">full <b>story</b></a>
+            &amp; <abbr>more</abbr> <A HREF="/y">here</A></p>"#;
+
+        let (text, link_text) = scan_text(html);
+        assert_eq!(text, "Read the full story & more here");
+        assert_eq!(link_text, "full story here");
+    }
+
+    #[test]
+    fn test_parse_elements_spans() {
+        let html = "<div id=\"a\"><p>One<br><p>Two</div><span>x</span>";
+        let elements = parse_elements(html);
+
+        let names: Vec<&str> = elements.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["div", "p", "br", "p", "span"]);
+
+        assert_eq!(
+            &html[elements[0].start..elements[0].end],
+            "<div id=\"a\"><p>One<br><p>Two</div>"
+        );
+        assert_eq!(&html[elements[1].start..elements[1].end], "<p>One<br>");
+        assert_eq!(elements[3].parent, Some(0));
+        assert_eq!(elements[4].parent, None);
+        assert_eq!(elements[0].hints, "a");
+    }
+}
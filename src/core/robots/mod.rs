@@ -0,0 +1,592 @@
+//! Robots Module
+//!
+//! This module parses robots.txt files and answers whether a path may be
+//! crawled by a given user agent.
+//!
+//! # Overview
+//!
+//! A robots.txt file is a list of groups. Each group starts with one or more
+//! `User-agent:` lines, followed by the `Allow:`/`Disallow:` rules (and an
+//! optional `Crawl-delay:`) for those agents. `Sitemap:` lines apply to the
+//! whole file.
+//!
+//! ```text
+//! User-agent: spiderman         ← group for "spiderman"
+//! Disallow: /private
+//! Allow: /private/press
+//! Crawl-delay: 2
+//!
+//! User-agent: *                 ← group for every other agent
+//! Disallow: /
+//!
+//! Sitemap: http://example.com/sitemap.xml
+//! ```
+//!
+//! # Matching Rules
+//!
+//! 1. **Group selection**: The group with the longest user agent matching the
+//!    crawler's name wins; `*` is the fallback. Groups naming the same agent
+//!    are merged.
+//! 2. **Rule precedence**: The longest matching rule wins. On a tie, `Allow`
+//!    wins. No matching rule means the path is allowed.
+//! 3. **Wildcards**: `*` matches any sequence of characters, and a trailing
+//!    `$` anchors the rule to the end of the path.
+//!
+//! # Examples
+//!
+//! ```
+//! use spiderman::core::robots::RobotsTxt;
+//!
+//! let robots = RobotsTxt::parse("User-agent: *\nDisallow: /admin\nAllow: /admin/help");
+//!
+//! assert!(robots.is_allowed("/", "spiderman"));
+//! assert!(!robots.is_allowed("/admin/users", "spiderman"));
+//! assert!(robots.is_allowed("/admin/help", "spiderman"));
+//! ```
+
+use std::time::Duration;
+
+/// A parsed robots.txt file
+///
+/// # Fields
+///
+/// * `groups` - The user agent groups, in file order
+/// * `sitemaps` - URLs from `Sitemap:` lines, in file order
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RobotsTxt {
+    /// User agent groups
+    groups: Vec<Group>,
+
+    /// Sitemap URLs declared anywhere in the file
+    sitemaps: Vec<String>,
+}
+
+/// The rules for one or more user agents
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Group {
+    /// Lowercased user agent names (`*` = any agent)
+    user_agents: Vec<String>,
+
+    /// Allow/Disallow rules, in file order
+    rules: Vec<Rule>,
+
+    /// Delay requested between requests
+    crawl_delay: Option<Duration>,
+}
+
+impl Group {
+    /// Returns true once the group has rules, so a following `User-agent`
+    /// line starts a new group
+    fn has_directives(&self) -> bool {
+        !self.rules.is_empty() || self.crawl_delay.is_some()
+    }
+}
+
+/// A single `Allow` or `Disallow` rule
+#[derive(Debug, Clone, PartialEq)]
+struct Rule {
+    /// Whether the rule allows (true) or disallows (false) matching paths
+    allow: bool,
+
+    /// Path pattern, possibly containing `*` and a trailing `$`
+    pattern: String,
+}
+
+impl RobotsTxt {
+    /// Parses the content of a robots.txt file
+    ///
+    /// Parsing never fails: unknown directives, comments and malformed
+    /// lines are ignored, and rules before the first `User-agent` line are
+    /// dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The robots.txt content
+    ///
+    /// # Returns
+    ///
+    /// The parsed `RobotsTxt`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spiderman::core::robots::RobotsTxt;
+    ///
+    /// let robots = RobotsTxt::parse("User-agent: *\nDisallow: /tmp # scratch space");
+    /// assert!(!robots.is_allowed("/tmp/file", "spiderman"));
+    /// ```
+    pub fn parse(text: &str) -> Self {
+        let mut robots = Self::default();
+        let mut current: Option<Group> = None;
+
+        for line in text.lines() {
+            // Drop comments
+            let line = line.split('#').next().unwrap_or("");
+            let Some((name, value)) = line.split_once(':') else {
+                continue;
+            };
+            let value = value.trim();
+
+            match name.trim().to_lowercase().as_str() {
+                "user-agent" => {
+                    // Consecutive User-agent lines share one group
+                    if current.as_ref().is_some_and(Group::has_directives) {
+                        robots.groups.extend(current.take());
+                    }
+                    current
+                        .get_or_insert_with(Group::default)
+                        .user_agents
+                        .push(value.to_lowercase());
+                }
+                directive @ ("allow" | "disallow") => {
+                    // An empty rule matches nothing
+                    if let (Some(group), false) = (current.as_mut(), value.is_empty()) {
+                        group.rules.push(Rule {
+                            allow: directive == "allow",
+                            pattern: value.to_string(),
+                        });
+                    }
+                }
+                "crawl-delay" => {
+                    // Values too large for a `Duration` are ignored
+                    let delay = value
+                        .parse::<f64>()
+                        .ok()
+                        .and_then(|secs| Duration::try_from_secs_f64(secs).ok());
+                    if let (Some(group), Some(delay)) = (current.as_mut(), delay) {
+                        group.crawl_delay = Some(delay);
+                    }
+                }
+                "sitemap" if !value.is_empty() => robots.sitemaps.push(value.to_string()),
+                _ => {}
+            }
+        }
+
+        robots.groups.extend(current);
+        robots
+    }
+
+    /// Checks whether a user agent may crawl a path
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The URL path, including any query string (e.g. `/a?b=1`)
+    /// * `user_agent` - The crawler's name or full `User-Agent` header
+    ///   (e.g. `Spiderman/1.0`)
+    ///
+    /// # Returns
+    ///
+    /// `true` unless the longest matching rule for the agent is a `Disallow`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spiderman::core::robots::RobotsTxt;
+    ///
+    /// let robots = RobotsTxt::parse("User-agent: *\nDisallow: /*.pdf$");
+    /// assert!(!robots.is_allowed("/files/report.pdf", "spiderman"));
+    /// assert!(robots.is_allowed("/files/report.pdf?view=1", "spiderman"));
+    /// ```
+    pub fn is_allowed(&self, path: &str, user_agent: &str) -> bool {
+        let path = if path.starts_with('/') {
+            path.to_string()
+        } else {
+            format!("/{}", path)
+        };
+
+        // The robots.txt file itself is always crawlable
+        if path == "/robots.txt" {
+            return true;
+        }
+
+        let mut best: Option<&Rule> = None;
+        for rule in self.groups_for(user_agent).flat_map(|group| &group.rules) {
+            if !pattern_matches(&rule.pattern, &path) {
+                continue;
+            }
+
+            // Longer patterns are more specific; on a tie Allow wins
+            let better = best.is_none_or(|best| {
+                rule.pattern.len() > best.pattern.len()
+                    || (rule.pattern.len() == best.pattern.len() && rule.allow && !best.allow)
+            });
+            if better {
+                best = Some(rule);
+            }
+        }
+
+        best.is_none_or(|rule| rule.allow)
+    }
+
+    /// Returns the crawl delay requested for a user agent
+    ///
+    /// # Arguments
+    ///
+    /// * `user_agent` - The crawler's name or full `User-Agent` header
+    ///
+    /// # Returns
+    ///
+    /// The `Crawl-delay` of the agent's group, or `None` if it sets none
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spiderman::core::robots::RobotsTxt;
+    /// use std::time::Duration;
+    ///
+    /// let robots = RobotsTxt::parse("User-agent: *\nCrawl-delay: 1.5");
+    /// assert_eq!(robots.crawl_delay("spiderman"), Some(Duration::from_millis(1500)));
+    /// ```
+    pub fn crawl_delay(&self, user_agent: &str) -> Option<Duration> {
+        self.groups_for(user_agent)
+            .find_map(|group| group.crawl_delay)
+    }
+
+    /// Returns the sitemap URLs declared in the file, in order
+    pub fn sitemaps(&self) -> &[String] {
+        &self.sitemaps
+    }
+
+    /// Returns the groups that apply to a user agent
+    ///
+    /// Only the groups naming the most specific matching agent are returned,
+    /// falling back to the `*` groups.
+    fn groups_for<'a>(&'a self, user_agent: &str) -> impl Iterator<Item = &'a Group> + 'a {
+        let name = product_token(user_agent);
+
+        // The longest group agent the crawler's name starts with
+        let best = self
+            .groups
+            .iter()
+            .flat_map(|group| &group.user_agents)
+            .filter(|agent| agent.as_str() != "*" && name.starts_with(agent.as_str()))
+            .max_by_key(|agent| agent.len())
+            .cloned()
+            .unwrap_or_else(|| "*".to_string());
+
+        self.groups
+            .iter()
+            .filter(move |group| group.user_agents.contains(&best))
+    }
+}
+
+/// Returns the lowercased name part of a user agent (`Spiderman/1.0` →
+/// `spiderman`)
+fn product_token(user_agent: &str) -> String {
+    user_agent
+        .split(|c: char| c == '/' || c.is_whitespace())
+        .find(|part| !part.is_empty())
+        .unwrap_or("")
+        .to_lowercase()
+}
+
+/// Checks whether a rule pattern matches the start of a path
+///
+/// `*` matches any sequence of characters; a trailing `$` requires the
+/// pattern to match the whole path. Matching is case-sensitive.
+fn pattern_matches(pattern: &str, path: &str) -> bool {
+    let (pattern, anchored) = match pattern.strip_suffix('$') {
+        Some(pattern) => (pattern, true),
+        None => (pattern, false),
+    };
+
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or("");
+    if !path.starts_with(first) {
+        return false;
+    }
+
+    let rest: Vec<&str> = parts.collect();
+    let Some((last, middle)) = rest.split_last() else {
+        // No wildcard: a plain prefix match
+        return !anchored || path.len() == first.len();
+    };
+
+    // Match each middle segment as early as possible, which leaves the most
+    // room for the ones after it
+    let mut pos = first.len();
+    for part in middle {
+        match path[pos..].find(part) {
+            Some(found) => pos += found + part.len(),
+            None => return false,
+        }
+    }
+
+    if anchored {
+        path.len() >= pos + last.len() && path.ends_with(last)
+    } else {
+        path[pos..].contains(last)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ===== Parsing Tests =====
+
+    #[test]
+    fn test_parse_groups() {
+        let robots = RobotsTxt::parse(
+            "User-agent: a\nUser-agent: b\nDisallow: /x\n\nUser-agent: *\nDisallow: /y",
+        );
+
+        assert_eq!(robots.groups.len(), 2);
+        assert_eq!(robots.groups[0].user_agents, vec!["a", "b"]);
+        assert_eq!(robots.groups[1].user_agents, vec!["*"]);
+    }
+
+    #[test]
+    fn test_parse_ignores_comments_and_junk() {
+        let robots = RobotsTxt::parse(
+            "# Our rules\nDisallow: /before-any-agent\nUser-agent: * # everyone\n\
+             Disallow: /private # secret\nnonsense line\nFoo: bar",
+        );
+
+        assert_eq!(robots.groups.len(), 1);
+        assert_eq!(
+            robots.groups[0].rules,
+            vec![Rule {
+                allow: false,
+                pattern: "/private".to_string()
+            }]
+        );
+        assert!(robots.is_allowed("/before-any-agent", "spiderman"));
+    }
+
+    #[test]
+    fn test_parse_directives_are_case_insensitive() {
+        let robots = RobotsTxt::parse("USER-AGENT: *\nDISALLOW: /Private");
+
+        assert!(!robots.is_allowed("/Private", "spiderman"));
+        // Paths stay case-sensitive
+        assert!(robots.is_allowed("/private", "spiderman"));
+    }
+
+    #[test]
+    fn test_parse_empty() {
+        let robots = RobotsTxt::parse("");
+
+        assert!(robots.is_allowed("/anything", "spiderman"));
+        assert_eq!(robots.crawl_delay("spiderman"), None);
+        assert!(robots.sitemaps().is_empty());
+    }
+
+    #[test]
+    fn test_sitemaps() {
+        let robots = RobotsTxt::parse(
+            "Sitemap: http://example.com/a.xml\nUser-agent: *\nDisallow: /\n\
+             sitemap: http://example.com/b.xml\nSitemap:",
+        );
+
+        assert_eq!(
+            robots.sitemaps(),
+            ["http://example.com/a.xml", "http://example.com/b.xml"]
+        );
+    }
+
+    // ===== Precedence Tests =====
+
+    #[test]
+    fn test_longest_match_wins() {
+        let robots = RobotsTxt::parse("User-agent: *\nAllow: /p\nDisallow: /");
+
+        assert!(robots.is_allowed("/page", "spiderman"));
+        assert!(!robots.is_allowed("/other", "spiderman"));
+    }
+
+    #[test]
+    fn test_tie_prefers_allow() {
+        let robots = RobotsTxt::parse("User-agent: *\nAllow: /folder\nDisallow: /folder");
+        assert!(robots.is_allowed("/folder/page", "spiderman"));
+
+        let robots = RobotsTxt::parse("User-agent: *\nAllow: /page\nDisallow: /*.ph");
+        assert!(robots.is_allowed("/page.php5", "spiderman"));
+    }
+
+    #[test]
+    fn test_longer_wildcard_rule_wins() {
+        let robots = RobotsTxt::parse("User-agent: *\nAllow: /page\nDisallow: /*.htm");
+
+        assert!(!robots.is_allowed("/page.htm", "spiderman"));
+        assert!(robots.is_allowed("/page", "spiderman"));
+    }
+
+    #[test]
+    fn test_anchored_root_allow() {
+        let robots = RobotsTxt::parse("User-agent: *\nAllow: /$\nDisallow: /");
+
+        assert!(robots.is_allowed("/", "spiderman"));
+        assert!(!robots.is_allowed("/page.htm", "spiderman"));
+    }
+
+    #[test]
+    fn test_empty_disallow_allows_everything() {
+        let robots = RobotsTxt::parse("User-agent: *\nDisallow:");
+        assert!(robots.is_allowed("/anything", "spiderman"));
+    }
+
+    #[test]
+    fn test_robots_txt_always_allowed() {
+        let robots = RobotsTxt::parse("User-agent: *\nDisallow: /");
+
+        assert!(robots.is_allowed("/robots.txt", "spiderman"));
+        assert!(!robots.is_allowed("/", "spiderman"));
+    }
+
+    #[test]
+    fn test_path_without_leading_slash() {
+        let robots = RobotsTxt::parse("User-agent: *\nDisallow: /admin");
+        assert!(!robots.is_allowed("admin/users", "spiderman"));
+    }
+
+    // ===== Wildcard Tests =====
+
+    #[test]
+    fn test_trailing_wildcard() {
+        let robots = RobotsTxt::parse("User-agent: *\nDisallow: /fish*");
+
+        assert!(!robots.is_allowed("/fish", "spiderman"));
+        assert!(!robots.is_allowed("/fish.html", "spiderman"));
+        assert!(!robots.is_allowed("/fishheads/yummy.html", "spiderman"));
+        assert!(robots.is_allowed("/Fish.asp", "spiderman"));
+        assert!(robots.is_allowed("/catfish", "spiderman"));
+    }
+
+    #[test]
+    fn test_trailing_slash_rule() {
+        let robots = RobotsTxt::parse("User-agent: *\nDisallow: /fish/");
+
+        assert!(!robots.is_allowed("/fish/", "spiderman"));
+        assert!(!robots.is_allowed("/fish/salmon.htm", "spiderman"));
+        assert!(robots.is_allowed("/fish", "spiderman"));
+        assert!(robots.is_allowed("/fish.html", "spiderman"));
+    }
+
+    #[test]
+    fn test_inner_wildcard() {
+        let robots = RobotsTxt::parse("User-agent: *\nDisallow: /*.php");
+
+        assert!(!robots.is_allowed("/filename.php", "spiderman"));
+        assert!(!robots.is_allowed("/folder/filename.php?parameters", "spiderman"));
+        assert!(!robots.is_allowed("/filename.php/", "spiderman"));
+        assert!(robots.is_allowed("/", "spiderman"));
+        assert!(robots.is_allowed("/windows.PHP", "spiderman"));
+    }
+
+    #[test]
+    fn test_end_anchor() {
+        let robots = RobotsTxt::parse("User-agent: *\nDisallow: /*.php$");
+
+        assert!(!robots.is_allowed("/filename.php", "spiderman"));
+        assert!(!robots.is_allowed("/folder/filename.php", "spiderman"));
+        assert!(robots.is_allowed("/filename.php?parameters", "spiderman"));
+        assert!(robots.is_allowed("/filename.php/", "spiderman"));
+        assert!(robots.is_allowed("/filename.php5", "spiderman"));
+    }
+
+    #[test]
+    fn test_multiple_wildcards() {
+        let robots = RobotsTxt::parse("User-agent: *\nDisallow: /fish*.php");
+
+        assert!(!robots.is_allowed("/fish.php", "spiderman"));
+        assert!(!robots.is_allowed("/fishheads/catfish.php?parameters", "spiderman"));
+        assert!(robots.is_allowed("/Fish.PHP", "spiderman"));
+
+        let robots = RobotsTxt::parse("User-agent: *\nDisallow: /a*b*c$");
+        assert!(!robots.is_allowed("/a-b-b-c", "spiderman"));
+        assert!(robots.is_allowed("/a-b-c-d", "spiderman"));
+        assert!(robots.is_allowed("/a-c-b", "spiderman"));
+    }
+
+    // ===== User Agent Tests =====
+
+    #[test]
+    fn test_specific_agent_overrides_wildcard() {
+        let robots = RobotsTxt::parse(
+            "User-agent: *\nDisallow: /\n\nUser-agent: spiderman\nDisallow: /private",
+        );
+
+        assert!(robots.is_allowed("/public", "Spiderman/1.0"));
+        assert!(!robots.is_allowed("/private", "spiderman"));
+        assert!(!robots.is_allowed("/public", "otherbot"));
+    }
+
+    #[test]
+    fn test_longest_agent_match_wins() {
+        let robots = RobotsTxt::parse(
+            "User-agent: googlebot\nDisallow: /a\n\nUser-agent: googlebot-news\nDisallow: /b",
+        );
+
+        assert!(robots.is_allowed("/a", "Googlebot-News"));
+        assert!(!robots.is_allowed("/b", "Googlebot-News"));
+        assert!(!robots.is_allowed("/a", "Googlebot-Image/1.0"));
+        assert!(robots.is_allowed("/b", "Googlebot-Image/1.0"));
+    }
+
+    #[test]
+    fn test_groups_for_same_agent_are_merged() {
+        let robots = RobotsTxt::parse(
+            "User-agent: spiderman\nDisallow: /a\n\nUser-agent: *\nDisallow: /c\n\n\
+             User-agent: spiderman\nDisallow: /b",
+        );
+
+        assert!(!robots.is_allowed("/a", "spiderman"));
+        assert!(!robots.is_allowed("/b", "spiderman"));
+        assert!(robots.is_allowed("/c", "spiderman"));
+    }
+
+    #[test]
+    fn test_shared_group() {
+        let robots = RobotsTxt::parse("User-agent: a\nUser-agent: b\nDisallow: /x");
+
+        assert!(!robots.is_allowed("/x", "a"));
+        assert!(!robots.is_allowed("/x", "b"));
+        assert!(robots.is_allowed("/x", "c"));
+    }
+
+    #[test]
+    fn test_no_matching_group_allows_everything() {
+        let robots = RobotsTxt::parse("User-agent: otherbot\nDisallow: /");
+        assert!(robots.is_allowed("/page", "spiderman"));
+    }
+
+    // ===== Crawl Delay Tests =====
+
+    #[test]
+    fn test_crawl_delay() {
+        let robots = RobotsTxt::parse(
+            "User-agent: *\nCrawl-delay: 10\n\nUser-agent: spiderman\nCrawl-delay: 0.5\n\n\
+             User-agent: slowbot\nCrawl-delay: soon",
+        );
+
+        assert_eq!(
+            robots.crawl_delay("spiderman"),
+            Some(Duration::from_millis(500))
+        );
+        assert_eq!(
+            robots.crawl_delay("otherbot"),
+            Some(Duration::from_secs(10))
+        );
+        assert_eq!(robots.crawl_delay("slowbot"), None);
+    }
+
+    #[test]
+    fn test_crawl_delay_out_of_range() {
+        for value in ["1e300", "inf", "NaN", "-1"] {
+            let robots = RobotsTxt::parse(&format!("User-agent: *\nCrawl-delay: {}", value));
+            assert_eq!(robots.crawl_delay("spiderman"), None, "{}", value);
+        }
+    }
+
+    #[test]
+    fn test_crawl_delay_ends_agent_list() {
+        // A Crawl-delay line closes the group like a rule does
+        let robots = RobotsTxt::parse("User-agent: a\nCrawl-delay: 1\nUser-agent: b\nDisallow: /");
+
+        assert_eq!(robots.crawl_delay("a"), Some(Duration::from_secs(1)));
+        assert_eq!(robots.crawl_delay("b"), None);
+        assert!(robots.is_allowed("/", "a"));
+        assert!(!robots.is_allowed("/", "b"));
+    }
+}
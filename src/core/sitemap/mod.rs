@@ -0,0 +1,407 @@
+//! Sitemap Module
+//!
+//! This module provides sitemap discovery and parsing for quick URL inventories.
+//! It can list every URL a site declares without crawling any page bodies.
+//!
+//! # Overview
+//!
+//! Sitemap discovery works in three steps:
+//! 1. **Robots.txt**: Reads `Sitemap:` lines from `/robots.txt`
+//! 2. **Sitemap indexes**: Follows `<sitemapindex>` entries to nested sitemaps
+//! 3. **URL sets**: Collects every `<url><loc>` entry, deduplicated
+//!
+//! If robots.txt declares no sitemaps, `/sitemap.xml` is tried instead.
+//!
+//! # Sitemap Formats
+//!
+//! ```text
+//! Sitemap index:                      URL set:
+//! <sitemapindex>                      <urlset>
+//!   <sitemap>                           <url>
+//!     <loc>.../sitemap1.xml</loc>         <loc>http://example.com/a</loc>
+//!   </sitemap>                          </url>
+//! </sitemapindex>                     </urlset>
+//! ```
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use spiderman::core::{CrawlConfig, Spiderman};
+//!
+//! async_std::task::block_on(async {
+//!     let spider = Spiderman::new("http://example.com");
+//!     let urls = spider.list_sitemap_urls(&CrawlConfig::default()).await.unwrap();
+//!
+//!     for url in urls {
+//!         println!("{}", url);
+//!     }
+//! });
+//! ```
+
+use super::crawl::CrawlConfig;
+use super::error::CrawlError;
+use super::link_extractor::parse_base_url;
+use super::robots::RobotsTxt;
+use super::webshooter::fetch_page;
+use super::Spiderman;
+use std::collections::{HashSet, VecDeque};
+
+/// Entries parsed from a sitemap document
+///
+/// # Fields
+///
+/// * `urls` - Page URLs from `<url>` entries (a URL set)
+/// * `sitemaps` - Nested sitemap URLs from `<sitemap>` entries (a sitemap index)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Sitemap {
+    /// Page URLs listed in the sitemap
+    pub urls: Vec<String>,
+
+    /// Nested sitemaps listed in a sitemap index
+    pub sitemaps: Vec<String>,
+}
+
+impl<'a> Spiderman<'a> {
+    /// Lists all URLs declared in the site's sitemaps without crawling pages
+    ///
+    /// Reads the `Sitemap:` lines from the seed host's robots.txt (falling
+    /// back to `/sitemap.xml`), follows nested sitemap indexes and returns
+    /// every page URL found, deduplicated in discovery order.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - Crawl configuration (used for verbose output)
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<String>)` - All unique page URLs found in the sitemaps
+    /// * `Err(CrawlError::InvalidSeed)` - If the seed URL is malformed
+    ///
+    /// Sitemaps that fail to fetch or return a non-2xx status are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use spiderman::core::{CrawlConfig, Spiderman};
+    ///
+    /// async_std::task::block_on(async {
+    ///     let spider = Spiderman::new("example.com");
+    ///     let urls = spider.list_sitemap_urls(&CrawlConfig::default()).await.unwrap();
+    ///     println!("Found {} URLs", urls.len());
+    /// });
+    /// ```
+    pub async fn list_sitemap_urls(&self, config: &CrawlConfig) -> Result<Vec<String>, CrawlError> {
+        let seed = config.validate_seed(self.url)?;
+        let origin = site_origin(&seed).ok_or_else(|| CrawlError::InvalidSeed {
+            url: self.url.to_string(),
+            reason: "empty host".to_string(),
+        })?;
+
+        let options = config.fetch_options();
+
+        // Sitemaps declared in robots.txt, or the conventional location
+        let robots_url = format!("{}/robots.txt", origin);
+        let mut queue: VecDeque<String> = match fetch_page(&robots_url, &options).await {
+            Ok(response) if response.is_success() => sitemaps_from_robots(&response.body).into(),
+            _ => VecDeque::new(),
+        };
+        if queue.is_empty() {
+            queue.push_back(format!("{}/sitemap.xml", origin));
+        }
+
+        let mut seen_sitemaps = HashSet::new();
+        let mut seen_urls = HashSet::new();
+        let mut urls = Vec::new();
+
+        while let Some(sitemap_url) = queue.pop_front() {
+            if !seen_sitemaps.insert(sitemap_url.clone()) {
+                continue;
+            }
+
+            if config.verbose {
+                println!("🗺️  Reading sitemap: {}", sitemap_url);
+            }
+
+            let response = match fetch_page(&sitemap_url, &options).await {
+                Ok(response) if response.is_success() => response,
+                Ok(response) => {
+                    if config.verbose {
+                        eprintln!("  └─ ✗ HTTP status {}", response.status);
+                    }
+                    continue;
+                }
+                Err(e) => {
+                    if config.verbose {
+                        eprintln!("  └─ ✗ Error: {}", e);
+                    }
+                    continue;
+                }
+            };
+
+            let sitemap = parse_sitemap(&response.body);
+            queue.extend(sitemap.sitemaps);
+
+            for url in sitemap.urls {
+                if seen_urls.insert(url.clone()) {
+                    urls.push(url);
+                }
+            }
+        }
+
+        Ok(urls)
+    }
+}
+
+/// Parses a sitemap or sitemap index document
+///
+/// # Arguments
+///
+/// * `xml` - The sitemap XML content
+///
+/// # Returns
+///
+/// A `Sitemap` with the page URLs and nested sitemap URLs it lists
+///
+/// # Examples
+///
+/// ```
+/// use spiderman::core::sitemap::parse_sitemap;
+///
+/// let xml = r#"<urlset><url><loc>http://example.com/a</loc></url></urlset>"#;
+/// let sitemap = parse_sitemap(xml);
+/// assert_eq!(sitemap.urls, vec!["http://example.com/a".to_string()]);
+/// ```
+pub fn parse_sitemap(xml: &str) -> Sitemap {
+    Sitemap {
+        urls: extract_locs(xml, "url"),
+        sitemaps: extract_locs(xml, "sitemap"),
+    }
+}
+
+/// Extracts the sitemap URLs declared in a robots.txt file
+///
+/// # Arguments
+///
+/// * `robots_txt` - The robots.txt content
+///
+/// # Returns
+///
+/// The values of all `Sitemap:` lines, in order
+///
+/// # Examples
+///
+/// ```
+/// use spiderman::core::sitemap::sitemaps_from_robots;
+///
+/// let robots = "User-agent: *\nDisallow: /admin\nSitemap: http://example.com/sitemap.xml";
+/// assert_eq!(
+///     sitemaps_from_robots(robots),
+///     vec!["http://example.com/sitemap.xml".to_string()]
+/// );
+/// ```
+pub fn sitemaps_from_robots(robots_txt: &str) -> Vec<String> {
+    RobotsTxt::parse(robots_txt).sitemaps().to_vec()
+}
+
+/// Extracts the `<loc>` values of all entries with the given tag name
+fn extract_locs(xml: &str, entry_tag: &str) -> Vec<String> {
+    let entry_re =
+        regex::Regex::new(&format!(r"(?is)<{0}(?:\s[^>]*)?>(.*?)</{0}>", entry_tag)).unwrap();
+    let loc_re = regex::Regex::new(r"(?is)<loc>\s*(.*?)\s*</loc>").unwrap();
+
+    entry_re
+        .captures_iter(xml)
+        .filter_map(|entry| {
+            let body = entry.get(1)?.as_str();
+            loc_re
+                .captures(body)
+                .and_then(|cap| cap.get(1))
+                .map(|m| m.as_str().replace("&amp;", "&"))
+        })
+        .collect()
+}
+
+/// Returns the `scheme://host` origin of a URL (defaulting to HTTP)
+fn site_origin(url: &str) -> Option<String> {
+    let url = if url.contains("://") {
+        url.to_string()
+    } else {
+        format!("http://{}", url)
+    };
+
+    let (protocol, host, _) = parse_base_url(&url)?;
+    if host.is_empty() {
+        return None;
+    }
+
+    Some(format!("{}://{}", protocol, host))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_server::{response, TestServer};
+
+    fn xml(body: &str) -> Vec<u8> {
+        response(200, &[("Content-Type", "application/xml")], body)
+    }
+
+    // ===== Parsing Tests =====
+
+    #[test]
+    fn test_parse_sitemap_urlset() {
+        let xml = r#"
+            <?xml version="1.0" encoding="UTF-8"?>
+            <urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+                <url><loc>http://example.com/</loc><lastmod>2024-01-01</lastmod></url>
+                <url>
+                    <loc> http://example.com/page?a=1&amp;b=2 </loc>
+                </url>
+            </urlset>
+        "#;
+
+        let sitemap = parse_sitemap(xml);
+
+        assert_eq!(
+            sitemap.urls,
+            vec![
+                "http://example.com/".to_string(),
+                "http://example.com/page?a=1&b=2".to_string()
+            ]
+        );
+        assert!(sitemap.sitemaps.is_empty());
+    }
+
+    #[test]
+    fn test_parse_sitemap_index() {
+        let xml = r#"
+            <sitemapindex xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+                <sitemap><loc>http://example.com/sitemap1.xml</loc></sitemap>
+                <sitemap><loc>http://example.com/sitemap2.xml</loc></sitemap>
+            </sitemapindex>
+        "#;
+
+        let sitemap = parse_sitemap(xml);
+
+        assert!(sitemap.urls.is_empty());
+        assert_eq!(sitemap.sitemaps.len(), 2);
+        assert_eq!(sitemap.sitemaps[1], "http://example.com/sitemap2.xml");
+    }
+
+    #[test]
+    fn test_parse_sitemap_empty() {
+        assert_eq!(parse_sitemap(""), Sitemap::default());
+    }
+
+    #[test]
+    fn test_sitemaps_from_robots() {
+        let robots = "User-agent: *\n\
+                      Disallow: /private\n\
+                      sitemap: http://example.com/a.xml\n\
+                      Sitemap:http://example.com/b.xml\n\
+                      Sitemap:\n";
+
+        assert_eq!(
+            sitemaps_from_robots(robots),
+            vec![
+                "http://example.com/a.xml".to_string(),
+                "http://example.com/b.xml".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_site_origin() {
+        assert_eq!(
+            site_origin("example.com/page"),
+            Some("http://example.com".to_string())
+        );
+        assert_eq!(
+            site_origin("https://example.com:8443/a/b"),
+            Some("https://example.com:8443".to_string())
+        );
+        assert_eq!(site_origin(""), None);
+    }
+
+    // ===== Discovery Tests =====
+
+    #[test]
+    fn test_list_sitemap_urls_follows_robots_and_index() {
+        let server = TestServer::start(vec![
+            (
+                "/sitemap-a.xml",
+                xml("<urlset><url><loc>http://example.com/1</loc></url>\
+                     <url><loc>http://example.com/2</loc></url></urlset>"),
+            ),
+            (
+                "/sitemap-b.xml",
+                xml("<urlset><url><loc>http://example.com/2</loc></url>\
+                     <url><loc>http://example.com/3</loc></url></urlset>"),
+            ),
+        ]);
+        server.route(
+            "/robots.txt",
+            response(
+                200,
+                &[],
+                &format!("User-agent: *\nSitemap: {}\n", server.url("/index.xml")),
+            ),
+        );
+        server.route(
+            "/index.xml",
+            xml(&format!(
+                "<sitemapindex>\
+                    <sitemap><loc>{}</loc></sitemap>\
+                    <sitemap><loc>{}</loc></sitemap>\
+                 </sitemapindex>",
+                server.url("/sitemap-a.xml"),
+                server.url("/sitemap-b.xml")
+            )),
+        );
+
+        let seed = server.url("/");
+        let urls = async_std::task::block_on(async {
+            let spider = Spiderman::new(&seed);
+            let config = CrawlConfig::default().with_verbose(false);
+            spider.list_sitemap_urls(&config).await.unwrap()
+        });
+
+        assert!(server.request_for("/sitemap.xml").is_none());
+        assert_eq!(
+            urls,
+            vec![
+                "http://example.com/1".to_string(),
+                "http://example.com/2".to_string(),
+                "http://example.com/3".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_list_sitemap_urls_falls_back_to_default_location() {
+        let server = TestServer::start(vec![(
+            "/sitemap.xml",
+            xml("<urlset><url><loc>http://example.com/only</loc></url></urlset>"),
+        )]);
+
+        let seed = server.url("/");
+        let urls = async_std::task::block_on(async {
+            let spider = Spiderman::new(&seed);
+            let config = CrawlConfig::default().with_verbose(false);
+            spider.list_sitemap_urls(&config).await.unwrap()
+        });
+
+        assert_eq!(urls, vec!["http://example.com/only".to_string()]);
+    }
+
+    #[test]
+    fn test_list_sitemap_urls_rejects_invalid_seed() {
+        let err = async_std::task::block_on(async {
+            let spider = Spiderman::new("htp://example");
+            let config = CrawlConfig::default().with_verbose(false);
+            spider.list_sitemap_urls(&config).await.unwrap_err()
+        });
+
+        assert!(matches!(err, CrawlError::InvalidSeed { .. }), "{:?}", err);
+    }
+}
@@ -0,0 +1,215 @@
+//! Test Server
+//!
+//! A minimal HTTP server used by the unit tests. It serves canned responses
+//! from `127.0.0.1` on a random port so crawl behavior can be tested without
+//! network access, and records every request it receives.
+
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// A local HTTP server answering each path with a fixed raw response
+pub(crate) struct TestServer {
+    /// Port the server is listening on
+    port: u16,
+
+    /// Raw response for each path
+    routes: Arc<Mutex<HashMap<String, Vec<u8>>>>,
+
+    /// Raw requests received so far (request line + headers)
+    requests: Arc<Mutex<Vec<String>>>,
+
+    /// Connection statistics and simulated latency
+    load: Arc<Load>,
+}
+
+/// Tracks how many requests are being handled at once
+#[derive(Default)]
+struct Load {
+    /// Delay before each response is written
+    latency: Mutex<Duration>,
+
+    /// Extra delay for specific paths
+    path_latency: Mutex<HashMap<String, Duration>>,
+
+    /// Requests currently being handled
+    in_flight: AtomicUsize,
+
+    /// Highest number of requests handled at once
+    max_in_flight: AtomicUsize,
+}
+
+impl TestServer {
+    /// Starts a server that answers each path with the given raw HTTP response
+    ///
+    /// Paths without a route get a `404 Not Found` response.
+    pub(crate) fn start(routes: Vec<(&str, Vec<u8>)>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let routes: Arc<Mutex<HashMap<String, Vec<u8>>>> = Arc::new(Mutex::new(
+            routes
+                .into_iter()
+                .map(|(path, response)| (path.to_string(), response))
+                .collect(),
+        ));
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let load = Arc::new(Load::default());
+
+        let server_routes = Arc::clone(&routes);
+        let log = Arc::clone(&requests);
+        let server_load = Arc::clone(&load);
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let routes = Arc::clone(&server_routes);
+                let log = Arc::clone(&log);
+                let load = Arc::clone(&server_load);
+                thread::spawn(move || handle_connection(stream, &routes, &log, &load));
+            }
+        });
+
+        Self {
+            port,
+            routes,
+            requests,
+            load,
+        }
+    }
+
+    /// Delays every response by the given duration
+    pub(crate) fn set_latency(&self, latency: Duration) {
+        *self.load.latency.lock().unwrap() = latency;
+    }
+
+    /// Delays responses for one path by the given duration (on top of any
+    /// latency set with `set_latency`)
+    pub(crate) fn set_path_latency(&self, path: &str, latency: Duration) {
+        self.load
+            .path_latency
+            .lock()
+            .unwrap()
+            .insert(path.to_string(), latency);
+    }
+
+    /// Returns the highest number of requests handled at the same time
+    pub(crate) fn max_in_flight(&self) -> usize {
+        self.load.max_in_flight.load(Ordering::SeqCst)
+    }
+
+    /// Adds or replaces the response for a path
+    ///
+    /// Useful when a response needs to contain the server's own URL.
+    pub(crate) fn route(&self, path: &str, response: Vec<u8>) {
+        self.routes
+            .lock()
+            .unwrap()
+            .insert(path.to_string(), response);
+    }
+
+    /// Returns the absolute URL for a path on this server
+    pub(crate) fn url(&self, path: &str) -> String {
+        format!("http://127.0.0.1:{}{}", self.port, path)
+    }
+
+    /// Returns all raw requests received so far
+    pub(crate) fn requests(&self) -> Vec<String> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// Returns the raw request received for a path, if any
+    pub(crate) fn request_for(&self, path: &str) -> Option<String> {
+        let prefix = format!("GET {} ", path);
+        self.requests()
+            .into_iter()
+            .find(|request| request.starts_with(&prefix))
+    }
+}
+
+/// Reads one request and writes back the matching response
+fn handle_connection(
+    mut stream: TcpStream,
+    routes: &Mutex<HashMap<String, Vec<u8>>>,
+    log: &Mutex<Vec<String>>,
+    load: &Load,
+) {
+    let request = read_request(&mut stream);
+    let path = request.split_whitespace().nth(1).unwrap_or("/").to_string();
+    log.lock().unwrap().push(request);
+
+    let in_flight = load.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+    load.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+    let latency = *load.latency.lock().unwrap()
+        + load
+            .path_latency
+            .lock()
+            .unwrap()
+            .get(&path)
+            .copied()
+            .unwrap_or_default();
+    if !latency.is_zero() {
+        thread::sleep(latency);
+    }
+
+    let response = routes
+        .lock()
+        .unwrap()
+        .get(&path)
+        .cloned()
+        .unwrap_or_else(|| response(404, &[], "Not Found"));
+    let _ = stream.write_all(&response);
+    load.in_flight.fetch_sub(1, Ordering::SeqCst);
+}
+
+/// Reads request bytes until the end of the headers
+fn read_request(stream: &mut TcpStream) -> String {
+    let mut request = Vec::new();
+    let mut buf = [0u8; 1024];
+
+    while !request.ends_with(b"\r\n\r\n") {
+        match stream.read(&mut buf) {
+            Ok(0) | Err(_) => break,
+            Ok(n) => request.extend_from_slice(&buf[..n]),
+        }
+    }
+
+    String::from_utf8_lossy(&request).to_string()
+}
+
+/// Builds a raw HTTP response with the given status, headers and body
+pub(crate) fn response(status: u16, headers: &[(&str, &str)], body: &str) -> Vec<u8> {
+    response_bytes(status, headers, body.as_bytes())
+}
+
+/// Builds a raw HTTP response with a non-UTF-8 (or binary) body
+pub(crate) fn response_bytes(status: u16, headers: &[(&str, &str)], body: &[u8]) -> Vec<u8> {
+    let reason = match status {
+        200 => "OK",
+        301 => "Moved Permanently",
+        302 => "Found",
+        304 => "Not Modified",
+        404 => "Not Found",
+        429 => "Too Many Requests",
+        500 => "Internal Server Error",
+        503 => "Service Unavailable",
+        _ => "Unknown",
+    };
+
+    let mut head = format!("HTTP/1.1 {} {}\r\n", status, reason);
+    for (name, value) in headers {
+        head.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    head.push_str(&format!("Content-Length: {}\r\n\r\n", body.len()));
+
+    let mut bytes = head.into_bytes();
+    bytes.extend_from_slice(body);
+    bytes
+}
+
+/// Builds a `200 OK` HTML response
+pub(crate) fn html(body: &str) -> Vec<u8> {
+    response(200, &[("Content-Type", "text/html")], body)
+}
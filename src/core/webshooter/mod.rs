@@ -1,37 +1,292 @@
+//! Fetches HTML content from a given URL using raw TCP connections
+//!
+//! This module provides web fetching functionality for the Spiderman web crawler.
+//! It handles HTTP requests by manually implementing the HTTP protocol over TCP.
+//!
+//! # Implementation Details
+//!
+//! The fetching is done using raw TCP sockets with manual HTTP request construction:
+//! - Establishes TCP connection to the host
+//! - Sends HTTP GET request with proper headers
+//! - Parses HTTP response headers
+//! - Extracts the response body and decodes it using the `Content-Type`
+//!   charset (UTF-8 when none is given)
+//!
+//! # Limitations
+//!
+//! - Only supports HTTP (port 80 unless the URL names a port or
+//!   `FetchOptions::default_port` is set), not HTTPS
+//! - Redirects are only followed by `fetch_following_redirects`
+//! - Basic HTTP/1.1 implementation (`HTTP/1.0` responses are accepted too;
+//!   the body is always read until the server closes the connection)
+//! - No support for chunked transfer encoding (uses Connection: close)
+//!
+//! # Errors
+//!
+//! Fetching will return an error if:
+//! - The URL format is invalid (missing host or path)
+//! - DNS resolution fails (`CrawlError::DnsFailure`)
+//! - TCP connection cannot be established (`CrawlError::ConnectionRefused`
+//!   when the host refuses it)
+//! - The connection is dropped mid-request (`CrawlError::ConnectionReset`)
+//! - A redirect points back at the URL that returned it
+//!   (`CrawlError::RedirectLoop`)
+//! - HTTP request/response parsing fails
+//! - Network I/O errors occur
+
+use super::document::RobotsDirectives;
+use super::error::CrawlError;
+use super::link_extractor::normalize_url;
 use super::Spiderman;
 use async_std::{
+    fs::OpenOptions,
     io::{BufReader, ReadExt, WriteExt},
-    net::TcpStream,
+    net::{TcpStream, ToSocketAddrs},
+    sync::Mutex,
 };
+use chrono::{DateTime, Utc};
+use encoding_rs::{Encoding, UTF_8};
+use regex::Regex;
+use std::collections::HashMap;
+use std::io;
+use std::sync::LazyLock;
+use std::time::Duration;
+
+/// Default maximum number of redirects followed for a single fetch
+pub const DEFAULT_MAX_REDIRECTS: usize = 10;
+
+/// Default number of times a 429/503 response with `Retry-After` is retried
+pub const DEFAULT_MAX_RETRIES: usize = 1;
+
+/// Default upper bound on a `Retry-After` wait
+pub const DEFAULT_MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+/// `Accept` header sent unless the request sets its own
+pub const DEFAULT_ACCEPT: &str = "text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8";
+
+/// A parsed HTTP response
+///
+/// # Fields
+///
+/// * `final_url` - The URL that produced this response (the last hop when
+///   redirects were followed)
+/// * `status` - The HTTP status code (e.g. 200, 404)
+/// * `headers` - Response headers, keyed by lowercase header name
+/// * `body` - The response body, decoded to UTF-8
+/// * `raw_body` - The response body bytes as received
+#[derive(Debug, Clone)]
+pub struct FetchResponse {
+    /// URL the response was fetched from, after redirects
+    pub final_url: String,
+
+    /// HTTP status code
+    pub status: u16,
+
+    /// Response headers (lowercase names)
+    pub headers: HashMap<String, String>,
+
+    /// Response body (decoded text)
+    pub body: String,
+
+    /// Response body (undecoded bytes, e.g. for binary assets)
+    pub raw_body: Vec<u8>,
+}
+
+/// Options controlling how a page is fetched
+///
+/// # Fields
+///
+/// * `headers` - Additional `(name, value)` request headers (e.g. `Referer`)
+/// * `default_port` - Port used when the URL names none (None = 80)
+/// * `max_redirects` - Maximum number of redirects followed by
+///   `fetch_following_redirects`
+/// * `http_log` - Optional file that request and response headers are
+///   appended to
+/// * `max_retries` - How often a 429/503 response with `Retry-After` is
+///   retried after waiting
+/// * `max_retry_after` - Longest `Retry-After` wait honored (longer waits
+///   are shortened to it)
+/// * `respect_meta_charset` - Whether a `<meta charset>` that contradicts
+///   the `Content-Type` charset wins
+#[derive(Debug, Clone)]
+pub struct FetchOptions {
+    /// Additional request headers
+    pub headers: Vec<(String, String)>,
+
+    /// Port for URLs without an explicit port (None = 80)
+    pub default_port: Option<u16>,
+
+    /// Maximum number of redirects to follow
+    pub max_redirects: usize,
+
+    /// File the request and response headers of every fetch are appended
+    /// to (None = no logging)
+    pub http_log: Option<String>,
+
+    /// Maximum number of `Retry-After` retries per request
+    pub max_retries: usize,
+
+    /// Cap on each `Retry-After` wait
+    pub max_retry_after: Duration,
+
+    /// Whether to re-decode HTML bodies whose `<meta charset>` contradicts
+    /// the header charset
+    pub respect_meta_charset: bool,
+}
+
+impl Default for FetchOptions {
+    fn default() -> Self {
+        Self {
+            headers: Vec::new(),
+            default_port: None,
+            max_redirects: DEFAULT_MAX_REDIRECTS,
+            http_log: None,
+            max_retries: DEFAULT_MAX_RETRIES,
+            max_retry_after: DEFAULT_MAX_RETRY_AFTER,
+            respect_meta_charset: false,
+        }
+    }
+}
+
+impl FetchOptions {
+    /// Adds a request header
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+}
+
+impl FetchResponse {
+    /// Returns a header value by name (case-insensitive)
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(&name.to_lowercase()).map(|s| s.as_str())
+    }
+
+    /// Returns true for 2xx status codes
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    /// Returns the directives of the `X-Robots-Tag` header
+    ///
+    /// Directives prefixed with a user agent (`googlebot: noindex`) are
+    /// aimed at other crawlers and ignored.
+    pub fn robots_directives(&self) -> RobotsDirectives {
+        self.header("x-robots-tag")
+            .map(|value| {
+                let general: Vec<&str> = value
+                    .split(',')
+                    .filter(|directive| !directive.contains(':'))
+                    .collect();
+                RobotsDirectives::parse(&general.join(","))
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns the media type of the `Content-Type` header, lowercased and
+    /// without parameters (e.g. `"text/html"`)
+    pub fn mime_type(&self) -> Option<String> {
+        self.header("content-type")
+            .and_then(|value| value.split(';').next())
+            .map(|mime| mime.trim().to_lowercase())
+            .filter(|mime| !mime.is_empty())
+    }
+
+    /// Returns true if the response is an HTML page
+    ///
+    /// Responses without a `Content-Type` are assumed to be HTML.
+    pub fn is_html(&self) -> bool {
+        match self.mime_type() {
+            Some(mime) => mime == "text/html" || mime == "application/xhtml+xml",
+            None => true,
+        }
+    }
+
+    /// Returns true if the response is JSON or a syndication feed
+    ///
+    /// Matches `application/json`, `application/*+json` and the RSS and
+    /// Atom types. Such bodies are data rather than pages, so the crawl
+    /// stores them verbatim instead of converting them. Generic XML
+    /// (`text/xml`) is not included, since it is often served for pages.
+    pub fn is_data(&self) -> bool {
+        const DATA_TYPES: [&str; 5] = [
+            "application/json",
+            "application/*+json",
+            "text/json",
+            "application/rss+xml",
+            "application/atom+xml",
+        ];
+
+        self.mime_type()
+            .is_some_and(|mime| DATA_TYPES.iter().any(|rule| mime_type_matches(rule, &mime)))
+    }
+
+    /// Returns true if the response's media type matches any of the rules
+    ///
+    /// Rules are media types with optional `*` wildcards (see
+    /// `mime_type_matches`). Responses without a `Content-Type` are treated
+    /// as `text/html`, like in `is_html`.
+    pub fn matches_content_type(&self, rules: &[String]) -> bool {
+        let mime = self.mime_type().unwrap_or_else(|| "text/html".to_string());
+        rules.iter().any(|rule| mime_type_matches(rule, &mime))
+    }
+
+    /// Returns true for redirect status codes (301, 302, 303, 307, 308)
+    pub fn is_redirect(&self) -> bool {
+        matches!(self.status, 301 | 302 | 303 | 307 | 308)
+    }
+
+    /// Returns how long to wait before retrying a 429 or 503 response
+    ///
+    /// Read from the `Retry-After` header (see `parse_retry_after`). Other
+    /// statuses, and responses without a valid header, return `None`.
+    pub fn retry_after(&self) -> Option<Duration> {
+        if !matches!(self.status, 429 | 503) {
+            return None;
+        }
+        parse_retry_after(self.header("retry-after")?, Utc::now())
+    }
+}
 
-/// Fetches HTML content from a given URL using raw TCP connections
+/// Parses a `Retry-After` header value into a wait duration
 ///
-/// This module provides web fetching functionality for the Spiderman web crawler.
-/// It handles HTTP requests by manually implementing the HTTP protocol over TCP.
+/// Accepts both forms of the header: a number of seconds (`120`) and an
+/// HTTP date (`Wed, 21 Oct 2015 07:28:00 GMT`). A date in the past means
+/// no wait.
 ///
-/// # Implementation Details
+/// # Arguments
 ///
-/// The fetching is done using raw TCP sockets with manual HTTP request construction:
-/// - Establishes TCP connection to the host
-/// - Sends HTTP GET request with proper headers
-/// - Parses HTTP response headers
-/// - Extracts and returns the response body
+/// * `value` - The header value
+/// * `now` - The current time, which HTTP dates are relative to
 ///
-/// # Limitations
+/// # Returns
+///
+/// The time to wait, or `None` if the value is not valid
 ///
-/// - Only supports HTTP (port 80), not HTTPS
-/// - Does not follow redirects automatically
-/// - Basic HTTP/1.1 implementation
-/// - No support for chunked transfer encoding (uses Connection: close)
+/// # Examples
 ///
-/// # Errors
+/// ```
+/// use chrono::Utc;
+/// use spiderman::core::webshooter::parse_retry_after;
+/// use std::time::Duration;
 ///
-/// This function will return an error if:
-/// - The URL format is invalid (missing host or path)
-/// - DNS resolution fails
-/// - TCP connection cannot be established
-/// - HTTP request/response parsing fails
-/// - Network I/O errors occur
+/// assert_eq!(parse_retry_after("5", Utc::now()), Some(Duration::from_secs(5)));
+/// assert_eq!(parse_retry_after("soon", Utc::now()), None);
+/// ```
+pub fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<Duration> {
+    let value = value.trim();
+
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    Some(
+        (date.with_timezone(&Utc) - now)
+            .to_std()
+            .unwrap_or_default(),
+    )
+}
 
 impl<'a> Spiderman<'a> {
     /// Fetches HTML content from the URL and stores it in the struct
@@ -69,40 +324,296 @@ impl<'a> Spiderman<'a> {
     ///     }
     /// });
     /// ```
-    pub(crate) async fn fetch(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        // Parse the URL to extract host and path
-        let (host, path) = parse_url(self.url)?;
-
-        // Connect to the host on port 80 (HTTP)
-        let address = format!("{}:80", host);
-        let mut stream = TcpStream::connect(&address).await?;
-
-        // Build HTTP GET request
-        let request = format!(
-            "GET {} HTTP/1.1\r\n\
-             Host: {}\r\n\
-             User-Agent: Spiderman/0.1.0 (Rust Web Crawler)\r\n\
-             Accept: text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8\r\n\
-             Connection: close\r\n\
-             \r\n",
-            path, host
-        );
+    pub async fn fetch(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        // Fetch the page with the default options
+        let response = fetch_page(self.url, &FetchOptions::default()).await?;
 
-        // Send the HTTP request
-        stream.write_all(request.as_bytes()).await?;
-        stream.flush().await?;
+        // Store the fetched HTML
+        self.html = Some(response.body);
+        Ok(())
+    }
+}
 
-        // Read the response
-        let mut reader = BufReader::new(stream);
-        let mut response = String::new();
-        reader.read_to_string(&mut response).await?;
+/// Fetches a URL and returns the parsed response
+///
+/// This is the shared fetch path used by both `Spiderman::fetch()` and the
+/// crawl loop. Extra headers are appended to the default request headers.
+///
+/// # Arguments
+///
+/// * `url` - The URL to fetch
+/// * `options` - Extra headers and connection settings
+///
+/// # Returns
+///
+/// * `Ok(FetchResponse)` - The response status, headers and body (any status)
+/// * `Err` - If the URL is invalid, any network error occurred or the
+///   response could not be parsed
+pub(crate) async fn fetch_page(
+    url: &str,
+    options: &FetchOptions,
+) -> Result<FetchResponse, Box<dyn std::error::Error>> {
+    // Parse the URL to extract host and path
+    let (host, path) = parse_url(url)?;
 
-        // Extract the body from the HTTP response
-        let html = extract_body(&response)?;
+    // Connect to the host (default port unless the URL names one)
+    let address = connect_address(&host, options.default_port);
+    let mut stream = connect(&host, &address).await?;
 
-        // Store the fetched HTML
-        self.html = Some(html);
-        Ok(())
+    // Build and send the HTTP GET request
+    let headers: Vec<(&str, &str)> = options
+        .headers
+        .iter()
+        .map(|(name, value)| (name.as_str(), value.as_str()))
+        .collect();
+    let request = build_request(&host, &path, &headers);
+    stream
+        .write_all(request.as_bytes())
+        .await
+        .map_err(|e| connection_error(&host, e))?;
+    stream
+        .flush()
+        .await
+        .map_err(|e| connection_error(&host, e))?;
+
+    // Read the raw response (decoded once the charset is known)
+    let mut reader = BufReader::new(stream);
+    let mut response = Vec::new();
+    reader
+        .read_to_end(&mut response)
+        .await
+        .map_err(|e| connection_error(&host, e))?;
+
+    if let Some(ref path) = options.http_log {
+        // Logging is a debugging aid; a failure to log doesn't fail the fetch
+        if let Err(e) = log_exchange(path, &request, &response).await {
+            eprintln!("⚠️  HTTP log error: {}", e);
+        }
+    }
+
+    // Parse status, headers and body
+    let mut response = parse_response(&response)?;
+    response.final_url = url.to_string();
+    if options.respect_meta_charset {
+        redecode_with_meta_charset(&mut response);
+    }
+    Ok(response)
+}
+
+/// Serializes appends to HTTP log files, so entries from concurrent fetches
+/// don't interleave (an async lock, so waiting doesn't block the executor)
+static HTTP_LOG_LOCK: Mutex<()> = Mutex::new(());
+
+/// Appends a request and the head of its response to the HTTP log
+///
+/// Request lines are prefixed with `> `, response lines with `< `, and
+/// each exchange ends with an empty line. Response bodies are not logged.
+async fn log_exchange(path: &str, request: &str, response: &[u8]) -> io::Result<()> {
+    let head_len =
+        extract_body(response).map_or(response.len(), |body| response.len() - body.len());
+    let head = String::from_utf8_lossy(&response[..head_len]);
+
+    let mut entry = String::new();
+    for line in request.lines().filter(|line| !line.is_empty()) {
+        entry.push_str(&format!("> {}\n", line));
+    }
+    for line in head.lines().filter(|line| !line.trim().is_empty()) {
+        entry.push_str(&format!("< {}\n", line));
+    }
+    entry.push('\n');
+
+    let _guard = HTTP_LOG_LOCK.lock().await;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .await?;
+    file.write_all(entry.as_bytes()).await?;
+    file.flush().await
+}
+
+/// Fetches a URL, following redirects
+///
+/// Each `Location` header is resolved against the URL that returned it.
+/// A redirect response without a `Location` header is returned as-is.
+/// A 429 or 503 response with a `Retry-After` header is fetched again
+/// after the requested wait (capped at `max_retry_after`), up to
+/// `max_retries` times.
+///
+/// # Arguments
+///
+/// * `url` - The URL to fetch
+/// * `options` - Headers (sent on every hop), connection settings, and the
+///   redirect and retry limits
+///
+/// # Returns
+///
+/// * `Ok((chain, FetchResponse))` - Every URL requested, starting with `url`
+///   and ending with the URL that produced the final response, and that
+///   response (whose `final_url` is the chain's last URL)
+/// * `Err` - If any hop fails, a `Location` is invalid, a URL redirects
+///   to itself (`CrawlError::RedirectLoop`) or more than `max_redirects`
+///   redirects are followed
+pub(crate) async fn fetch_following_redirects(
+    url: &str,
+    options: &FetchOptions,
+) -> Result<(Vec<String>, FetchResponse), Box<dyn std::error::Error>> {
+    let max_redirects = options.max_redirects;
+    let mut chain = vec![url.to_string()];
+    let mut retries = 0;
+
+    loop {
+        let current = chain.last().unwrap();
+        let response = fetch_page(current, options).await?;
+
+        // Back off as the server asks, then request the same URL again
+        if let Some(wait) = response
+            .retry_after()
+            .filter(|_| retries < options.max_retries)
+        {
+            async_std::task::sleep(wait.min(options.max_retry_after)).await;
+            retries += 1;
+            continue;
+        }
+
+        let location = match response.header("location") {
+            Some(location) if response.is_redirect() => location,
+            _ => return Ok((chain, response)),
+        };
+
+        if chain.len() > max_redirects {
+            return Err(format!("Too many redirects (more than {})", max_redirects).into());
+        }
+
+        let next = normalize_url(location, current)
+            .ok_or_else(|| format!("Invalid redirect location: {}", location))?;
+
+        // A URL redirecting to itself would loop until `max_redirects`.
+        // The resolved URLs are compared as-is: a redirect that only adds a
+        // trailing slash is a common, legitimate canonicalization.
+        if next == *current {
+            return Err(Box::new(CrawlError::RedirectLoop {
+                url: current.clone(),
+            }));
+        }
+        chain.push(next);
+    }
+}
+
+/// Builds the raw HTTP GET request for a host and path
+///
+/// # Arguments
+///
+/// * `host` - The host (with optional port) for the `Host` header
+/// * `path` - The request path (and query); unsafe characters are
+///   percent-encoded
+/// * `headers` - Additional `(name, value)` headers to include; an
+///   `Accept` header here replaces `DEFAULT_ACCEPT`
+///
+/// # Returns
+///
+/// The complete request string, terminated by an empty line
+fn build_request(host: &str, path: &str, headers: &[(&str, &str)]) -> String {
+    let path = encode_request_target(path);
+    let mut request = format!(
+        "GET {} HTTP/1.1\r\n\
+         Host: {}\r\n\
+         User-Agent: Spiderman/0.1.0 (Rust Web Crawler)\r\n",
+        path, host
+    );
+
+    if !headers
+        .iter()
+        .any(|(name, _)| name.eq_ignore_ascii_case("accept"))
+    {
+        request.push_str(&format!("Accept: {}\r\n", DEFAULT_ACCEPT));
+    }
+
+    for (name, value) in headers {
+        request.push_str(&format!("{}: {}\r\n", name, value));
+    }
+
+    request.push_str("Connection: close\r\n\r\n");
+    request
+}
+
+/// Percent-encodes characters that aren't allowed in a request target
+///
+/// Spaces, control characters, non-ASCII bytes and characters such as `"`,
+/// `<` or `{` are encoded; URL delimiters (`/`, `?`, `&`, `=`, ...) are
+/// kept. Existing `%XX` escapes are left as they are, so encoding twice
+/// changes nothing.
+fn encode_request_target(target: &str) -> String {
+    let bytes = target.as_bytes();
+    let mut encoded = String::with_capacity(target.len());
+
+    for (i, &byte) in bytes.iter().enumerate() {
+        let is_escape = byte == b'%'
+            && bytes.get(i + 1).is_some_and(u8::is_ascii_hexdigit)
+            && bytes.get(i + 2).is_some_and(u8::is_ascii_hexdigit);
+        let allowed = byte.is_ascii_alphanumeric() || b"-._~!$&'()*+,;=:@/?".contains(&byte);
+
+        if allowed || is_escape {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+
+    encoded
+}
+
+/// Resolves and connects to an address, categorizing failures
+///
+/// A failed lookup becomes `CrawlError::DnsFailure`; connection errors are
+/// mapped by `connection_error`.
+async fn connect(host: &str, address: &str) -> Result<TcpStream, CrawlError> {
+    let dns_failure = |source| CrawlError::DnsFailure {
+        host: host.to_string(),
+        source,
+    };
+
+    let addrs: Vec<_> = address
+        .to_socket_addrs()
+        .await
+        .map_err(dns_failure)?
+        .collect();
+    if addrs.is_empty() {
+        return Err(dns_failure(io::Error::new(
+            io::ErrorKind::NotFound,
+            "no addresses found",
+        )));
+    }
+
+    TcpStream::connect(&addrs[..])
+        .await
+        .map_err(|e| connection_error(host, e))
+}
+
+/// Maps a socket error to a specific `CrawlError` where one exists
+///
+/// Refused connections become `ConnectionRefused`; resets, aborts and
+/// broken pipes become `ConnectionReset`. Other errors stay `Io`.
+fn connection_error(host: &str, source: io::Error) -> CrawlError {
+    let host = host.to_string();
+    match source.kind() {
+        io::ErrorKind::ConnectionRefused => CrawlError::ConnectionRefused { host, source },
+        io::ErrorKind::ConnectionReset
+        | io::ErrorKind::ConnectionAborted
+        | io::ErrorKind::BrokenPipe => CrawlError::ConnectionReset { host, source },
+        _ => CrawlError::Io(source),
+    }
+}
+
+/// Returns the socket address to connect to for a host
+///
+/// Uses the port from the host if one is given (`localhost:8080`),
+/// otherwise `default_port`, falling back to the HTTP port 80.
+fn connect_address(host: &str, default_port: Option<u16>) -> String {
+    if host.contains(':') {
+        host.to_string()
+    } else {
+        format!("{}:{}", host, default_port.unwrap_or(80))
     }
 }
 
@@ -151,41 +662,251 @@ fn parse_url(url: &str) -> Result<(String, String), Box<dyn std::error::Error>>
     Ok((host, path))
 }
 
-/// Extracts the HTML body from an HTTP response
+/// Parses a raw HTTP response into status, headers and body
+///
+/// The body is transcoded to UTF-8 using the charset from the
+/// `Content-Type` header, so non-UTF-8 pages (e.g. Windows-1252) decode
+/// correctly before any metadata is extracted.
+///
+/// # Arguments
+///
+/// * `response` - The complete raw HTTP response
+///
+/// # Returns
+///
+/// * `Ok(FetchResponse)` - The parsed response
+/// * `Err` - If the status line or header/body separator is invalid
+fn parse_response(response: &[u8]) -> Result<FetchResponse, Box<dyn std::error::Error>> {
+    let body = extract_body(response)?;
+    let head = String::from_utf8_lossy(&response[..response.len() - body.len()]);
+
+    let mut lines = head.lines();
+    let status = parse_status_line(lines.next().unwrap_or(""))?;
+
+    let headers: HashMap<String, String> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_lowercase(), value.trim().to_string()))
+        .collect();
+
+    let raw_body = body.to_vec();
+    let body = decode_body(body, headers.get("content-type").map(|s| s.as_str()));
+
+    Ok(FetchResponse {
+        final_url: String::new(),
+        status,
+        headers,
+        body,
+        raw_body,
+    })
+}
+
+/// Returns the charset parameter of a `Content-Type` header value
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(charset_from_content_type("text/html; charset=\"ISO-8859-1\""), Some("ISO-8859-1"));
+/// assert_eq!(charset_from_content_type("text/html"), None);
+/// ```
+fn charset_from_content_type(content_type: &str) -> Option<&str> {
+    content_type.split(';').skip(1).find_map(|param| {
+        let (name, value) = param.split_once('=')?;
+        if name.trim().eq_ignore_ascii_case("charset") {
+            Some(value.trim().trim_matches(|c| c == '"' || c == '\''))
+        } else {
+            None
+        }
+    })
+}
+
+/// Decodes a response body to a UTF-8 string
+///
+/// Uses the charset from the `Content-Type` header when it names a known
+/// encoding, otherwise UTF-8. Invalid sequences are replaced rather than
+/// rejected.
+fn decode_body(body: &[u8], content_type: Option<&str>) -> String {
+    let encoding = content_type
+        .and_then(charset_from_content_type)
+        .and_then(|label| Encoding::for_label(label.as_bytes()))
+        .unwrap_or(UTF_8);
+
+    let (decoded, _, _) = encoding.decode(body);
+    decoded.into_owned()
+}
+
+/// Number of leading body bytes searched for a `<meta charset>`
+const META_CHARSET_PRESCAN: usize = 1024;
+
+/// Returns the encoding declared by a `<meta>` tag early in an HTML body
+///
+/// Looks at the first 1024 bytes for `<meta charset="...">` or the
+/// `http-equiv="Content-Type"` form with a `charset` parameter.
+fn meta_charset(body: &[u8]) -> Option<&'static Encoding> {
+    static META_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)<meta\s[^>]*>").unwrap());
+    static CHARSET_RE: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r#"(?i)\bcharset\s*=\s*["']?([\w.:-]+)"#).unwrap());
+
+    let prefix = String::from_utf8_lossy(&body[..body.len().min(META_CHARSET_PRESCAN)]);
+    META_RE.find_iter(&prefix).find_map(|meta| {
+        let label = CHARSET_RE.captures(meta.as_str())?;
+        Encoding::for_label(label[1].as_bytes())
+    })
+}
+
+/// Re-decodes an HTML body whose `<meta charset>` contradicts its header
+///
+/// Servers often send a blanket `charset=utf-8` for pages that are really
+/// stored in a legacy encoding; the page's own declaration is then the
+/// more reliable one. The body is decoded from the raw bytes again, so it
+/// is never decoded twice.
+fn redecode_with_meta_charset(response: &mut FetchResponse) {
+    if !response.is_html() {
+        return;
+    }
+
+    let header_encoding = response
+        .headers
+        .get("content-type")
+        .and_then(|content_type| charset_from_content_type(content_type))
+        .and_then(|label| Encoding::for_label(label.as_bytes()))
+        .unwrap_or(UTF_8);
+
+    if let Some(encoding) = meta_charset(&response.raw_body) {
+        if encoding != header_encoding {
+            let (decoded, _, _) = encoding.decode(&response.raw_body);
+            response.body = decoded.into_owned();
+        }
+    }
+}
+
+/// Matches a media type against a rule with `*` wildcards
+///
+/// The rule is compared case-insensitively and any parameters (`; charset`)
+/// are ignored. A `*` matches any run of characters within the type or the
+/// subtype, so `text/*` covers `text/html` and `text/plain`,
+/// `application/*+xml` covers `application/atom+xml`, and `*/*` matches
+/// every media type.
+///
+/// # Arguments
+///
+/// * `rule` - The media type rule (e.g. `text/*`)
+/// * `mime` - The media type to test (e.g. `text/html`)
+///
+/// # Returns
+///
+/// `true` if the media type matches the rule
+///
+/// # Examples
+///
+/// ```
+/// use spiderman::core::webshooter::mime_type_matches;
+///
+/// assert!(mime_type_matches("text/*", "text/html"));
+/// assert!(mime_type_matches("application/*+xml", "application/rss+xml"));
+/// assert!(!mime_type_matches("text/*", "application/pdf"));
+/// ```
+pub fn mime_type_matches(rule: &str, mime: &str) -> bool {
+    let essence = |value: &str| value.split(';').next().unwrap_or("").trim().to_lowercase();
+    let (rule, mime) = (essence(rule), essence(mime));
+
+    match (rule.split_once('/'), mime.split_once('/')) {
+        (Some((rule_type, rule_subtype)), Some((mime_type, mime_subtype))) => {
+            wildcard_matches(rule_type, mime_type) && wildcard_matches(rule_subtype, mime_subtype)
+        }
+        _ => false,
+    }
+}
+
+/// Matches text against a pattern where `*` matches any run of characters
+fn wildcard_matches(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or("");
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        // No wildcard: the pattern must match exactly
+        return rest.is_empty();
+    };
+
+    for part in middle {
+        match rest.find(part) {
+            Some(pos) => rest = &rest[pos + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+/// Parses the status code from an HTTP status line
+///
+/// Any `HTTP/x.y` version is accepted (old or embedded servers still
+/// answer with `HTTP/1.0`).
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(parse_status_line("HTTP/1.1 404 Not Found")?, 404);
+/// assert_eq!(parse_status_line("HTTP/1.0 200 OK")?, 200);
+/// ```
+fn parse_status_line(line: &str) -> Result<u16, Box<dyn std::error::Error>> {
+    let mut parts = line.split_whitespace();
+
+    match (parts.next(), parts.next()) {
+        (Some(version), Some(code)) if version.starts_with("HTTP/") => code
+            .parse()
+            .map_err(|_| format!("Invalid HTTP status code: {}", code).into()),
+        _ => Err(format!("Invalid HTTP status line: {}", line).into()),
+    }
+}
+
+/// Extracts the body bytes from an HTTP response
 ///
 /// Parses the HTTP response and extracts the content after the headers.
-/// The body starts after the first empty line (`\r\n\r\n` or `\n\n`).
+/// The body starts after the first empty line: whichever of `\r\n\r\n`
+/// and `\n\n` comes first, so responses mixing CRLF and bare LF line
+/// endings are split at the end of the headers.
 ///
 /// # Arguments
 ///
-/// * `response` - The complete HTTP response string
+/// * `response` - The complete raw HTTP response
 ///
 /// # Returns
 ///
-/// * `Ok(body)` - The extracted body content
+/// * `Ok(body)` - The extracted (undecoded) body content
 /// * `Err` - If the response format is invalid
 ///
 /// # Example
 ///
 /// ```
-/// let response = "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\n\r\n<html>...</html>";
-/// let body = extract_body(response)?;
-/// assert_eq!(body, "<html>...</html>");
+/// let response = b"HTTP/1.1 200 OK\r\nContent-Type: text/html\r\n\r\n<html>...</html>";
+/// let body = extract_body(response.as_bytes())?;
+/// assert_eq!(body, b"<html>...</html>");
 /// ```
-fn extract_body(response: &str) -> Result<String, Box<dyn std::error::Error>> {
+fn extract_body(response: &[u8]) -> Result<&[u8], Box<dyn std::error::Error>> {
     // Find the separator between headers and body
-    if let Some(pos) = response.find("\r\n\r\n") {
-        Ok(response[pos + 4..].to_string())
-    } else if let Some(pos) = response.find("\n\n") {
-        Ok(response[pos + 2..].to_string())
-    } else {
-        Err("Invalid HTTP response: no body separator found".into())
+    let crlf = find_bytes(response, b"\r\n\r\n").map(|pos| (pos, 4));
+    let lf = find_bytes(response, b"\n\n").map(|pos| (pos, 2));
+
+    match [crlf, lf].into_iter().flatten().min() {
+        Some((pos, len)) => Ok(&response[pos + len..]),
+        None => Err("Invalid HTTP response: no body separator found".into()),
     }
 }
 
+/// Returns the position of the first occurrence of `needle` in `haystack`
+fn find_bytes(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::test_server::{html, response, TestServer};
 
     #[test]
     fn test_parse_url_with_http_protocol() {
@@ -239,15 +960,15 @@ mod tests {
     fn test_extract_body_with_crlf() {
         let response =
             "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\n\r\n<html><body>Test</body></html>";
-        let body = extract_body(response).unwrap();
-        assert_eq!(body, "<html><body>Test</body></html>");
+        let body = extract_body(response.as_bytes()).unwrap();
+        assert_eq!(body, b"<html><body>Test</body></html>");
     }
 
     #[test]
     fn test_extract_body_with_lf() {
         let response = "HTTP/1.1 200 OK\nContent-Type: text/html\n\n<html><body>Test</body></html>";
-        let body = extract_body(response).unwrap();
-        assert_eq!(body, "<html><body>Test</body></html>");
+        let body = extract_body(response.as_bytes()).unwrap();
+        assert_eq!(body, b"<html><body>Test</body></html>");
     }
 
     #[test]
@@ -258,22 +979,613 @@ mod tests {
                        Server: TestServer\r\n\
                        \r\n\
                        <html>Content</html>";
+        let body = extract_body(response.as_bytes()).unwrap();
+        assert_eq!(body, b"<html>Content</html>");
+    }
+
+    #[test]
+    fn test_extract_body_crlf_headers_lf_separator() {
+        // The body's own CRLF blank line must not be taken for the separator
+        let response = b"HTTP/1.1 200 OK\r\nContent-Type: text/plain\n\nline 1\r\n\r\nline 2";
         let body = extract_body(response).unwrap();
-        assert_eq!(body, "<html>Content</html>");
+        assert_eq!(body, b"line 1\r\n\r\nline 2");
+
+        let parsed = parse_response(response).unwrap();
+        assert_eq!(parsed.header("content-type"), Some("text/plain"));
+        assert_eq!(parsed.body, "line 1\r\n\r\nline 2");
+    }
+
+    #[test]
+    fn test_extract_body_lf_headers_crlf_separator() {
+        let response = b"HTTP/1.1 200 OK\nContent-Type: text/plain\r\n\r\nline 1\n\nline 2";
+        let body = extract_body(response).unwrap();
+        assert_eq!(body, b"line 1\n\nline 2");
+
+        let parsed = parse_response(response).unwrap();
+        assert_eq!(parsed.header("content-type"), Some("text/plain"));
+        assert_eq!(parsed.body, "line 1\n\nline 2");
     }
 
     #[test]
     fn test_extract_body_no_separator() {
         let response = "HTTP/1.1 200 OK";
-        let result = extract_body(response);
+        let result = extract_body(response.as_bytes());
         assert!(result.is_err(), "Response without separator should error");
     }
 
     #[test]
     fn test_extract_body_empty_body() {
         let response = "HTTP/1.1 200 OK\r\n\r\n";
-        let body = extract_body(response).unwrap();
-        assert_eq!(body, "");
+        let body = extract_body(response.as_bytes()).unwrap();
+        assert_eq!(body, b"");
+    }
+
+    #[test]
+    fn test_parse_status_line() {
+        assert_eq!(parse_status_line("HTTP/1.1 200 OK").unwrap(), 200);
+        assert_eq!(parse_status_line("HTTP/1.1 404 Not Found").unwrap(), 404);
+        assert!(parse_status_line("garbage").is_err());
+        assert!(parse_status_line("HTTP/1.1 abc").is_err());
+    }
+
+    #[test]
+    fn test_parse_status_line_http_1_0() {
+        assert_eq!(parse_status_line("HTTP/1.0 200 OK").unwrap(), 200);
+        assert_eq!(parse_status_line("HTTP/1.0 302 Found").unwrap(), 302);
+    }
+
+    #[test]
+    fn test_parse_response_http_1_0_without_content_length() {
+        let response = parse_response(
+            b"HTTP/1.0 200 OK\r\nServer: embedded\r\nContent-Type: text/html\r\n\r\n<p>Old</p>\n<p>School</p>",
+        )
+        .unwrap();
+
+        assert_eq!(response.status, 200);
+        assert_eq!(response.header("server"), Some("embedded"));
+        assert_eq!(response.body, "<p>Old</p>\n<p>School</p>");
+    }
+
+    #[test]
+    fn test_parse_response() {
+        let response = "HTTP/1.1 301 Moved Permanently\r\n\
+                       Location: http://example.com/new\r\n\
+                       Content-Type: text/html\r\n\
+                       \r\n\
+                       <html>Moved</html>";
+        let parsed = parse_response(response.as_bytes()).unwrap();
+
+        assert_eq!(parsed.status, 301);
+        assert!(!parsed.is_success());
+        assert_eq!(parsed.header("location"), Some("http://example.com/new"));
+        assert_eq!(parsed.header("Content-Type"), Some("text/html"));
+        assert_eq!(parsed.body, "<html>Moved</html>");
+    }
+
+    #[test]
+    fn test_robots_directives_header() {
+        let response = parse_response(
+            b"HTTP/1.1 200 OK\r\nX-Robots-Tag: noindex, googlebot: nofollow\r\n\r\nbody",
+        )
+        .unwrap();
+
+        let robots = response.robots_directives();
+        assert!(robots.noindex);
+        assert!(!robots.nofollow);
+
+        let plain = parse_response(b"HTTP/1.1 200 OK\r\n\r\nbody").unwrap();
+        assert_eq!(plain.robots_directives(), RobotsDirectives::default());
+    }
+
+    #[test]
+    fn test_mime_type_and_is_html() {
+        let html =
+            parse_response(b"HTTP/1.1 200 OK\r\nContent-Type: Text/HTML; charset=utf-8\r\n\r\n")
+                .unwrap();
+        assert_eq!(html.mime_type(), Some("text/html".to_string()));
+        assert!(html.is_html());
+
+        let pdf = parse_response(b"HTTP/1.1 200 OK\r\nContent-Type: application/pdf\r\n\r\n%PDF")
+            .unwrap();
+        assert!(!pdf.is_html());
+        assert_eq!(pdf.raw_body, b"%PDF");
+
+        let untyped = parse_response(b"HTTP/1.1 200 OK\r\n\r\n").unwrap();
+        assert_eq!(untyped.mime_type(), None);
+        assert!(untyped.is_html());
+    }
+
+    #[test]
+    fn test_mime_type_matches_wildcards() {
+        assert!(mime_type_matches("text/*", "text/html"));
+        assert!(mime_type_matches("text/*", "text/plain"));
+        assert!(!mime_type_matches("text/*", "application/pdf"));
+
+        assert!(mime_type_matches("*/*", "text/html"));
+        assert!(mime_type_matches("*/*", "application/pdf"));
+        assert!(mime_type_matches("*/*", "image/svg+xml"));
+
+        assert!(mime_type_matches(
+            "application/*+xml",
+            "application/atom+xml"
+        ));
+        assert!(!mime_type_matches("application/*+xml", "application/json"));
+        assert!(!mime_type_matches("application/*+xml", "text/xml"));
+
+        assert!(mime_type_matches("TEXT/HTML", "text/html; charset=utf-8"));
+        assert!(!mime_type_matches("text/html", "text/htmlx"));
+        assert!(!mime_type_matches("text", "text/html"));
+    }
+
+    #[test]
+    fn test_matches_content_type() {
+        let rules = vec!["text/*".to_string(), "application/*+xml".to_string()];
+
+        let pdf =
+            parse_response(b"HTTP/1.1 200 OK\r\nContent-Type: application/pdf\r\n\r\n").unwrap();
+        assert!(!pdf.matches_content_type(&rules));
+
+        let feed = parse_response(b"HTTP/1.1 200 OK\r\nContent-Type: application/rss+xml\r\n\r\n")
+            .unwrap();
+        assert!(feed.matches_content_type(&rules));
+
+        let untyped = parse_response(b"HTTP/1.1 200 OK\r\n\r\n").unwrap();
+        assert!(untyped.matches_content_type(&rules));
+        assert!(!untyped.matches_content_type(&[]));
+    }
+
+    #[test]
+    fn test_is_data() {
+        let typed = |content_type: &str| {
+            let raw = format!("HTTP/1.1 200 OK\r\nContent-Type: {}\r\n\r\n", content_type);
+            parse_response(raw.as_bytes()).unwrap()
+        };
+
+        assert!(typed("application/json; charset=utf-8").is_data());
+        assert!(typed("application/ld+json").is_data());
+        assert!(typed("application/rss+xml").is_data());
+        assert!(typed("application/atom+xml").is_data());
+        assert!(!typed("text/html").is_data());
+        assert!(!typed("application/xhtml+xml").is_data());
+        assert!(!typed("application/xml").is_data());
+        assert!(!typed("text/xml").is_data());
+        assert!(!parse_response(b"HTTP/1.1 200 OK\r\n\r\n")
+            .unwrap()
+            .is_data());
+    }
+
+    // ===== Charset Tests =====
+
+    #[test]
+    fn test_charset_from_content_type() {
+        assert_eq!(
+            charset_from_content_type("text/html; charset=windows-1252"),
+            Some("windows-1252")
+        );
+        assert_eq!(
+            charset_from_content_type("text/html;Charset=\"ISO-8859-1\""),
+            Some("ISO-8859-1")
+        );
+        assert_eq!(charset_from_content_type("text/html"), None);
+    }
+
+    #[test]
+    fn test_decode_body_windows_1252() {
+        let body = b"<title>Caf\xe9 cr\xe8me</title>";
+        assert_eq!(
+            decode_body(body, Some("text/html; charset=windows-1252")),
+            "<title>Café crème</title>"
+        );
+    }
+
+    #[test]
+    fn test_decode_body_defaults_to_utf8() {
+        assert_eq!(decode_body("Café".as_bytes(), Some("text/html")), "Café");
+        assert_eq!(decode_body("Café".as_bytes(), None), "Café");
+        assert_eq!(
+            decode_body("Café".as_bytes(), Some("text/html; charset=bogus")),
+            "Café"
+        );
+    }
+
+    #[test]
+    fn test_parse_response_transcodes_body() {
+        let mut response =
+            b"HTTP/1.1 200 OK\r\nContent-Type: text/html; charset=ISO-8859-1\r\n\r\n".to_vec();
+        response.extend_from_slice(b"<p>Gr\xfc\xdfe</p>");
+
+        let parsed = parse_response(&response).unwrap();
+        assert_eq!(parsed.body, "<p>Grüße</p>");
+    }
+
+    #[test]
+    fn test_meta_charset() {
+        assert_eq!(
+            meta_charset(b"<head><meta charset=\"windows-1252\"></head>"),
+            Some(encoding_rs::WINDOWS_1252)
+        );
+        assert_eq!(
+            meta_charset(
+                b"<meta http-equiv=\"Content-Type\" content=\"text/html; charset=ISO-8859-1\">"
+            ),
+            Some(encoding_rs::WINDOWS_1252)
+        );
+        assert_eq!(
+            meta_charset(b"<meta name=\"viewport\" content=\"x\">"),
+            None
+        );
+
+        // Declarations past the prescan window are ignored
+        let mut late = vec![b' '; META_CHARSET_PRESCAN];
+        late.extend_from_slice(b"<meta charset=\"windows-1252\">");
+        assert_eq!(meta_charset(&late), None);
+    }
+
+    #[test]
+    fn test_redecode_with_meta_charset() {
+        let mut response =
+            b"HTTP/1.1 200 OK\r\nContent-Type: text/html; charset=utf-8\r\n\r\n".to_vec();
+        response.extend_from_slice(b"<meta charset=\"windows-1252\"><p>Caf\xe9</p>");
+
+        let mut parsed = parse_response(&response).unwrap();
+        assert_eq!(
+            parsed.body,
+            "<meta charset=\"windows-1252\"><p>Caf\u{fffd}</p>"
+        );
+
+        redecode_with_meta_charset(&mut parsed);
+        assert_eq!(parsed.body, "<meta charset=\"windows-1252\"><p>Café</p>");
+    }
+
+    #[test]
+    fn test_redecode_with_meta_charset_agreeing_header() {
+        let mut response =
+            b"HTTP/1.1 200 OK\r\nContent-Type: text/html; charset=utf-8\r\n\r\n".to_vec();
+        response.extend_from_slice("<meta charset=\"UTF-8\"><p>Café</p>".as_bytes());
+
+        let mut parsed = parse_response(&response).unwrap();
+        redecode_with_meta_charset(&mut parsed);
+        assert_eq!(parsed.body, "<meta charset=\"UTF-8\"><p>Café</p>");
+    }
+
+    #[test]
+    fn test_build_request_default_headers() {
+        let request = build_request("example.com", "/page", &[]);
+        assert!(request.starts_with("GET /page HTTP/1.1\r\n"));
+        assert!(request.contains("Host: example.com\r\n"));
+        assert!(request.contains(&format!("Accept: {}\r\n", DEFAULT_ACCEPT)));
+        assert!(request.ends_with("Connection: close\r\n\r\n"));
+        assert!(!request.contains("Referer:"));
+    }
+
+    #[test]
+    fn test_build_request_custom_accept() {
+        let request = build_request("example.com", "/api", &[("accept", "application/json")]);
+        assert!(request.contains("accept: application/json\r\n"));
+        assert!(!request.contains("Accept:"));
+    }
+
+    #[test]
+    fn test_build_request_extra_headers() {
+        let request = build_request(
+            "example.com",
+            "/child",
+            &[("Referer", "http://example.com/parent")],
+        );
+        assert!(request.contains("Referer: http://example.com/parent\r\n"));
+        assert!(request.ends_with("\r\n\r\n"));
+    }
+
+    #[test]
+    fn test_encode_request_target() {
+        assert_eq!(encode_request_target("/a b/c"), "/a%20b/c");
+        assert_eq!(encode_request_target("/a%20b"), "/a%20b");
+        assert_eq!(encode_request_target("/100%"), "/100%25");
+        assert_eq!(encode_request_target("/café"), "/caf%C3%A9");
+        assert_eq!(
+            encode_request_target("/search?q=a b&tags={x}"),
+            "/search?q=a%20b&tags=%7Bx%7D"
+        );
+
+        let once = encode_request_target("/x y%2F\"z\"");
+        assert_eq!(encode_request_target(&once), once);
+    }
+
+    #[test]
+    fn test_fetch_encodes_unsafe_path() {
+        let server = TestServer::start(vec![("/my%20page", html("<p>Found</p>"))]);
+
+        let response = async_std::task::block_on(fetch_page(
+            &server.url("/my page"),
+            &FetchOptions::default(),
+        ))
+        .unwrap();
+        assert_eq!(response.status, 200);
+        assert!(server.request_for("/my%20page").is_some());
+
+        // Already-encoded paths are sent unchanged
+        async_std::task::block_on(fetch_page(
+            &server.url("/my%20page?x=1"),
+            &FetchOptions::default(),
+        ))
+        .unwrap();
+        assert!(server.request_for("/my%20page?x=1").is_some());
+    }
+
+    // ===== Connection Error Tests =====
+
+    #[test]
+    fn test_fetch_unresolvable_host_is_dns_failure() {
+        let result = async_std::task::block_on(fetch_page(
+            "http://spiderman-test.invalid/",
+            &FetchOptions::default(),
+        ));
+
+        let err = CrawlError::from(result.unwrap_err());
+        assert!(
+            matches!(err, CrawlError::DnsFailure { ref host, .. } if host == "spiderman-test.invalid"),
+            "{:?}",
+            err
+        );
+        assert!(err.to_string().contains("spiderman-test.invalid"));
+    }
+
+    #[test]
+    fn test_fetch_closed_port_is_connection_refused() {
+        // Reserve a free port, then close it so nothing is listening
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let url = format!("http://127.0.0.1:{}/", port);
+
+        let result = async_std::task::block_on(fetch_page(&url, &FetchOptions::default()));
+
+        let err = CrawlError::from(result.unwrap_err());
+        let expected_host = format!("127.0.0.1:{}", port);
+        assert!(
+            matches!(err, CrawlError::ConnectionRefused { ref host, .. } if *host == expected_host),
+            "{:?}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_connection_error_categories() {
+        let reset = connection_error("example.com", io::ErrorKind::ConnectionReset.into());
+        assert!(matches!(reset, CrawlError::ConnectionReset { .. }));
+
+        let other = connection_error("example.com", io::ErrorKind::TimedOut.into());
+        assert!(matches!(other, CrawlError::Io(_)));
+    }
+
+    #[test]
+    fn test_connect_address() {
+        assert_eq!(connect_address("example.com", None), "example.com:80");
+        assert_eq!(connect_address("localhost:8080", None), "localhost:8080");
+        assert_eq!(
+            connect_address("example.com", Some(8080)),
+            "example.com:8080"
+        );
+        assert_eq!(
+            connect_address("localhost:9000", Some(8080)),
+            "localhost:9000"
+        );
+    }
+
+    #[test]
+    fn test_fetch_page_uses_default_port() {
+        let server = TestServer::start(vec![("/page", html("<p>Staging</p>"))]);
+        let port: u16 = server.url("").rsplit(':').next().unwrap().parse().unwrap();
+        let options = FetchOptions {
+            default_port: Some(port),
+            ..FetchOptions::default()
+        };
+
+        let response =
+            async_std::task::block_on(fetch_page("http://127.0.0.1/page", &options)).unwrap();
+
+        assert_eq!(response.body, "<p>Staging</p>");
+        assert!(server.request_for("/page").is_some());
+    }
+
+    #[test]
+    fn test_fetch_page_http_1_0_reads_until_close() {
+        // No Content-Length: the body ends when the server closes the
+        // connection
+        let server = TestServer::start(vec![(
+            "/",
+            b"HTTP/1.0 200 OK\r\nContent-Type: text/html\r\n\r\n<p>Legacy</p>".to_vec(),
+        )]);
+
+        let response =
+            async_std::task::block_on(fetch_page(&server.url("/"), &FetchOptions::default()))
+                .unwrap();
+
+        assert_eq!(response.status, 200);
+        assert!(response.is_html());
+        assert_eq!(response.body, "<p>Legacy</p>");
+    }
+
+    #[test]
+    fn test_fetch_page_sends_option_headers() {
+        let server = TestServer::start(vec![("/", html("ok"))]);
+        let options = FetchOptions::default().with_header("X-Test", "yes");
+
+        async_std::task::block_on(fetch_page(&server.url("/"), &options)).unwrap();
+
+        assert!(server.request_for("/").unwrap().contains("X-Test: yes\r\n"));
+    }
+
+    // ===== Retry-After Tests =====
+
+    #[test]
+    fn test_parse_retry_after_seconds() {
+        let now = Utc::now();
+        assert_eq!(parse_retry_after("5", now), Some(Duration::from_secs(5)));
+        assert_eq!(parse_retry_after(" 0 ", now), Some(Duration::ZERO));
+        assert_eq!(parse_retry_after("-5", now), None);
+        assert_eq!(parse_retry_after("", now), None);
+    }
+
+    #[test]
+    fn test_parse_retry_after_http_date() {
+        let now = DateTime::parse_from_rfc3339("2015-10-21T07:27:30Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT", now),
+            Some(Duration::from_secs(30))
+        );
+        // Dates in the past mean "retry now"
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:00:00 GMT", now),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after("Someday, 21 Oct 2015", now), None);
+    }
+
+    #[test]
+    fn test_retry_after_only_for_429_and_503() {
+        let limited =
+            parse_response(b"HTTP/1.1 429 Too Many Requests\r\nRetry-After: 3\r\n\r\n").unwrap();
+        assert_eq!(limited.retry_after(), Some(Duration::from_secs(3)));
+
+        let unavailable =
+            parse_response(b"HTTP/1.1 503 Service Unavailable\r\nRetry-After: 7\r\n\r\n").unwrap();
+        assert_eq!(unavailable.retry_after(), Some(Duration::from_secs(7)));
+
+        let moved = parse_response(b"HTTP/1.1 301 Moved\r\nRetry-After: 3\r\n\r\n").unwrap();
+        assert_eq!(moved.retry_after(), None);
+
+        let bare = parse_response(b"HTTP/1.1 429 Too Many Requests\r\n\r\n").unwrap();
+        assert_eq!(bare.retry_after(), None);
+    }
+
+    #[test]
+    fn test_fetch_waits_and_retries_on_retry_after() {
+        let server = TestServer::start(vec![(
+            "/busy",
+            response(429, &[("Retry-After", "120")], "slow down"),
+        )]);
+        let options = FetchOptions {
+            max_retries: 2,
+            max_retry_after: Duration::from_millis(50),
+            ..FetchOptions::default()
+        };
+
+        let started = std::time::Instant::now();
+        let (chain, response) =
+            async_std::task::block_on(fetch_following_redirects(&server.url("/busy"), &options))
+                .unwrap();
+
+        // Two capped waits, then the last 429 is returned
+        assert!(started.elapsed() >= Duration::from_millis(100));
+        assert!(started.elapsed() < Duration::from_secs(10));
+        assert_eq!(response.status, 429);
+        assert_eq!(chain, [server.url("/busy")]);
+        assert_eq!(server.requests().len(), 3);
+
+        let no_retry = FetchOptions {
+            max_retries: 0,
+            ..FetchOptions::default()
+        };
+        async_std::task::block_on(fetch_following_redirects(&server.url("/busy"), &no_retry))
+            .unwrap();
+        assert_eq!(server.requests().len(), 4);
+    }
+
+    // ===== Redirect Tests =====
+
+    #[test]
+    fn test_fetch_following_redirects() {
+        let server = TestServer::start(vec![
+            ("/old", response(301, &[("Location", "/middle")], "")),
+            ("/new", html("<p>landed</p>")),
+        ]);
+        server.route(
+            "/middle",
+            response(302, &[("Location", &server.url("/new"))], ""),
+        );
+
+        let (chain, response) = async_std::task::block_on(fetch_following_redirects(
+            &server.url("/old"),
+            &FetchOptions::default(),
+        ))
+        .unwrap();
+
+        assert_eq!(
+            chain,
+            vec![
+                server.url("/old"),
+                server.url("/middle"),
+                server.url("/new")
+            ]
+        );
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body, "<p>landed</p>");
+        assert_eq!(response.final_url, server.url("/new"));
+    }
+
+    #[test]
+    fn test_fetch_following_redirects_without_location() {
+        let server = TestServer::start(vec![("/old", response(301, &[], ""))]);
+
+        let (chain, response) = async_std::task::block_on(fetch_following_redirects(
+            &server.url("/old"),
+            &FetchOptions::default(),
+        ))
+        .unwrap();
+
+        assert_eq!(chain, vec![server.url("/old")]);
+        assert_eq!(response.status, 301);
+        assert_eq!(response.final_url, server.url("/old"));
+    }
+
+    #[test]
+    fn test_fetch_following_redirects_limit() {
+        let server = TestServer::start(vec![
+            ("/a", response(302, &[("Location", "/b")], "")),
+            ("/b", response(302, &[("Location", "/a")], "")),
+        ]);
+
+        let options = FetchOptions {
+            max_redirects: 3,
+            ..FetchOptions::default()
+        };
+        let result =
+            async_std::task::block_on(fetch_following_redirects(&server.url("/a"), &options));
+
+        assert!(result.is_err());
+        assert_eq!(server.requests().len(), 4);
+    }
+
+    #[test]
+    fn test_fetch_following_redirects_to_self() {
+        let server = TestServer::start(vec![
+            ("/page", response(302, &[("Location", "/page")], "")),
+            ("/slash", response(301, &[("Location", "/slash/")], "")),
+            ("/slash/", html("<p>landed</p>")),
+        ]);
+
+        let result = async_std::task::block_on(fetch_following_redirects(
+            &server.url("/page"),
+            &FetchOptions::default(),
+        ));
+
+        let err = CrawlError::from(result.unwrap_err());
+        assert!(matches!(err, CrawlError::RedirectLoop { ref url } if *url == server.url("/page")));
+        assert_eq!(server.requests().len(), 1);
+
+        // Adding a trailing slash is not a loop
+        let (chain, _) = async_std::task::block_on(fetch_following_redirects(
+            &server.url("/slash"),
+            &FetchOptions::default(),
+        ))
+        .unwrap();
+        assert_eq!(chain, vec![server.url("/slash"), server.url("/slash/")]);
     }
 
     #[test]
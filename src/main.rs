@@ -21,10 +21,10 @@ fn main() {
         // .with_allowed_domains(vec!["example.com".to_string()]);
 
         // Create crawler with seed URL
-        let mut spider = Spiderman::new("example.com");
+        let mut spider = Spiderman::with_config("example.com", config);
 
         // Start crawling
-        match spider.crawl(config).await {
+        match spider.run().await {
             Ok(result) => {
                 println!("🎉 Success!");
                 println!();